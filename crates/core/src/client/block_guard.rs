@@ -0,0 +1,210 @@
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+use thiserror::Error;
+
+/// An upstream block inconsistent with the blocks served before it.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum BlockInconsistency {
+    #[error(
+        "block {number} has timestamp {timestamp}, before the timestamp {other_timestamp} of block {other_number}"
+    )]
+    TimestampBeforeEarlierBlock { number: u64, timestamp: u64, other_number: u64, other_timestamp: u64 },
+    #[error("latest block {number} is behind block {head}, served as latest before")]
+    HeadRegression { number: u64, head: u64 },
+}
+
+/// Detects the blocks of the Starknet node which are inconsistent with the blocks it served
+/// before, e.g. after a switch to a misconfigured node or a deep reorg, so that inconsistent
+/// headers are not served to indexers.
+///
+/// The timestamps of the last `window` blocks served are kept, and every new block must be
+/// ordered by timestamp after the blocks below it. A block replacing a block served, or ordered
+/// before the blocks above it, is a reorg which replaced the blocks above it too. The latest block
+/// number may only go backwards by a reorg.
+pub struct BlockGuard {
+    window: u64,
+    state: Mutex<BlockGuardState>,
+    inconsistencies: AtomicU64,
+}
+
+#[derive(Default)]
+struct BlockGuardState {
+    /// The timestamps of the blocks served, by block number.
+    timestamps: BTreeMap<u64, u64>,
+    /// The highest block number served as the latest block.
+    head: Option<u64>,
+}
+
+impl BlockGuard {
+    #[must_use]
+    pub fn new(window: u64) -> Self {
+        Self { window, state: Mutex::new(BlockGuardState::default()), inconsistencies: AtomicU64::new(0) }
+    }
+
+    /// Returns the number of inconsistencies detected so far.
+    pub fn inconsistencies(&self) -> u64 {
+        self.inconsistencies.load(Ordering::Relaxed)
+    }
+
+    /// Checks that a block is ordered by timestamp after the blocks served below it, and records
+    /// it.
+    ///
+    /// A block replacing one with the same number, or before a block above it, e.g. after a reorg,
+    /// overwrites it and the blocks above it are forgotten.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the block is inconsistent, in which case it is not recorded.
+    pub fn check_block(&self, number: u64, timestamp: u64) -> Result<(), BlockInconsistency> {
+        let mut state = self.state.lock().expect("block guard lock poisoned");
+
+        if let Some((&other_number, &other_timestamp)) = state.timestamps.range(..number).next_back() {
+            if other_timestamp > timestamp {
+                return Err(self.inconsistency(BlockInconsistency::TimestampBeforeEarlierBlock {
+                    number,
+                    timestamp,
+                    other_number,
+                    other_timestamp,
+                }));
+            }
+        }
+        let replaced = state.timestamps.get(&number).map_or(false, |&recorded| recorded != timestamp);
+        let before_later_block = state
+            .timestamps
+            .range((Bound::Excluded(number), Bound::Unbounded))
+            .next()
+            .map_or(false, |(_, &other_timestamp)| other_timestamp < timestamp);
+        if replaced || before_later_block {
+            state.timestamps.split_off(&(number + 1));
+        }
+
+        state.timestamps.insert(number, timestamp);
+        // Only the blocks of the window ending at the highest block served are kept
+        let highest = state.timestamps.keys().next_back().copied().unwrap_or(number);
+        let window_start = (highest + 1).saturating_sub(self.window);
+        state.timestamps = state.timestamps.split_off(&window_start);
+        Ok(())
+    }
+
+    /// Checks that the latest block number did not go backwards by more than the window, and
+    /// records it.
+    ///
+    /// A latest block behind by at most the window is a reorg. A deeper regression, e.g. after a
+    /// reset of the chain, is reported once and recorded, so that the next latest blocks are
+    /// checked against it. Either way, the blocks after the latest block are forgotten.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the latest block is behind a latest block served before by more than
+    /// the window.
+    pub fn check_head(&self, number: u64) -> Result<(), BlockInconsistency> {
+        let mut state = self.state.lock().expect("block guard lock poisoned");
+        match state.head.replace(number) {
+            Some(head) if number < head => {
+                state.timestamps.split_off(&(number + 1));
+                if head - number > self.window {
+                    return Err(self.inconsistency(BlockInconsistency::HeadRegression { number, head }));
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn inconsistency(&self, inconsistency: BlockInconsistency) -> BlockInconsistency {
+        self.inconsistencies.fetch_add(1, Ordering::Relaxed);
+        log::warn!("Inconsistent block from the Starknet node: {inconsistency}");
+        inconsistency
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_block() {
+        let guard = BlockGuard::new(10);
+        guard.check_block(5, 100).unwrap();
+        guard.check_block(7, 120).unwrap();
+        // Blocks may share a timestamp
+        guard.check_block(6, 120).unwrap();
+
+        assert_eq!(
+            guard.check_block(8, 110),
+            Err(BlockInconsistency::TimestampBeforeEarlierBlock {
+                number: 8,
+                timestamp: 110,
+                other_number: 7,
+                other_timestamp: 120
+            })
+        );
+        assert_eq!(guard.inconsistencies(), 1);
+
+        // Blocks out of the window are forgotten
+        guard.check_block(20, 200).unwrap();
+        guard.check_block(8, 110).unwrap();
+    }
+
+    #[test]
+    fn test_check_block_forgets_the_blocks_above_a_replaced_block() {
+        let guard = BlockGuard::new(10);
+        guard.check_block(5, 100).unwrap();
+        guard.check_block(6, 110).unwrap();
+        guard.check_block(7, 120).unwrap();
+
+        // The block 6 is replaced by a block after its old successor, which is forgotten
+        guard.check_block(6, 130).unwrap();
+        assert_eq!(guard.inconsistencies(), 0);
+
+        // The old successor is not ordered with the replacing block
+        assert_eq!(
+            guard.check_block(7, 120),
+            Err(BlockInconsistency::TimestampBeforeEarlierBlock {
+                number: 7,
+                timestamp: 120,
+                other_number: 6,
+                other_timestamp: 130
+            })
+        );
+        // A block before a block above it replaces the blocks above it too
+        guard.check_block(7, 140).unwrap();
+        guard.check_block(6, 135).unwrap();
+        guard.check_block(7, 136).unwrap();
+        assert_eq!(guard.inconsistencies(), 1);
+    }
+
+    #[test]
+    fn test_check_head() {
+        let guard = BlockGuard::new(10);
+        guard.check_head(5).unwrap();
+        guard.check_head(5).unwrap();
+        guard.check_head(6).unwrap();
+        // A reorg within the window
+        guard.check_head(4).unwrap();
+        guard.check_head(20).unwrap();
+
+        assert_eq!(guard.check_head(5), Err(BlockInconsistency::HeadRegression { number: 5, head: 20 }));
+        assert_eq!(guard.inconsistencies(), 1);
+        // The chain was reset, the next latest blocks are checked against the new one
+        guard.check_head(6).unwrap();
+        assert_eq!(guard.inconsistencies(), 1);
+    }
+
+    #[test]
+    fn test_check_head_forgets_the_blocks_reverted() {
+        let guard = BlockGuard::new(10);
+        guard.check_block(5, 100).unwrap();
+        guard.check_block(6, 120).unwrap();
+        guard.check_head(6).unwrap();
+
+        guard.check_head(5).unwrap();
+
+        // The new block 6 is not ordered with the block it replaced
+        guard.check_block(6, 101).unwrap();
+        assert_eq!(guard.inconsistencies(), 0);
+    }
+}
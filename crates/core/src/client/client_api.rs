@@ -1,42 +1,89 @@
+use std::time::Duration;
+
 use async_trait::async_trait;
 use eyre::Result;
 // TODO: all reth_primitives::rpc types should be replaced when native reth Log is implemented
 // https://github.com/paradigmxyz/reth/issues/1396#issuecomment-1440890689
-use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, H256, U128, U256, U64};
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bloom, Bytes, H256, U128, U256, U64};
 use reth_rpc_types::{
-    BlockTransactions, CallRequest, FeeHistory, Index, RichBlock, SyncStatus, Transaction as EtherTransaction,
-    TransactionReceipt,
+    BlockTransactions, CallRequest, FeeHistory, Filter, Index, Log, RichBlock, SyncStatus,
+    Transaction as EtherTransaction, TransactionReceipt,
 };
 use starknet::core::types::{BlockId as StarknetBlockId, BroadcastedInvokeTransactionV1, FieldElement};
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
 
+use super::block_guard::BlockGuard;
 use super::errors::EthApiError;
-use crate::models::balance::TokenBalances;
+use super::events::{ChainFollower, EventBus};
+use super::filters::FilterChanges;
+use super::helpers::AddressConversionPolicy;
+use super::watchlist::Watchlist;
+use crate::models::balance::{BalanceChange, TokenBalances};
+use crate::models::block::StarknetBlockInfo;
+use crate::models::chain_metadata::ChainMetadata;
+use crate::models::confirmation::L1Confirmation;
+use crate::models::event::RawKakarotEvent;
+use crate::models::metadata::{GasEstimate, TransactionMetadata};
 use crate::models::transaction::StarknetTransactions;
 
 #[async_trait]
 pub trait KakarotProvider: Send + Sync {
     fn kakarot_address(&self) -> FieldElement;
+    fn is_kakarot_address(&self, address: &FieldElement) -> bool;
     fn proxy_account_class_hash(&self) -> FieldElement;
     fn starknet_provider(&self) -> &JsonRpcClient<HttpTransport>;
+    fn expose_starknet_block_info(&self) -> bool;
+    fn address_conversion_policy(&self) -> AddressConversionPolicy;
+    fn chain_id(&self) -> u64;
+    /// The block read by the requests which do not specify one.
+    fn default_block_id(&self) -> BlockId;
+    /// The time budget of a request, covering all of its Starknet calls.
+    fn request_timeout(&self) -> Duration;
+    fn chain_metadata(&self) -> ChainMetadata;
+    fn events(&self) -> &EventBus;
+    fn block_guard(&self) -> &BlockGuard;
+    fn follower(&self) -> &ChainFollower;
+    fn watchlist(&self) -> &Watchlist;
+
+    async fn resolve_block_id(&self, block_id: BlockId) -> Result<StarknetBlockId, EthApiError>;
 
     async fn block_number(&self) -> Result<U64, EthApiError>;
 
+    async fn is_accepted_on_l1(&self, block_number: u64) -> Result<bool, EthApiError>;
+
     async fn transaction_by_hash(&self, hash: H256) -> Result<EtherTransaction, EthApiError>;
 
+    async fn raw_transaction_by_hash(&self, hash: H256) -> Result<Bytes, EthApiError>;
+
     async fn get_eth_block_from_starknet_block(
         &self,
         block_id: StarknetBlockId,
         hydrated_tx: bool,
     ) -> Result<RichBlock, EthApiError>;
 
+    async fn get_eth_blocks_in_range(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        hydrated_tx: bool,
+    ) -> Result<Vec<RichBlock>, EthApiError>;
+
+    async fn starknet_block_info(&self, block_id: StarknetBlockId) -> Result<StarknetBlockInfo, EthApiError>;
+
     async fn get_code(
         &self,
         ethereum_address: Address,
         starknet_block_id: StarknetBlockId,
     ) -> Result<Bytes, EthApiError>;
 
+    async fn storage_at(
+        &self,
+        ethereum_address: Address,
+        index: U256,
+        starknet_block_id: StarknetBlockId,
+    ) -> Result<H256, EthApiError>;
+
     async fn call_view(
         &self,
         ethereum_address: Address,
@@ -64,14 +111,30 @@ pub trait KakarotProvider: Send + Sync {
 
     async fn submit_starknet_transaction(&self, request: BroadcastedInvokeTransactionV1) -> Result<H256, EthApiError>;
 
+    /// Whether transactions are queued while the Starknet node is unavailable.
+    fn is_submission_queue_enabled(&self) -> bool;
+
+    /// The number of transactions waiting in the submission queue.
+    fn submission_queue_depth(&self) -> usize;
+
+    async fn flush_submission_queue(&self) -> Result<usize, EthApiError>;
+
     async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>, EthApiError>;
 
+    async fn block_receipts(&self, starknet_block_id: StarknetBlockId) -> Result<Vec<TransactionReceipt>, EthApiError>;
+
     async fn get_evm_address(
         &self,
         starknet_address: &FieldElement,
         starknet_block_id: &StarknetBlockId,
     ) -> Result<Address, EthApiError>;
 
+    async fn class_hash_at(
+        &self,
+        starknet_block_id: StarknetBlockId,
+        starknet_address: FieldElement,
+    ) -> Result<FieldElement, EthApiError>;
+
     async fn nonce(&self, ethereum_address: Address, starknet_block_id: StarknetBlockId) -> Result<U256, EthApiError>;
 
     async fn balance(&self, ethereum_address: Address, starknet_block_id: StarknetBlockId)
@@ -81,6 +144,7 @@ pub trait KakarotProvider: Send + Sync {
         &self,
         address: Address,
         contract_addresses: Vec<Address>,
+        block_id: StarknetBlockId,
     ) -> Result<TokenBalances, EthApiError>;
 
     async fn filter_starknet_into_eth_txs(
@@ -92,12 +156,18 @@ pub trait KakarotProvider: Send + Sync {
 
     async fn send_transaction(&self, bytes: Bytes) -> Result<H256, EthApiError>;
 
+    async fn send_unchecked_transaction(&self, bytes: Bytes) -> Result<H256, EthApiError>;
+
+    async fn pending_transactions(&self) -> Result<Vec<EtherTransaction>, EthApiError>;
+
     async fn get_transaction_count_by_block(&self, starknet_block_id: StarknetBlockId) -> Result<U64, EthApiError>;
 
     fn base_fee_per_gas(&self) -> U256;
 
     fn max_priority_fee_per_gas(&self) -> U128;
 
+    async fn suggest_max_priority_fee_per_gas(&self) -> Result<U128, EthApiError>;
+
     async fn fee_history(
         &self,
         _block_count: U256,
@@ -105,6 +175,38 @@ pub trait KakarotProvider: Send + Sync {
         _reward_percentiles: Option<Vec<f64>>,
     ) -> Result<FeeHistory, EthApiError>;
 
-    async fn estimate_gas(&self, call_request: CallRequest, block_number: Option<BlockId>)
-    -> Result<U256, EthApiError>;
+    async fn transaction_metadata(&self, hash: H256) -> Result<Option<TransactionMetadata>, EthApiError>;
+
+    async fn revert_reason(&self, hash: H256) -> Result<Option<String>, EthApiError>;
+
+    async fn estimate_gas(
+        &self,
+        call_request: CallRequest,
+        block_number: Option<BlockId>,
+    ) -> Result<GasEstimate, EthApiError>;
+
+    async fn get_logs(&self, filter: Filter) -> Result<Vec<Log>, EthApiError>;
+
+    async fn block_logs_bloom(&self, starknet_block_id: StarknetBlockId) -> Result<Bloom, EthApiError>;
+
+    async fn new_filter(&self, filter: Filter) -> Result<U64, EthApiError>;
+
+    async fn new_block_filter(&self) -> Result<U64, EthApiError>;
+
+    async fn filter_changes(&self, id: U64) -> Result<FilterChanges, EthApiError>;
+
+    async fn filter_logs(&self, id: U64) -> Result<Vec<Log>, EthApiError>;
+
+    async fn uninstall_filter(&self, id: U64) -> Result<bool, EthApiError>;
+
+    async fn raw_events(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        keys: Option<Vec<Vec<FieldElement>>>,
+    ) -> Result<Vec<RawKakarotEvent>, EthApiError>;
+
+    async fn l1_confirmation(&self, hash: H256) -> Result<Option<L1Confirmation>, EthApiError>;
+
+    async fn balance_changes(&self, hash: H256) -> Result<Option<Vec<BalanceChange>>, EthApiError>;
 }
@@ -1,21 +1,380 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
 use eyre::Result;
+use reth_primitives::{Address, BlockNumberOrTag};
+use serde_json::{json, Value};
 use starknet::core::types::FieldElement;
 
+use super::constants::events::{DEFAULT_EVENTS_BLOCK_SPAN, DEFAULT_EVENTS_PAGE_SIZE};
+use super::constants::requests::{
+    DEFAULT_MAX_TRANSACTION_INPUT_SIZE, DEFAULT_MAX_WATCHED_ADDRESSES, DEFAULT_REQUEST_TIMEOUT_MS,
+};
+use super::constants::{CHAIN_ID, DEFAULT_CHAIN_NAME, DEFAULT_NATIVE_CURRENCY_NAME, DEFAULT_NATIVE_CURRENCY_SYMBOL};
 use super::errors::ConfigError;
+use super::helpers::{AddressConversionPolicy, ConfirmationLevel, KakarotDeployment};
 
 fn get_env_var(name: &str) -> Result<String, ConfigError> {
     std::env::var(name).map_err(|_| ConfigError::EnvironmentVariableMissing(name.into()))
 }
 
+/// Reads an optional environment variable and parses it, falling back to `default` when the
+/// variable is not set.
+fn get_optional_env_var<T: FromStr>(name: &str, default: T) -> Result<T, ConfigError> {
+    match std::env::var(name) {
+        Ok(value) => value
+            .parse::<T>()
+            .map_err(|_| ConfigError::EnvironmentVariableSetWrong(format!("{name} could not be parsed, got {value}"))),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Reads an optional environment variable holding one of the documented `values`, see
+/// [`ConfigValue::OneOf`], and parses it, falling back to `default` when the variable is not set.
+fn get_optional_one_of_env_var<T: FromStr>(name: &str, values: &[&str], default: T) -> Result<T, ConfigError> {
+    match std::env::var(name) {
+        Ok(value) if !values.contains(&value.as_str()) => Err(ConfigError::EnvironmentVariableSetWrong(format!(
+            "{name} should be one of {}, got {value}",
+            values.join(", ")
+        ))),
+        _ => get_optional_env_var(name, default),
+    }
+}
+
+/// Reads an optional comma separated list from an environment variable, empty when the variable
+/// is not set.
+fn get_optional_list_env_var(name: &str) -> Vec<String> {
+    match std::env::var(name) {
+        Ok(list) => list.split(',').map(str::trim).filter(|item| !item.is_empty()).map(ToString::to_string).collect(),
+        Err(_) => vec![],
+    }
+}
+
+/// Matches a hex encoded field element.
+pub const FELT_PATTERN: &str = "^0x[0-9a-fA-F]{1,64}$";
+/// Matches an EVM address.
+pub const ADDRESS_PATTERN: &str = "^0x[0-9a-fA-F]{40}$";
+/// Matches a comma separated list of EVM addresses.
+pub const ADDRESS_LIST_PATTERN: &str = "^\\s*(0x[0-9a-fA-F]{40}\\s*(,\\s*0x[0-9a-fA-F]{40}\\s*)*)?$";
+/// Matches a non-negative integer.
+pub const UNSIGNED_PATTERN: &str = "^[0-9]+$";
+/// Matches a positive integer.
+pub const POSITIVE_PATTERN: &str = "^0*[1-9][0-9]*$";
+pub const BOOLEAN_VALUES: &[&str] = &["true", "false"];
+/// The blocks which the requests not specifying one can be configured to read.
+const DEFAULT_BLOCK_VALUES: &[&str] = &["latest", "pending", "safe", "finalized"];
+
+/// The values accepted by an environment variable of the configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigValue {
+    Text,
+    Pattern(&'static str),
+    OneOf(&'static [&'static str]),
+}
+
+/// An environment variable of the configuration, documented for deployment tooling validating
+/// configurations before rollout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigField {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub required: bool,
+    /// The value used when the variable is not set, if any.
+    pub default: Option<String>,
+    pub value: ConfigValue,
+}
+
+impl ConfigField {
+    #[must_use]
+    pub fn required(name: &'static str, description: &'static str, value: ConfigValue) -> Self {
+        Self { name, description, required: true, default: None, value }
+    }
+
+    #[must_use]
+    pub fn optional(name: &'static str, description: &'static str, default: impl ToString, value: ConfigValue) -> Self {
+        Self { name, description, required: false, default: Some(default.to_string()), value }
+    }
+
+    /// An optional variable, unset by default.
+    #[must_use]
+    pub fn unset(name: &'static str, description: &'static str, value: ConfigValue) -> Self {
+        Self { name, description, required: false, default: None, value }
+    }
+
+    /// Returns the JSON Schema of the variable, whose value is always a string.
+    #[must_use]
+    pub fn json_schema(&self) -> Value {
+        let mut schema = json!({ "type": "string", "description": self.description });
+        if let Some(default) = &self.default {
+            schema["default"] = json!(default);
+        }
+        match self.value {
+            ConfigValue::Text => {}
+            ConfigValue::Pattern(pattern) => schema["pattern"] = json!(pattern),
+            ConfigValue::OneOf(values) => schema["enum"] = json!(values),
+        }
+        schema
+    }
+}
+
+/// Returns the JSON Schema of a configuration made of environment variables, e.g. an env file.
+#[must_use]
+pub fn config_json_schema(fields: &[ConfigField]) -> Value {
+    let properties: serde_json::Map<String, Value> =
+        fields.iter().map(|field| (field.name.to_string(), field.json_schema())).collect();
+    let required: Vec<&str> = fields.iter().filter(|field| field.required).map(|field| field.name).collect();
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "Kakarot RPC configuration",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
 pub struct StarknetConfig {
     pub starknet_rpc: String,
+    /// A Starknet node queried for the blocks and events the primary node is missing, e.g. an
+    /// archive node or a gateway-backed node.
+    pub fallback_starknet_rpc: Option<String>,
     pub kakarot_address: FieldElement,
     pub proxy_account_class_hash: FieldElement,
+    /// Number of events requested per `starknet_getEvents` page.
+    pub events_page_size: u64,
+    /// Number of blocks covered by each concurrently fetched `starknet_getEvents` sub-range.
+    pub events_block_span: u64,
+    /// Whether EVM block responses carry the underlying Starknet block information.
+    pub expose_starknet_block_info: bool,
+    /// Time budget, in milliseconds, of a request, covering all of its Starknet calls. Must be
+    /// greater than 0.
+    pub request_timeout_ms: u64,
+    /// Number of transactions queued while the Starknet node is unavailable, 0 to disable queueing.
+    pub submission_queue_capacity: usize,
+    /// File the submission queue is persisted to, so that the queued transactions survive a
+    /// restart. The queue is only kept in memory if `None`.
+    pub submission_queue_path: Option<PathBuf>,
+    /// The Starknet chain id, from which the hash of a queued transaction is computed while the
+    /// Starknet node is unavailable. Required when the submission queue is enabled.
+    pub starknet_chain_id: Option<FieldElement>,
+    /// EVM ERC-20 representing the native balance, if any. When set, `eth_getBalance` returns
+    /// the balance of this token instead of the Starknet fee token balance.
+    pub wrapped_native_token: Option<Address>,
+    /// How Starknet addresses without an EVM equivalent which do not fit in 20 bytes are
+    /// converted, e.g. the sequencer address reported as the block miner.
+    pub address_conversion_policy: AddressConversionPolicy,
+    /// Whether raw transactions are simulated before submission, so that the ones which would
+    /// fail are rejected.
+    pub prevalidate_transactions: bool,
+    /// The Starknet status from which transactions get a receipt and blocks are `finalized`.
+    pub confirmation_level: ConfirmationLevel,
+    /// The EVM chain id, returned by `eth_chainId` and expected in signed transactions.
+    pub chain_id: u64,
+    /// Maximum size, in bytes, of the input of raw transactions.
+    pub max_transaction_input_size: usize,
+    /// The deployments of Kakarot core which served older blocks, queried instead of
+    /// `kakarot_address` for these blocks.
+    pub previous_deployments: Vec<KakarotDeployment>,
+    /// The name of the chain, returned by `kakarot_getChainMetadata`.
+    pub chain_name: String,
+    /// The public RPC URLs of the chain, returned by `kakarot_getChainMetadata`. EIP-3085
+    /// requires at least one, see [`StarknetConfig::with_fallback_rpc_url`].
+    pub rpc_urls: Vec<String>,
+    /// The block explorer URLs of the chain, returned by `kakarot_getChainMetadata`.
+    pub block_explorer_urls: Vec<String>,
+    /// The name and symbol of the native currency, returned by `kakarot_getChainMetadata`.
+    pub native_currency_name: String,
+    pub native_currency_symbol: String,
+    /// The addresses whose activity is recorded and broadcast by the chain follower, e.g. for
+    /// custodial monitoring.
+    pub watched_addresses: Vec<Address>,
+    /// Maximum number of watched addresses, including the ones watched through the admin API.
+    pub max_watched_addresses: usize,
+    /// The block read by the requests which do not specify one, e.g. `eth_getBalance` without a
+    /// block parameter.
+    pub default_block: BlockNumberOrTag,
 }
 
 impl StarknetConfig {
     pub fn new(starknet_rpc: &str, kakarot_address: FieldElement, proxy_account_class_hash: FieldElement) -> Self {
-        StarknetConfig { starknet_rpc: String::from(starknet_rpc), kakarot_address, proxy_account_class_hash }
+        StarknetConfig {
+            starknet_rpc: String::from(starknet_rpc),
+            fallback_starknet_rpc: None,
+            kakarot_address,
+            proxy_account_class_hash,
+            events_page_size: DEFAULT_EVENTS_PAGE_SIZE,
+            events_block_span: DEFAULT_EVENTS_BLOCK_SPAN,
+            expose_starknet_block_info: false,
+            request_timeout_ms: DEFAULT_REQUEST_TIMEOUT_MS,
+            submission_queue_capacity: 0,
+            submission_queue_path: None,
+            starknet_chain_id: None,
+            wrapped_native_token: None,
+            address_conversion_policy: AddressConversionPolicy::default(),
+            prevalidate_transactions: false,
+            confirmation_level: ConfirmationLevel::default(),
+            chain_id: CHAIN_ID,
+            max_transaction_input_size: DEFAULT_MAX_TRANSACTION_INPUT_SIZE,
+            previous_deployments: vec![],
+            chain_name: DEFAULT_CHAIN_NAME.to_string(),
+            rpc_urls: vec![],
+            block_explorer_urls: vec![],
+            native_currency_name: DEFAULT_NATIVE_CURRENCY_NAME.to_string(),
+            native_currency_symbol: DEFAULT_NATIVE_CURRENCY_SYMBOL.to_string(),
+            watched_addresses: vec![],
+            max_watched_addresses: DEFAULT_MAX_WATCHED_ADDRESSES,
+            default_block: BlockNumberOrTag::Latest,
+        }
+    }
+
+    /// Falls back to `rpc_url`, e.g. the URL of the adapter itself, when no public RPC URL is
+    /// configured, as EIP-3085 rejects chain metadata without RPC URLs.
+    #[must_use]
+    pub fn with_fallback_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        if self.rpc_urls.is_empty() {
+            self.rpc_urls = vec![rpc_url.into()];
+        }
+        self
+    }
+
+    /// Documents the environment variables read by [`StarknetConfig::from_env`].
+    #[must_use]
+    pub fn document() -> Vec<ConfigField> {
+        use ConfigValue::{OneOf, Pattern, Text};
+        vec![
+            ConfigField::required("STARKNET_RPC_URL", "Starknet RPC URL", Text),
+            ConfigField::unset(
+                "STARKNET_FALLBACK_RPC_URL",
+                "Starknet RPC URL queried for the blocks and events the primary node is missing",
+                Text,
+            ),
+            ConfigField::required("KAKAROT_ADDRESS", "Kakarot address", Pattern(FELT_PATTERN)),
+            ConfigField::required("PROXY_ACCOUNT_CLASS_HASH", "Proxy account class hash", Pattern(FELT_PATTERN)),
+            ConfigField::optional(
+                "STARKNET_EVENTS_PAGE_SIZE",
+                "Number of events requested per `starknet_getEvents` page",
+                DEFAULT_EVENTS_PAGE_SIZE,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "STARKNET_EVENTS_BLOCK_SPAN",
+                "Number of blocks per concurrently fetched `starknet_getEvents` sub-range",
+                DEFAULT_EVENTS_BLOCK_SPAN,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "STARKNET_EXPOSE_BLOCK_INFO",
+                "Add the underlying Starknet block to EVM block responses under `starknet`",
+                false,
+                OneOf(BOOLEAN_VALUES),
+            ),
+            ConfigField::optional(
+                "STARKNET_REQUEST_TIMEOUT_MS",
+                "Time budget of a request, covering all of its Starknet calls",
+                DEFAULT_REQUEST_TIMEOUT_MS,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "STARKNET_SUBMISSION_QUEUE_CAPACITY",
+                "Transactions queued while the Starknet node is unavailable, 0 disables queueing",
+                0,
+                Pattern(UNSIGNED_PATTERN),
+            ),
+            ConfigField::unset(
+                "STARKNET_SUBMISSION_QUEUE_PATH",
+                "File the submission queue is persisted to, the queue is only kept in memory if unset",
+                Text,
+            ),
+            ConfigField::unset(
+                "STARKNET_CHAIN_ID",
+                "Starknet chain id, e.g. 0x534e5f474f45524c49 for SN_GOERLI, required by the submission queue",
+                Pattern(FELT_PATTERN),
+            ),
+            ConfigField::unset(
+                "KAKAROT_WRAPPED_NATIVE_TOKEN",
+                "EVM ERC-20 whose balance is returned by `eth_getBalance` instead of the fee token",
+                Pattern(ADDRESS_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_ADDRESS_CONVERSION_POLICY",
+                "Conversion of Starknet addresses wider than 20 bytes",
+                "truncate",
+                OneOf(&["truncate", "zero", "zero_address", "error"]),
+            ),
+            ConfigField::optional(
+                "KAKAROT_PREVALIDATE_TRANSACTIONS",
+                "Simulate raw transactions before submission and reject the ones which would fail",
+                false,
+                OneOf(BOOLEAN_VALUES),
+            ),
+            ConfigField::optional(
+                "KAKAROT_CONFIRMATION_LEVEL",
+                "Status from which transactions get a receipt and blocks are `finalized`",
+                "l2",
+                OneOf(&["l2", "accepted_on_l2", "l1", "accepted_on_l1"]),
+            ),
+            ConfigField::optional(
+                "KAKAROT_CHAIN_ID",
+                "EVM chain id returned by `eth_chainId` and expected in signed transactions",
+                CHAIN_ID,
+                Pattern(UNSIGNED_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_MAX_TRANSACTION_INPUT_SIZE",
+                "Maximum size, in bytes, of the input of raw transactions",
+                DEFAULT_MAX_TRANSACTION_INPUT_SIZE,
+                Pattern(UNSIGNED_PATTERN),
+            ),
+            ConfigField::unset(
+                "KAKAROT_PREVIOUS_DEPLOYMENTS",
+                "Comma separated `address:from_block-to_block` Kakarot deployments serving older blocks",
+                Text,
+            ),
+            ConfigField::optional(
+                "KAKAROT_CHAIN_NAME",
+                "Chain name returned by `kakarot_getChainMetadata`",
+                DEFAULT_CHAIN_NAME,
+                Text,
+            ),
+            ConfigField::unset(
+                "KAKAROT_PUBLIC_RPC_URLS",
+                "Comma separated public RPC URLs returned by `kakarot_getChainMetadata`, the adapter URL if unset",
+                Text,
+            ),
+            ConfigField::unset(
+                "KAKAROT_BLOCK_EXPLORER_URLS",
+                "Comma separated block explorer URLs returned by `kakarot_getChainMetadata`",
+                Text,
+            ),
+            ConfigField::optional(
+                "KAKAROT_NATIVE_CURRENCY_NAME",
+                "Native currency name returned by `kakarot_getChainMetadata`",
+                DEFAULT_NATIVE_CURRENCY_NAME,
+                Text,
+            ),
+            ConfigField::optional(
+                "KAKAROT_NATIVE_CURRENCY_SYMBOL",
+                "Native currency symbol returned by `kakarot_getChainMetadata`",
+                DEFAULT_NATIVE_CURRENCY_SYMBOL,
+                Text,
+            ),
+            ConfigField::unset(
+                "KAKAROT_WATCHED_ADDRESSES",
+                "Comma separated EVM addresses whose activity is recorded",
+                Pattern(ADDRESS_LIST_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_MAX_WATCHED_ADDRESSES",
+                "Maximum number of watched addresses, including the ones watched through the admin API",
+                DEFAULT_MAX_WATCHED_ADDRESSES,
+                Pattern(UNSIGNED_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_DEFAULT_BLOCK",
+                "Block read by the requests which do not specify one",
+                "latest",
+                OneOf(DEFAULT_BLOCK_VALUES),
+            ),
+        ]
     }
 
     pub fn from_env() -> Result<Self, ConfigError> {
@@ -35,6 +394,124 @@ impl StarknetConfig {
             ))
         })?;
 
-        Ok(StarknetConfig::new(&starknet_rpc_url, kakarot_address, proxy_account_class_hash))
+        let events_page_size = get_optional_env_var("STARKNET_EVENTS_PAGE_SIZE", DEFAULT_EVENTS_PAGE_SIZE)?;
+        let events_block_span = get_optional_env_var("STARKNET_EVENTS_BLOCK_SPAN", DEFAULT_EVENTS_BLOCK_SPAN)?;
+        if events_page_size == 0 || events_block_span == 0 {
+            return Err(ConfigError::EnvironmentVariableSetWrong(
+                "STARKNET_EVENTS_PAGE_SIZE and STARKNET_EVENTS_BLOCK_SPAN should be greater than 0".into(),
+            ));
+        }
+
+        let fallback_starknet_rpc = std::env::var("STARKNET_FALLBACK_RPC_URL").ok();
+        let expose_starknet_block_info = get_optional_env_var("STARKNET_EXPOSE_BLOCK_INFO", false)?;
+        let request_timeout_ms = get_optional_env_var("STARKNET_REQUEST_TIMEOUT_MS", DEFAULT_REQUEST_TIMEOUT_MS)?;
+        if request_timeout_ms == 0 {
+            return Err(ConfigError::EnvironmentVariableSetWrong(
+                "STARKNET_REQUEST_TIMEOUT_MS should be greater than 0".into(),
+            ));
+        }
+        let submission_queue_capacity = get_optional_env_var("STARKNET_SUBMISSION_QUEUE_CAPACITY", 0)?;
+        let submission_queue_path = std::env::var("STARKNET_SUBMISSION_QUEUE_PATH").ok().map(PathBuf::from);
+        let starknet_chain_id = match std::env::var("STARKNET_CHAIN_ID") {
+            Ok(chain_id) => Some(FieldElement::from_hex_be(&chain_id).map_err(|_| {
+                ConfigError::EnvironmentVariableSetWrong(format!(
+                    "STARKNET_CHAIN_ID should be provided as a hex string, got {chain_id}"
+                ))
+            })?),
+            Err(_) => None,
+        };
+        // The hashes of queued transactions are computed while the node, which knows the chain
+        // id, is unavailable
+        if submission_queue_capacity > 0 && starknet_chain_id.is_none() {
+            return Err(ConfigError::EnvironmentVariableSetWrong(
+                "STARKNET_CHAIN_ID should be set when STARKNET_SUBMISSION_QUEUE_CAPACITY is greater than 0".into(),
+            ));
+        }
+        let wrapped_native_token = match std::env::var("KAKAROT_WRAPPED_NATIVE_TOKEN") {
+            Ok(token) => Some(Address::from_str(&token).map_err(|_| {
+                ConfigError::EnvironmentVariableSetWrong(format!(
+                    "KAKAROT_WRAPPED_NATIVE_TOKEN should be provided as an EVM address, got {token}"
+                ))
+            })?),
+            Err(_) => None,
+        };
+
+        let address_conversion_policy =
+            get_optional_env_var("KAKAROT_ADDRESS_CONVERSION_POLICY", AddressConversionPolicy::default())?;
+        let prevalidate_transactions = get_optional_env_var("KAKAROT_PREVALIDATE_TRANSACTIONS", false)?;
+        let confirmation_level = get_optional_env_var("KAKAROT_CONFIRMATION_LEVEL", ConfirmationLevel::default())?;
+        let chain_id = get_optional_env_var("KAKAROT_CHAIN_ID", CHAIN_ID)?;
+        let max_transaction_input_size =
+            get_optional_env_var("KAKAROT_MAX_TRANSACTION_INPUT_SIZE", DEFAULT_MAX_TRANSACTION_INPUT_SIZE)?;
+        let previous_deployments = match std::env::var("KAKAROT_PREVIOUS_DEPLOYMENTS") {
+            Ok(deployments) => deployments
+                .split(',')
+                .filter(|deployment| !deployment.trim().is_empty())
+                .map(|deployment| {
+                    KakarotDeployment::from_str(deployment).map_err(|err| {
+                        ConfigError::EnvironmentVariableSetWrong(format!("KAKAROT_PREVIOUS_DEPLOYMENTS: {err}"))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            Err(_) => vec![],
+        };
+
+        let chain_name = get_optional_env_var("KAKAROT_CHAIN_NAME", DEFAULT_CHAIN_NAME.to_string())?;
+        let rpc_urls = get_optional_list_env_var("KAKAROT_PUBLIC_RPC_URLS");
+        let block_explorer_urls = get_optional_list_env_var("KAKAROT_BLOCK_EXPLORER_URLS");
+        let native_currency_name =
+            get_optional_env_var("KAKAROT_NATIVE_CURRENCY_NAME", DEFAULT_NATIVE_CURRENCY_NAME.to_string())?;
+        let native_currency_symbol =
+            get_optional_env_var("KAKAROT_NATIVE_CURRENCY_SYMBOL", DEFAULT_NATIVE_CURRENCY_SYMBOL.to_string())?;
+
+        let watched_addresses = get_optional_list_env_var("KAKAROT_WATCHED_ADDRESSES")
+            .into_iter()
+            .map(|address| {
+                Address::from_str(&address).map_err(|_| {
+                    ConfigError::EnvironmentVariableSetWrong(format!(
+                        "KAKAROT_WATCHED_ADDRESSES should be provided as EVM addresses, got {address}"
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let max_watched_addresses =
+            get_optional_env_var("KAKAROT_MAX_WATCHED_ADDRESSES", DEFAULT_MAX_WATCHED_ADDRESSES)?;
+        if watched_addresses.len() > max_watched_addresses {
+            return Err(ConfigError::EnvironmentVariableSetWrong(format!(
+                "KAKAROT_WATCHED_ADDRESSES holds {} addresses, more than KAKAROT_MAX_WATCHED_ADDRESSES {}",
+                watched_addresses.len(),
+                max_watched_addresses
+            )));
+        }
+
+        let default_block =
+            get_optional_one_of_env_var("KAKAROT_DEFAULT_BLOCK", DEFAULT_BLOCK_VALUES, BlockNumberOrTag::Latest)?;
+
+        Ok(StarknetConfig {
+            fallback_starknet_rpc,
+            events_page_size,
+            events_block_span,
+            expose_starknet_block_info,
+            request_timeout_ms,
+            submission_queue_capacity,
+            submission_queue_path,
+            starknet_chain_id,
+            wrapped_native_token,
+            address_conversion_policy,
+            prevalidate_transactions,
+            confirmation_level,
+            chain_id,
+            max_transaction_input_size,
+            previous_deployments,
+            chain_name,
+            rpc_urls,
+            block_explorer_urls,
+            native_currency_name,
+            native_currency_symbol,
+            watched_addresses,
+            max_watched_addresses,
+            default_block,
+            ..StarknetConfig::new(&starknet_rpc_url, kakarot_address, proxy_account_class_hash)
+        })
     }
 }
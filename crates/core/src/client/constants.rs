@@ -4,6 +4,16 @@ use starknet::core::types::FieldElement;
 
 pub const CHAIN_ID: u64 = 1_263_227_476;
 
+/// The chain name returned by `kakarot_getChainMetadata` when none is configured.
+pub const DEFAULT_CHAIN_NAME: &str = "Kakarot";
+
+/// The native currency returned by `kakarot_getChainMetadata` when none is configured.
+pub const DEFAULT_NATIVE_CURRENCY_NAME: &str = "Ether";
+pub const DEFAULT_NATIVE_CURRENCY_SYMBOL: &str = "ETH";
+
+/// The decimals of the native currency, which wallets only accept as 18, like Ether.
+pub const NATIVE_CURRENCY_DECIMALS: u8 = 18;
+
 pub const STARKNET_NATIVE_TOKEN: &str = "0x49d36570d4e46f48e99674bd3fcc84644ddd6b96f7c741b1562b82f9e004dc7";
 
 pub mod selectors {
@@ -12,6 +22,7 @@ pub mod selectors {
 
     pub const GET_STARKNET_CONTRACT_ADDRESS: FieldElement = selector!("get_starknet_contract_address");
     pub const BYTECODE: FieldElement = selector!("bytecode");
+    pub const STORAGE: FieldElement = selector!("storage");
 
     pub const ETH_CALL: FieldElement = selector!("eth_call");
     pub const ETH_SEND_TRANSACTION: FieldElement = selector!("eth_send_transaction");
@@ -19,7 +30,17 @@ pub mod selectors {
 
     pub const GET_EVM_ADDRESS: FieldElement = selector!("get_evm_address");
 
+    /// Key of the event emitted by Kakarot when a transaction deploys an EVM contract.
+    pub const EVM_CONTRACT_DEPLOYED: FieldElement = selector!("evm_contract_deployed");
+
     pub const BALANCE_OF: FieldElement = selector!("balanceOf");
+
+    /// Key of the event emitted by the native token on transfers, including fee payments.
+    pub const TRANSFER: FieldElement = selector!("Transfer");
+
+    /// Key of the event emitted by the Kakarot account of the sender with the result of the EVM
+    /// execution of its transaction.
+    pub const TRANSACTION_EXECUTED: FieldElement = selector!("transaction_executed");
 }
 
 /// This module contains constants related to EVM gas fees.
@@ -32,21 +53,148 @@ pub mod gas {
     /// TODO: Get Starknet "historical" Gas Price instead
     pub const BASE_FEE_PER_GAS: u64 = 1;
 
-    /// The maximum priority fee for a transaction in gwei.
+    /// The maximum priority fee for a transaction in gwei, suggested until the gas oracle sees a
+    /// transaction.
     ///
     /// This fee is the maximum amount a user is willing to pay to have their transaction
     /// included in a block quickly.
     ///
     /// Since Starknet does not currently have a market for gas fees, transactions are processed
     /// on a "first come first served" basis by the Sequencer.
-    /// As a result, the priority fee is set to 0.
+    /// As a result, the priority fee defaults to 0.
     pub const MAX_PRIORITY_FEE_PER_GAS: U128 = U128::ZERO;
+
+    /// Number of recent blocks whose priority fees are sampled by the gas oracle.
+    pub const GAS_ORACLE_BLOCKS: u64 = 20;
+
+    /// Number of blocks the gas oracle fetches at most per suggestion, so that the first
+    /// suggestions do not fetch the whole window within a single request.
+    pub const GAS_ORACLE_BLOCKS_PER_SUGGESTION: u64 = 4;
+
+    /// Percentile of the sampled priority fees suggested by the gas oracle.
+    pub const GAS_ORACLE_PERCENTILE: f64 = 60.0;
+
+    /// The gas limit of transactions built by the transaction builder when none is set.
+    ///
+    /// Kakarot does not meter gas against the Starknet fee, so a fixed limit equal to the
+    /// reported block gas limit keeps transactions deterministic without an estimation round trip.
+    pub const DEFAULT_TRANSACTION_GAS_LIMIT: u64 = 1_000_000;
+
+    /// Intrinsic gas of any transaction.
+    pub const TX_BASE_GAS: u64 = 21_000;
+    /// Additional intrinsic gas of a contract deployment.
+    pub const TX_CREATE_GAS: u64 = 32_000;
+    /// Intrinsic gas per zero byte of input.
+    pub const TX_DATA_ZERO_GAS: u64 = 4;
+    /// Intrinsic gas per non-zero byte of input, as of EIP-2028.
+    pub const TX_DATA_NON_ZERO_GAS: u64 = 16;
+    /// Intrinsic gas per address of the access list, as of EIP-2930.
+    pub const TX_ACCESS_LIST_ADDRESS_GAS: u64 = 2_400;
+    /// Intrinsic gas per storage key of the access list, as of EIP-2930.
+    pub const TX_ACCESS_LIST_STORAGE_KEY_GAS: u64 = 1_900;
+}
+
+/// This module contains constants related to the retrieval of Starknet events.
+pub mod events {
+    /// The default number of events requested per `starknet_getEvents` page.
+    ///
+    /// Most Starknet nodes cap the chunk size at 1024 events per page.
+    pub const DEFAULT_EVENTS_PAGE_SIZE: u64 = 1_000;
+
+    /// The default number of blocks covered by a single `starknet_getEvents` sub-range.
+    ///
+    /// Large block ranges are split into disjoint sub-ranges of this size, which are fetched
+    /// concurrently.
+    pub const DEFAULT_EVENTS_BLOCK_SPAN: u64 = 1_000;
+
+    /// The maximum number of topics of an EVM log (`LOG0` to `LOG4`).
+    pub const MAX_LOG_TOPICS: usize = 4;
+}
+
+/// This module contains constants related to the handling of requests.
+pub mod requests {
+    /// The default time budget, in milliseconds, of a request fanning out to many Starknet calls,
+    /// such as a hydrated block or a log query.
+    pub const DEFAULT_REQUEST_TIMEOUT_MS: u64 = 30_000;
+
+    /// Interval, in milliseconds, at which queued transactions are submitted again.
+    pub const SUBMISSION_QUEUE_FLUSH_INTERVAL_MS: u64 = 1_000;
+
+    /// The maximum number of Starknet calls a single request runs concurrently.
+    pub const MAX_CONCURRENT_REQUESTS: usize = 16;
+
+    /// Number of recent blocks whose transaction positions are cached for their receipts.
+    pub const TRANSACTION_POSITIONS_CACHE_SIZE: usize = 16;
+
+    /// The maximum number of blocks covered by a single `eth_getLogs` request.
+    pub const MAX_LOGS_BLOCK_RANGE: u64 = 10_000;
+
+    /// The maximum number of blocks returned by a single `kakarot_getBlockRange` request.
+    pub const MAX_BLOCK_RANGE: u64 = 100;
+
+    /// Time, in seconds, after which a filter which is not polled is uninstalled.
+    pub const FILTER_TIMEOUT_SECS: u64 = 300;
+
+    /// The maximum number of filters installed at once, across all clients.
+    pub const MAX_FILTERS: usize = 1_000;
+
+    /// Interval, in milliseconds, at which the Starknet node is polled for new blocks while
+    /// subscriptions are open.
+    pub const NEW_HEADS_POLL_INTERVAL_MS: u64 = 1_000;
+
+    /// Number of recent blocks whose timestamps new blocks are checked against, which is also the
+    /// deepest reorg the latest block may go back by.
+    pub const BLOCK_GUARD_WINDOW: u64 = 1_024;
+
+    /// Number of recent activities kept for each watched address.
+    pub const WATCHLIST_HISTORY_SIZE: usize = 1_000;
+
+    /// The default maximum number of watched addresses.
+    pub const DEFAULT_MAX_WATCHED_ADDRESSES: usize = 1_000;
+
+    /// Time budget, in milliseconds, of the delivery of a watchlist event to a webhook.
+    pub const WEBHOOK_TIMEOUT_MS: u64 = 5_000;
+
+    /// The default maximum size, in bytes, of the input of a raw transaction.
+    pub const DEFAULT_MAX_TRANSACTION_INPUT_SIZE: usize = 128 * 1024;
 }
 
 /// This module contains error messages related to Kakarot.
 pub mod error_messages {
     /// Error message when a transaction is not part of Kakarot.
     pub const INVALID_TRANSACTION_TYPE: &str = "L1Handler, Declare, Deploy and DeployAccount transactions unsupported";
+
+    /// Fragments of the upstream error messages returned when the node or sequencer already
+    /// received the exact same transaction, e.g. when a submission is retried.
+    pub const DUPLICATE_TRANSACTION: [&str; 3] = ["duplicate", "already exists", "already in the mempool"];
+
+    /// Error message, as returned by Ethereum nodes, when the gas limit of a transaction does not
+    /// cover its intrinsic gas.
+    pub const INTRINSIC_GAS_TOO_LOW: &str = "intrinsic gas too low";
+
+    /// Error message, as returned by Ethereum nodes, when the input of a transaction exceeds the
+    /// maximum size.
+    pub const OVERSIZED_DATA: &str = "oversized data";
+
+    /// Error message, as returned by Ethereum nodes, when the nonce of a transaction was already
+    /// used by its sender.
+    pub const NONCE_TOO_LOW: &str = "nonce too low";
+
+    /// Error message, as returned by Ethereum nodes, when the sender cannot pay for the gas and
+    /// the value of a transaction.
+    pub const INSUFFICIENT_FUNDS: &str = "insufficient funds for gas * price + value";
+
+    /// Error message, as returned by Ethereum nodes, when the sender cannot pay for the value of a
+    /// transaction.
+    pub const INSUFFICIENT_FUNDS_FOR_TRANSFER: &str = "insufficient funds for transfer";
+
+    /// Error message, as returned by Ethereum nodes, when the max fee per gas of a transaction
+    /// does not cover the base fee.
+    pub const FEE_CAP_TOO_LOW: &str = "max fee per gas less than block base fee";
+
+    /// Error message, as returned by Ethereum nodes, when the max priority fee per gas of an
+    /// EIP-1559 transaction exceeds its max fee per gas.
+    pub const TIP_ABOVE_FEE_CAP: &str = "max priority fee per gas higher than max fee per gas";
 }
 
 // This module contains constants which are being used in place of real data that should be fetched
@@ -59,6 +207,8 @@ lazy_static! {
     pub static ref SIZE: Option<U256> = Some(U256::from(1_000_000u64));
     pub static ref MAX_FEE: FieldElement = FieldElement::from(100_000_000_000_000_000u64);
     pub static ref ESTIMATE_GAS: U256 = U256::from(100_000_000_000_000_000u64);
+    /// Added to `ESTIMATE_GAS` for the transactions deploying their sender account.
+    pub static ref ACCOUNT_DEPLOYMENT_GAS: U256 = U256::from(32_000u64);
     pub static ref TRANSACTION_TYPE: U8 = U8::from(0);
     pub static ref NONCE: Option<H64> = Some(H64::zero());
     pub static ref MIX_HASH: H256 = H256::zero();
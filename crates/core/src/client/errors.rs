@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, INVALID_PARAMS_CODE, SERVER_IS_BUSY_CODE, UNKNOWN_ERROR_CODE};
 use jsonrpsee::types::ErrorObject;
 use starknet::core::types::StarknetError;
@@ -5,7 +7,10 @@ use starknet::providers::jsonrpc::JsonRpcClientError;
 use starknet::providers::ProviderError;
 use thiserror::Error;
 
+use super::block_guard::BlockInconsistency;
+use super::filters::TooManyFilters;
 use super::helpers::DataDecodingError;
+use crate::codec::CodecError;
 use crate::models::ConversionError;
 
 /// List of JSON-RPC error codes from reth
@@ -21,6 +26,8 @@ pub enum EthRpcErrorCode {
     ResourceNotFound = -32001,
     /// Failed to send transaction, See also <https://github.com/MetaMask/eth-rpc-errors/blob/main/src/error-constants.ts>
     TransactionRejected = -32003,
+    /// Request exceeds a limit of the node, <https://eips.ethereum.org/EIPS/eip-1474#error-codes>
+    LimitExceeded = -32005,
 }
 
 // Error that can accure when preparing configuration.
@@ -45,6 +52,30 @@ pub enum EthApiError {
     /// Data decoding into ETH types failed.
     #[error(transparent)]
     DataDecodingError(#[from] DataDecodingError),
+    /// Decoding of a raw EVM transaction failed.
+    #[error("Kakarot send_transaction: {0}")]
+    CodecError(#[from] CodecError),
+    /// A Kakarot transaction read from Starknet does not wrap a decodable EVM transaction.
+    #[error("Kakarot Core: stored transaction cannot be decoded, {0}")]
+    TransactionDecodingError(CodecError),
+    /// The Starknet node served a block inconsistent with the blocks it served before.
+    #[error("Kakarot Core: inconsistent upstream block, {0}")]
+    InconsistentBlock(#[from] BlockInconsistency),
+    /// The parameters of the request are invalid.
+    #[error("{0}")]
+    InvalidParams(String),
+    /// The request did not complete within its time budget.
+    #[error("request exceeded its {0:?} deadline")]
+    RequestTimeout(Duration),
+    /// Starknet rejected a transaction after its hash was returned to its sender.
+    #[error("{0}")]
+    TransactionRejected(String),
+    /// No filter with this id is installed, or it expired.
+    #[error("filter not found")]
+    FilterNotFound,
+    /// Too many filters are installed to install another one.
+    #[error(transparent)]
+    TooManyFilters(#[from] TooManyFilters),
     /// Other error.
     #[error(transparent)]
     OtherError(#[from] anyhow::Error),
@@ -83,6 +114,22 @@ impl From<EthApiError> for ErrorObject<'static> {
             },
             EthApiError::ConversionError(err) => rpc_err(INTERNAL_ERROR_CODE, err.to_string()),
             EthApiError::DataDecodingError(err) => rpc_err(INTERNAL_ERROR_CODE, err.to_string()),
+            EthApiError::CodecError(err) => {
+                rpc_err(EthRpcErrorCode::InvalidInput as i32, format!("Kakarot send_transaction: {err}"))
+            }
+            EthApiError::TransactionDecodingError(err) => {
+                rpc_err(INTERNAL_ERROR_CODE, format!("Kakarot Core: stored transaction cannot be decoded, {err}"))
+            }
+            EthApiError::InconsistentBlock(err) => {
+                rpc_err(INTERNAL_ERROR_CODE, format!("Kakarot Core: inconsistent upstream block, {err}"))
+            }
+            EthApiError::InvalidParams(msg) => rpc_err(INVALID_PARAMS_CODE, msg),
+            EthApiError::RequestTimeout(timeout) => {
+                rpc_err(SERVER_IS_BUSY_CODE, format!("request exceeded its {timeout:?} deadline"))
+            }
+            EthApiError::TransactionRejected(msg) => rpc_err(EthRpcErrorCode::TransactionRejected as i32, msg),
+            EthApiError::FilterNotFound => rpc_err(EthRpcErrorCode::InvalidInput as i32, "filter not found"),
+            EthApiError::TooManyFilters(err) => rpc_err(EthRpcErrorCode::LimitExceeded as i32, err.to_string()),
             EthApiError::OtherError(err) => rpc_err(INTERNAL_ERROR_CODE, err.to_string()),
         }
     }
@@ -0,0 +1,308 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use reth_primitives::{H256, U64};
+use reth_rpc_types::{RichBlock, TransactionReceipt};
+use starknet::core::types::BlockId as StarknetBlockId;
+use tokio::runtime::Handle;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+use super::client_api::KakarotProvider;
+use super::constants::requests::MAX_BLOCK_RANGE;
+use super::errors::EthApiError;
+use super::helpers::touched_addresses;
+use crate::models::activity::AddressActivity;
+
+/// Number of events buffered for a receiver before it starts missing events.
+///
+/// Every block followed is broadcast as a single event, so the capacity is a number of blocks: a
+/// receiver keeps up as long as it is less than two ranges of blocks followed behind.
+pub const EVENTS_CAPACITY: usize = 2 * MAX_BLOCK_RANGE as usize;
+
+/// Number of the last blocks followed whose hashes are kept to detect the reorgs.
+pub const REORG_DEPTH: u64 = 64;
+
+/// An event of the chain followed by the client, for Rust applications embedding it.
+#[derive(Debug, Clone)]
+pub enum ClientEvent {
+    /// A new block, without its transactions, along with their receipts and so their logs.
+    NewBlock { block: RichBlock, receipts: Vec<TransactionReceipt> },
+    /// A transaction accepted by the Starknet node, by its hash.
+    TxSubmitted(H256),
+    /// The blocks from `from_block` on were replaced or dropped since they were followed.
+    ReorgDetected { from_block: u64 },
+    /// A transaction of a new block touched a watched address.
+    AddressActivity(AddressActivity),
+    /// The block of an activity broadcast before was replaced or dropped.
+    AddressActivityReverted(AddressActivity),
+}
+
+/// Broadcasts the events of the client to every receiver.
+///
+/// Submitted transactions are broadcast by the client itself, while the events of new blocks
+/// are broadcast by the [`ChainFollower`] of the client, which embedders start next to it:
+///
+/// ```ignore
+/// let mut events = client.events().subscribe();
+/// client.follower().start(client.clone(), Duration::from_secs(1), None);
+/// while let Ok(event) = events.recv().await {
+///     // ...
+/// }
+/// ```
+pub struct EventBus {
+    sender: broadcast::Sender<ClientEvent>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(EVENTS_CAPACITY);
+        Self { sender }
+    }
+}
+
+impl EventBus {
+    /// Returns a channel receiving the events broadcast from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ClientEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn emit(&self, event: ClientEvent) {
+        // Sending only fails when there are no receivers, in which case the event is dropped
+        let _ = self.sender.send(event);
+    }
+}
+
+/// Runs the single [`follow_chain`] task of a client, shared by the consumers of the events of
+/// new blocks, e.g. the watchlist and the subscriptions, so that every block is fetched once.
+///
+/// The follower also tracks the last block accepted on L1, advancing it from the last one known
+/// on every poll, and its own lag behind the head of the node.
+#[derive(Default)]
+pub struct ChainFollower {
+    task: Mutex<Option<JoinHandle<()>>>,
+    /// The number of blocks left to follow up to the head of the node at the last poll.
+    lag: Mutex<Option<u64>>,
+    /// The number of the last block accepted on L1, `None` until found.
+    last_l1_accepted_block: Mutex<Option<u64>>,
+}
+
+impl ChainFollower {
+    /// Follows the chain of `client` every `poll_interval`, on `runtime` if set or on the current
+    /// runtime otherwise, unless the chain is already followed.
+    pub fn start(&self, client: Arc<dyn KakarotProvider>, poll_interval: Duration, runtime: Option<&Handle>) {
+        let mut task = self.task.lock().expect("chain follower lock poisoned");
+        if task.as_ref().map_or(false, |task| !task.is_finished()) {
+            return;
+        }
+        let follow = follow_chain(client, poll_interval);
+        *task = Some(match runtime {
+            Some(runtime) => runtime.spawn(follow),
+            None => tokio::spawn(follow),
+        });
+    }
+
+    /// Stops following the chain. Stopping a stopped follower does nothing.
+    pub fn stop(&self) {
+        if let Some(task) = self.task.lock().expect("chain follower lock poisoned").take() {
+            task.abort();
+        }
+    }
+
+    /// Returns the number of the last block accepted on L1, `None` if the chain is not followed
+    /// or the block is not found yet.
+    pub fn last_l1_accepted_block(&self) -> Option<u64> {
+        let task = self.task.lock().expect("chain follower lock poisoned");
+        if task.as_ref().map_or(true, |task| task.is_finished()) {
+            return None;
+        }
+        *self.last_l1_accepted_block.lock().expect("chain follower lock poisoned")
+    }
+
+    /// Returns the number of blocks the follower is behind the head of the node, as of its last
+    /// poll, `None` if the chain is not followed.
+    ///
+    /// The follower keeps up while the lag stays at zero, and falls behind when blocks fail to be
+    /// followed or when the node moves by more than a range of blocks per poll.
+    pub fn lag(&self) -> Option<u64> {
+        let task = self.task.lock().expect("chain follower lock poisoned");
+        if task.as_ref().map_or(true, |task| task.is_finished()) {
+            return None;
+        }
+        *self.lag.lock().expect("chain follower lock poisoned")
+    }
+
+    /// Records that `next_block` is the next block to follow up to `latest_block`.
+    fn record_progress(&self, next_block: u64, latest_block: u64) {
+        *self.lag.lock().expect("chain follower lock poisoned") = Some((latest_block + 1).saturating_sub(next_block));
+    }
+}
+
+/// Follows the chain every `poll_interval`, broadcasting the new blocks with their receipts, the
+/// activity of the watched addresses and the reorgs on the event bus of the client, and tracking
+/// its lag and the last block accepted on L1.
+///
+/// Every block is followed, by ranges of blocks if the node moved by more than a range since the
+/// last poll, and a block which fails to be followed is followed again on next poll. Runs until
+/// the task is aborted, see [`ChainFollower`].
+pub async fn follow_chain(client: Arc<dyn KakarotProvider>, poll_interval: Duration) {
+    let mut interval = tokio::time::interval(poll_interval);
+    // The hashes of the last blocks followed, by number
+    let mut followed = BTreeMap::new();
+
+    loop {
+        interval.tick().await;
+
+        // The raw head of the node, which is behind the blocks followed after a reorg
+        let latest_block = match client.starknet_provider().block_number().await {
+            Ok(block_number) => block_number,
+            Err(err) => {
+                log::warn!("Failed to fetch the latest block to follow: {err}");
+                continue;
+            }
+        };
+        if followed.keys().next_back().map_or(false, |&head| latest_block < head) {
+            revert(client.as_ref(), &mut followed, latest_block);
+        }
+
+        let mut from_block = followed.keys().next_back().map_or(latest_block, |&head| head + 1);
+        client.follower().record_progress(from_block, latest_block);
+        while from_block <= latest_block {
+            let to_block = latest_block.min(from_block + MAX_BLOCK_RANGE - 1);
+            match follow_blocks(client.as_ref(), &mut followed, from_block, to_block).await {
+                Ok(next_block) => {
+                    from_block = next_block;
+                    client.follower().record_progress(from_block, latest_block);
+                }
+                Err(err) => {
+                    log::warn!("Failed to follow the blocks {from_block} to {to_block}: {err}");
+                    break;
+                }
+            }
+        }
+
+        if let Err(err) = track_l1_accepted_block(client.as_ref(), latest_block).await {
+            log::warn!("Failed to track the last block accepted on L1: {err}");
+        }
+    }
+}
+
+/// Follows the blocks from `from_block` to `to_block` and returns the next block to follow,
+/// which is the parent of a block if the parent was replaced.
+async fn follow_blocks(
+    client: &dyn KakarotProvider,
+    followed: &mut BTreeMap<u64, H256>,
+    from_block: u64,
+    to_block: u64,
+) -> Result<u64, EthApiError> {
+    let blocks = client.get_eth_blocks_in_range(from_block, to_block, false).await?;
+    for block in blocks {
+        let (number, hash) = match (block.header.number, block.header.hash) {
+            (Some(number), Some(hash)) => (number.to::<u64>(), hash),
+            _ => {
+                return Err(EthApiError::OtherError(anyhow::anyhow!(
+                    "Kakarot Core: followed block without a number or a hash"
+                )));
+            }
+        };
+        if let Some(parent_hash) = number.checked_sub(1).and_then(|parent| followed.get(&parent)) {
+            if *parent_hash != block.header.parent_hash {
+                revert(client, followed, number - 1);
+                return Ok(number - 1);
+            }
+        }
+        let receipts = client.block_receipts(StarknetBlockId::Number(number)).await?;
+
+        for receipt in &receipts {
+            for address in touched_addresses(receipt) {
+                let activity = AddressActivity {
+                    address,
+                    block_hash: hash,
+                    block_number: U64::from(number),
+                    transaction_hash: receipt.transaction_hash.unwrap_or_default(),
+                };
+                if client.watchlist().record(activity.clone()) {
+                    client.events().emit(ClientEvent::AddressActivity(activity));
+                }
+            }
+        }
+        client.events().emit(ClientEvent::NewBlock { block, receipts });
+
+        followed.insert(number, hash);
+        *followed = followed.split_off(&(number + 1).saturating_sub(REORG_DEPTH));
+    }
+    Ok(to_block + 1)
+}
+
+/// Advances the last block accepted on L1 tracked by the follower of `client`, up to
+/// `latest_block`.
+///
+/// Once the block is known, a single block is fetched per poll while no new block is accepted,
+/// the bisection only covering the blocks after it otherwise.
+async fn track_l1_accepted_block(client: &dyn KakarotProvider, latest_block: u64) -> Result<(), EthApiError> {
+    let follower = client.follower();
+    let known = *follower.last_l1_accepted_block.lock().expect("chain follower lock poisoned");
+    let from_block = match known {
+        Some(known) if known >= latest_block || !client.is_accepted_on_l1(known + 1).await? => return Ok(()),
+        Some(known) => known + 1,
+        None => 0,
+    };
+    let last_l1_accepted_block = find_last_l1_accepted_block(client, from_block, latest_block).await?;
+    *follower.last_l1_accepted_block.lock().expect("chain follower lock poisoned") = Some(last_l1_accepted_block);
+    Ok(())
+}
+
+/// Returns the number of the last block accepted on L1 among the blocks from `from_block` to
+/// `to_block`, `from_block` if there is none.
+///
+/// Blocks are accepted on L1 in order, so the block is found by bisecting the range on the status
+/// of its blocks.
+pub(crate) async fn find_last_l1_accepted_block(
+    client: &dyn KakarotProvider,
+    from_block: u64,
+    to_block: u64,
+) -> Result<u64, EthApiError> {
+    let (mut low, mut high) = (from_block, to_block);
+    while low < high {
+        let middle = low + (high - low + 1) / 2;
+        if client.is_accepted_on_l1(middle).await? {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Forgets the blocks followed from `from_block` on, which were replaced or dropped.
+fn revert(client: &dyn KakarotProvider, followed: &mut BTreeMap<u64, H256>, from_block: u64) {
+    followed.split_off(&from_block);
+    client.events().emit(ClientEvent::ReorgDetected { from_block });
+    for activity in client.watchlist().revert(from_block) {
+        client.events().emit(ClientEvent::AddressActivityReverted(activity));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_event_bus_broadcasts_to_every_receiver() {
+        let bus = EventBus::default();
+        // Events emitted without receivers are dropped
+        bus.emit(ClientEvent::ReorgDetected { from_block: 1 });
+
+        let mut first = bus.subscribe();
+        let mut second = bus.subscribe();
+        bus.emit(ClientEvent::TxSubmitted(H256::from_low_u64_be(1)));
+
+        for receiver in [&mut first, &mut second] {
+            assert!(
+                matches!(receiver.recv().await, Ok(ClientEvent::TxSubmitted(hash)) if hash == H256::from_low_u64_be(1))
+            );
+            assert!(receiver.try_recv().is_err());
+        }
+    }
+}
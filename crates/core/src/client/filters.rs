@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reth_primitives::H256;
+use reth_rpc_types::{Filter, Log};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::constants::requests::{MAX_BLOCK_RANGE, MAX_LOGS_BLOCK_RANGE};
+
+/// What an installed filter reports on `eth_getFilterChanges`.
+#[derive(Debug, Clone)]
+pub enum FilterKind {
+    /// New logs matching the filter, installed with `eth_newFilter`.
+    Logs(Filter),
+    /// Hashes of new blocks, installed with `eth_newBlockFilter`.
+    Blocks,
+}
+
+impl FilterKind {
+    /// The maximum number of blocks whose changes are reported by a single poll.
+    fn max_blocks_per_poll(&self) -> u64 {
+        match self {
+            FilterKind::Logs(_) => MAX_LOGS_BLOCK_RANGE,
+            FilterKind::Blocks => MAX_BLOCK_RANGE,
+        }
+    }
+}
+
+/// The changes reported by `eth_getFilterChanges`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FilterChanges {
+    Logs(Vec<Log>),
+    Hashes(Vec<H256>),
+}
+
+/// A filter which cannot be installed because too many filters are installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("too many filters installed, at most {0} filters may be installed")]
+pub struct TooManyFilters(pub usize);
+
+struct InstalledFilter {
+    kind: FilterKind,
+    /// The last block whose changes were reported.
+    last_polled_block: u64,
+    last_poll: Instant,
+}
+
+/// Tracks the filters installed by clients and the blocks already reported to them.
+///
+/// Filters which are not polled for `timeout` are uninstalled, as clients commonly forget to
+/// uninstall them, and at most `max_filters` filters are installed at once.
+pub struct FilterManager {
+    timeout: Duration,
+    max_filters: usize,
+    state: Mutex<FilterManagerState>,
+}
+
+#[derive(Default)]
+struct FilterManagerState {
+    next_id: u64,
+    filters: HashMap<u64, InstalledFilter>,
+}
+
+impl FilterManager {
+    #[must_use]
+    pub fn new(timeout: Duration, max_filters: usize) -> Self {
+        Self { timeout, max_filters, state: Mutex::new(FilterManagerState::default()) }
+    }
+
+    /// Installs a filter reporting changes after `current_block`.
+    ///
+    /// ## Returns
+    /// The id of the filter.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if `max_filters` filters are already installed and none of them expired.
+    pub fn install(&self, kind: FilterKind, current_block: u64) -> Result<u64, TooManyFilters> {
+        let mut state = self.state.lock().expect("filter manager lock poisoned");
+
+        let timeout = self.timeout;
+        state.filters.retain(|_, filter| filter.last_poll.elapsed() < timeout);
+        if state.filters.len() >= self.max_filters {
+            return Err(TooManyFilters(self.max_filters));
+        }
+
+        state.next_id += 1;
+        let id = state.next_id;
+        state.filters.insert(id, InstalledFilter { kind, last_polled_block: current_block, last_poll: Instant::now() });
+        Ok(id)
+    }
+
+    /// Uninstalls a filter.
+    ///
+    /// ## Returns
+    /// `false` if no filter with this id is installed.
+    pub fn uninstall(&self, id: u64) -> bool {
+        self.state.lock().expect("filter manager lock poisoned").filters.remove(&id).is_some()
+    }
+
+    /// Returns an installed filter, without marking it as polled.
+    pub fn get(&self, id: u64) -> Option<FilterKind> {
+        self.state.lock().expect("filter manager lock poisoned").filters.get(&id).map(|filter| filter.kind.clone())
+    }
+
+    /// Returns an installed filter along with the oldest blocks not yet reported to it, up to
+    /// `current_block` and at most the blocks a single poll reports, the others being left to
+    /// the next polls.
+    ///
+    /// The range is empty if no block was produced since the last poll. The blocks are only
+    /// reported once [`FilterManager::mark_reported`], so that a poll failing to fetch their
+    /// changes does not skip them.
+    pub fn poll(&self, id: u64, current_block: u64) -> Option<(FilterKind, RangeInclusive<u64>)> {
+        let mut state = self.state.lock().expect("filter manager lock poisoned");
+
+        let timeout = self.timeout;
+        state.filters.retain(|_, filter| filter.last_poll.elapsed() < timeout);
+
+        let filter = state.filters.get_mut(&id)?;
+        filter.last_poll = Instant::now();
+        let from_block = filter.last_polled_block + 1;
+        let to_block = current_block.min(from_block + filter.kind.max_blocks_per_poll() - 1);
+
+        Some((filter.kind.clone(), from_block..=to_block))
+    }
+
+    /// Marks the blocks up to `to_block` as reported to a filter, once their changes were
+    /// fetched.
+    pub fn mark_reported(&self, id: u64, to_block: u64) {
+        if let Some(filter) = self.state.lock().expect("filter manager lock poisoned").filters.get_mut(&id) {
+            filter.last_polled_block = filter.last_polled_block.max(to_block);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_reports_each_block_once() {
+        let manager = FilterManager::new(Duration::from_secs(60), 10);
+        let id = manager.install(FilterKind::Blocks, 10).unwrap();
+
+        let (_, range) = manager.poll(id, 10).unwrap();
+        assert!(range.is_empty());
+
+        let (_, range) = manager.poll(id, 13).unwrap();
+        assert_eq!(range, 11..=13);
+        manager.mark_reported(id, 13);
+
+        let (_, range) = manager.poll(id, 13).unwrap();
+        assert!(range.is_empty());
+    }
+
+    #[test]
+    fn test_poll_reports_blocks_again_until_marked_reported() {
+        let manager = FilterManager::new(Duration::from_secs(60), 10);
+        let id = manager.install(FilterKind::Blocks, 10).unwrap();
+
+        // The changes of the first poll failed to be fetched
+        assert_eq!(manager.poll(id, 12).unwrap().1, 11..=12);
+        assert_eq!(manager.poll(id, 13).unwrap().1, 11..=13);
+    }
+
+    #[test]
+    fn test_poll_reports_the_oldest_blocks_first() {
+        let manager = FilterManager::new(Duration::from_secs(60), 10);
+        let id = manager.install(FilterKind::Blocks, 0).unwrap();
+        let current_block = 2 * MAX_BLOCK_RANGE + 10;
+
+        let (_, range) = manager.poll(id, current_block).unwrap();
+        assert_eq!(range, 1..=MAX_BLOCK_RANGE);
+        manager.mark_reported(id, *range.end());
+
+        let (_, range) = manager.poll(id, current_block).unwrap();
+        assert_eq!(range, MAX_BLOCK_RANGE + 1..=2 * MAX_BLOCK_RANGE);
+        manager.mark_reported(id, *range.end());
+
+        let (_, range) = manager.poll(id, current_block).unwrap();
+        assert_eq!(range, 2 * MAX_BLOCK_RANGE + 1..=current_block);
+    }
+
+    #[test]
+    fn test_uninstall() {
+        let manager = FilterManager::new(Duration::from_secs(60), 10);
+        let id = manager.install(FilterKind::Blocks, 0).unwrap();
+
+        assert!(manager.uninstall(id));
+        assert!(!manager.uninstall(id));
+        assert!(manager.poll(id, 1).is_none());
+    }
+
+    #[test]
+    fn test_expired_filters_are_uninstalled() {
+        let manager = FilterManager::new(Duration::ZERO, 10);
+        let expired = manager.install(FilterKind::Blocks, 0).unwrap();
+
+        manager.install(FilterKind::Blocks, 0).unwrap();
+
+        assert!(manager.get(expired).is_none());
+    }
+
+    #[test]
+    fn test_expired_filters_are_not_polled() {
+        let manager = FilterManager::new(Duration::ZERO, 10);
+        let expired = manager.install(FilterKind::Blocks, 0).unwrap();
+
+        assert!(manager.poll(expired, 1).is_none());
+    }
+
+    #[test]
+    fn test_install_is_rejected_once_the_filters_are_at_capacity() {
+        let manager = FilterManager::new(Duration::from_secs(60), 2);
+        let id = manager.install(FilterKind::Blocks, 0).unwrap();
+        manager.install(FilterKind::Blocks, 0).unwrap();
+
+        assert_eq!(manager.install(FilterKind::Blocks, 0), Err(TooManyFilters(2)));
+        // Uninstalling a filter makes room for another
+        assert!(manager.uninstall(id));
+        assert!(manager.install(FilterKind::Blocks, 0).is_ok());
+    }
+
+    #[test]
+    fn test_expired_filters_do_not_count_towards_the_capacity() {
+        let manager = FilterManager::new(Duration::ZERO, 1);
+        manager.install(FilterKind::Blocks, 0).unwrap();
+
+        assert!(manager.install(FilterKind::Blocks, 0).is_ok());
+    }
+}
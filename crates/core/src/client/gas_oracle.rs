@@ -0,0 +1,125 @@
+use std::collections::VecDeque;
+use std::ops::RangeInclusive;
+use std::sync::Mutex;
+
+use reth_primitives::{U128, U256};
+
+/// Suggests a priority fee per gas from the priority fees paid in a moving window of recent
+/// blocks.
+///
+/// The suggestion is the priority fee at `percentile` of the ones paid by the transactions of the
+/// window. It is kept unchanged while the window holds no transaction.
+pub struct GasOracle {
+    window: u64,
+    percentile: f64,
+    state: Mutex<GasOracleState>,
+}
+
+struct GasOracleState {
+    /// The priority fees paid in each block of the window, oldest first.
+    blocks: VecDeque<(u64, Vec<U256>)>,
+    suggestion: U128,
+}
+
+impl GasOracle {
+    /// Creates an oracle over the last `window` blocks, suggesting `default_suggestion` until a
+    /// transaction is recorded.
+    #[must_use]
+    pub fn new(window: u64, percentile: f64, default_suggestion: U128) -> Self {
+        Self {
+            window,
+            percentile,
+            state: Mutex::new(GasOracleState { blocks: VecDeque::new(), suggestion: default_suggestion }),
+        }
+    }
+
+    /// Returns the last suggested priority fee per gas.
+    pub fn suggestion(&self) -> U128 {
+        self.state.lock().expect("gas oracle lock poisoned").suggestion
+    }
+
+    /// Returns the newest blocks of the window ending at `latest_block` which are not recorded
+    /// yet, at most `max_blocks` of them.
+    ///
+    /// Older missing blocks are never recorded, so the window of a new oracle fills up over the
+    /// suggestions instead of being fetched at once.
+    pub fn missing_blocks(&self, latest_block: u64, max_blocks: u64) -> RangeInclusive<u64> {
+        let state = self.state.lock().expect("gas oracle lock poisoned");
+        let window_start = (latest_block + 1).saturating_sub(self.window.min(max_blocks));
+        let from_block = match state.blocks.back() {
+            Some((last_block, _)) => (last_block + 1).max(window_start),
+            None => window_start,
+        };
+        from_block..=latest_block
+    }
+
+    /// Records the priority fees paid in a block, drops the blocks out of the window and updates
+    /// the suggestion.
+    ///
+    /// Blocks must be recorded in increasing order, blocks already recorded are ignored.
+    pub fn record_block(&self, block_number: u64, priority_fees: Vec<U256>) {
+        let mut state = self.state.lock().expect("gas oracle lock poisoned");
+        if matches!(state.blocks.back(), Some((last_block, _)) if *last_block >= block_number) {
+            return;
+        }
+
+        state.blocks.push_back((block_number, priority_fees));
+        let window_start = (block_number + 1).saturating_sub(self.window);
+        while matches!(state.blocks.front(), Some((block, _)) if *block < window_start) {
+            state.blocks.pop_front();
+        }
+
+        let mut priority_fees: Vec<U256> = state.blocks.iter().flat_map(|(_, fees)| fees.iter().copied()).collect();
+        if priority_fees.is_empty() {
+            return;
+        }
+        priority_fees.sort_unstable();
+        let index = ((priority_fees.len() - 1) as f64 * self.percentile / 100.0) as usize;
+        state.suggestion = U128::from(u128::try_from(priority_fees[index]).unwrap_or(u128::MAX));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_blocks() {
+        let oracle = GasOracle::new(3, 50.0, U128::ZERO);
+        assert_eq!(oracle.missing_blocks(10, 5), 8..=10);
+
+        oracle.record_block(8, vec![]);
+        assert_eq!(oracle.missing_blocks(10, 5), 9..=10);
+        assert_eq!(oracle.missing_blocks(20, 5), 18..=20);
+    }
+
+    #[test]
+    fn test_missing_blocks_are_capped_to_the_newest() {
+        let oracle = GasOracle::new(20, 50.0, U128::ZERO);
+        assert_eq!(oracle.missing_blocks(100, 4), 97..=100);
+
+        oracle.record_block(100, vec![]);
+        assert_eq!(oracle.missing_blocks(102, 4), 101..=102);
+        assert_eq!(oracle.missing_blocks(110, 4), 107..=110);
+    }
+
+    #[test]
+    fn test_suggestion_follows_the_window() {
+        let oracle = GasOracle::new(2, 50.0, U128::from(7));
+        assert_eq!(oracle.suggestion(), U128::from(7));
+
+        oracle.record_block(1, vec![U256::from(1), U256::from(3)]);
+        oracle.record_block(2, vec![U256::from(2)]);
+        assert_eq!(oracle.suggestion(), U128::from(2));
+
+        // Block 1 leaves the window
+        oracle.record_block(3, vec![U256::from(5)]);
+        assert_eq!(oracle.suggestion(), U128::from(2));
+        oracle.record_block(4, vec![U256::from(6), U256::from(8)]);
+        assert_eq!(oracle.suggestion(), U128::from(6));
+
+        // Empty windows keep the last suggestion
+        oracle.record_block(10, vec![]);
+        assert_eq!(oracle.suggestion(), U128::from(6));
+    }
+}
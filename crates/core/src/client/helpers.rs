@@ -1,21 +1,41 @@
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
 use eyre::Result;
 use reth_primitives::{
-    Address, BlockId as EthBlockId, BlockNumberOrTag, Bloom, Bytes, Signature, TransactionSigned, H160,
+    keccak256, Address, BlockId as EthBlockId, BlockNumberOrTag, Bloom, Bytes, Signature, Transaction, TransactionKind,
+    TransactionSigned, H160, H256, U256,
 };
 use reth_rlp::Decodable;
-use reth_rpc_types::TransactionReceipt;
+use reth_rpc_types::{Log, TransactionReceipt};
 use starknet::accounts::Call;
+use starknet::core::crypto::compute_hash_on_elements;
 use starknet::core::types::{
-    BlockId as StarknetBlockId, BlockTag, FieldElement, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
-    ValueOutOfRangeError,
+    BlockId as StarknetBlockId, BlockTag, BroadcastedInvokeTransactionV1, Event, FieldElement,
+    MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, StarknetError,
+    TransactionStatus as StarknetTransactionStatus, ValueOutOfRangeError,
 };
+use starknet::providers::jsonrpc::JsonRpcClientError;
+use starknet::providers::ProviderError;
 use thiserror::Error;
 
-use super::constants::{CUMULATIVE_GAS_USED, EFFECTIVE_GAS_PRICE, GAS_USED, TRANSACTION_TYPE};
-use crate::client::constants::selectors::ETH_SEND_TRANSACTION;
+use super::constants::error_messages::DUPLICATE_TRANSACTION;
+use super::constants::gas::{
+    TX_ACCESS_LIST_ADDRESS_GAS, TX_ACCESS_LIST_STORAGE_KEY_GAS, TX_BASE_GAS, TX_CREATE_GAS, TX_DATA_NON_ZERO_GAS,
+    TX_DATA_ZERO_GAS,
+};
+use super::constants::selectors::{TRANSACTION_EXECUTED, TRANSFER};
+use super::constants::{CUMULATIVE_GAS_USED, EFFECTIVE_GAS_PRICE, GAS_USED, STARKNET_NATIVE_TOKEN, TRANSACTION_TYPE};
 use crate::client::errors::EthApiError;
 use crate::models::felt::Felt252Wrapper;
 
+/// First 4 bytes of keccak256("balanceOf(address)")
+const ERC20_BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+/// Cairo string for "invoke"
+const PREFIX_INVOKE: FieldElement =
+    FieldElement::from_mont([18443034532770911073, 18446744073709551615, 18446744073709551615, 513398556346534256]);
+
 #[derive(Debug, Error)]
 pub enum DataDecodingError {
     #[error("failed to decode signature {0}")]
@@ -38,6 +58,144 @@ pub enum MaybePendingStarknetBlock {
     BlockWithTxs(MaybePendingBlockWithTxs),
 }
 
+/// How Starknet addresses which do not fit in 20 bytes are converted to EVM addresses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AddressConversionPolicy {
+    /// Keep the last 20 bytes of the address. Distinct Starknet addresses may collide.
+    #[default]
+    Truncate,
+    /// Replace the address with the zero address.
+    ZeroAddress,
+    /// Fail the conversion.
+    Error,
+}
+
+impl FromStr for AddressConversionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "truncate" => Ok(Self::Truncate),
+            "zero" | "zero_address" => Ok(Self::ZeroAddress),
+            "error" => Ok(Self::Error),
+            _ => Err(format!("unknown address conversion policy {s}, expected truncate, zero or error")),
+        }
+    }
+}
+
+/// The Starknet status from which a transaction is reported as confirmed, i.e. gets a receipt
+/// and is part of the `finalized` block.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConfirmationLevel {
+    /// Transactions are confirmed once accepted by the sequencer.
+    #[default]
+    AcceptedOnL2,
+    /// Transactions are confirmed once their block is proven on L1.
+    AcceptedOnL1,
+}
+
+impl ConfirmationLevel {
+    /// Returns whether a transaction with the given status is accepted but has not reached this
+    /// level yet.
+    pub const fn is_awaiting_confirmation(self, status: &StarknetTransactionStatus) -> bool {
+        matches!((self, status), (Self::AcceptedOnL1, StarknetTransactionStatus::AcceptedOnL2))
+    }
+}
+
+impl FromStr for ConfirmationLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "l2" | "accepted_on_l2" => Ok(Self::AcceptedOnL2),
+            "l1" | "accepted_on_l1" => Ok(Self::AcceptedOnL1),
+            _ => Err(format!("unknown confirmation level {s}, expected l2 or l1")),
+        }
+    }
+}
+
+/// A previous deployment of Kakarot core, which served the blocks `from_block..=to_block` before
+/// being migrated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KakarotDeployment {
+    pub address: FieldElement,
+    pub from_block: u64,
+    pub to_block: u64,
+}
+
+impl FromStr for KakarotDeployment {
+    type Err = String;
+
+    /// Parses a deployment formatted as `address:from_block-to_block`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || format!("invalid Kakarot deployment {s}, expected address:from_block-to_block");
+        let (address, range) = s.trim().split_once(':').ok_or_else(err)?;
+        let (from_block, to_block) = range.split_once('-').ok_or_else(err)?;
+
+        let address = FieldElement::from_hex_be(address).map_err(|_| err())?;
+        let from_block = from_block.parse().map_err(|_| err())?;
+        let to_block = to_block.parse().map_err(|_| err())?;
+        if from_block > to_block {
+            return Err(err());
+        }
+        Ok(Self { address, from_block, to_block })
+    }
+}
+
+/// Returns the address of the Kakarot deployment serving a block: the previous deployment whose
+/// range contains it if any, the current one otherwise.
+#[must_use]
+pub fn kakarot_address_at(
+    deployments: &[KakarotDeployment],
+    current_address: FieldElement,
+    block_number: u64,
+) -> FieldElement {
+    deployments
+        .iter()
+        .find(|deployment| (deployment.from_block..=deployment.to_block).contains(&block_number))
+        .map_or(current_address, |deployment| deployment.address)
+}
+
+/// Splits a block range into the sub-ranges served by each Kakarot deployment, in order.
+#[must_use]
+pub fn kakarot_addresses_in_range(
+    deployments: &[KakarotDeployment],
+    current_address: FieldElement,
+    from_block: u64,
+    to_block: u64,
+) -> Vec<(FieldElement, RangeInclusive<u64>)> {
+    let mut overlapping: Vec<_> = deployments
+        .iter()
+        .filter(|deployment| deployment.from_block <= to_block && deployment.to_block >= from_block)
+        .collect();
+    overlapping.sort_by_key(|deployment| deployment.from_block);
+
+    let mut ranges = Vec::new();
+    // The first block not assigned to a deployment yet, `None` once the range is covered
+    let mut next_block = Some(from_block);
+    for deployment in overlapping {
+        let start = match next_block {
+            Some(start) => start,
+            None => break,
+        };
+        let deployment_start = deployment.from_block.max(start);
+        let deployment_end = deployment.to_block.min(to_block);
+        if deployment_start > deployment_end {
+            continue;
+        }
+        if deployment_start > start {
+            ranges.push((current_address, start..=deployment_start - 1));
+        }
+        ranges.push((deployment.address, deployment_start..=deployment_end));
+        next_block = deployment_end.checked_add(1).filter(|block| *block <= to_block);
+    }
+    if let Some(start) = next_block {
+        ranges.push((current_address, start..=to_block));
+    }
+
+    ranges
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum FeltOrFeltArray {
     Felt(FieldElement),
@@ -83,6 +241,10 @@ pub fn ethers_block_id_to_starknet_block_id(block: EthBlockId) -> Result<Starkne
 }
 
 /// Converts a `Eth` block number to a `Starknet` block id.
+///
+/// Starknet has no `safe` nor `finalized` block: both are the latest block here, see
+/// [`KakarotProvider::resolve_block_id`](super::client_api::KakarotProvider::resolve_block_id)
+/// for the blocks they resolve to once transactions are only confirmed on L1.
 pub const fn ethers_block_number_to_starknet_block_id(block: BlockNumberOrTag) -> StarknetBlockId {
     match block {
         BlockNumberOrTag::Safe | BlockNumberOrTag::Latest | BlockNumberOrTag::Finalized => {
@@ -245,10 +407,289 @@ pub fn decode_signature_from_tx_calldata(calldata: &[FieldElement]) -> Result<Si
     Ok(decoded_tx.signature)
 }
 
+/// Computes the hash of an invoke v1 transaction, as done by the sequencer.
+///
+/// ## Arguments
+/// * `chain_id` - The Starknet chain id
+/// * `request` - The invoke transaction
+#[must_use]
+pub fn compute_invoke_v1_transaction_hash(
+    chain_id: FieldElement,
+    request: &BroadcastedInvokeTransactionV1,
+) -> FieldElement {
+    compute_hash_on_elements(&[
+        PREFIX_INVOKE,
+        FieldElement::ONE, // version
+        request.sender_address,
+        FieldElement::ZERO, // entry_point_selector
+        compute_hash_on_elements(&request.calldata),
+        request.max_fee,
+        chain_id,
+        request.nonce,
+    ])
+}
+
+/// Returns the address of a contract deployed with `CREATE`: the last 20 bytes of the keccak hash
+/// of the RLP encoded sender and nonce.
+#[must_use]
+pub fn create_address(sender: Address, nonce: u64) -> Address {
+    let nonce_bytes = nonce.to_be_bytes();
+    let nonce_bytes = &nonce_bytes[nonce_bytes.iter().take_while(|byte| **byte == 0).count()..];
+
+    // The payload is at most 30 bytes long, so every RLP header fits in a single byte
+    let mut payload = Vec::with_capacity(31);
+    payload.push(0x80 + 20);
+    payload.extend_from_slice(sender.as_bytes());
+    match nonce_bytes {
+        [byte] if *byte < 0x80 => payload.push(*byte),
+        _ => {
+            payload.push(0x80 + nonce_bytes.len() as u8);
+            payload.extend_from_slice(nonce_bytes);
+        }
+    }
+
+    let mut encoded = vec![0xc0 + payload.len() as u8];
+    encoded.extend_from_slice(&payload);
+    Address::from_slice(&keccak256(encoded).0[12..])
+}
+
+/// Returns the intrinsic gas of an EVM transaction: the gas charged before any execution, for
+/// the transaction itself, its input and its access list.
+#[must_use]
+pub fn intrinsic_gas(transaction: &Transaction) -> u64 {
+    let mut gas = TX_BASE_GAS;
+    if matches!(transaction.kind(), TransactionKind::Create) {
+        gas += TX_CREATE_GAS;
+    }
+
+    let zero_bytes = transaction.input().iter().filter(|byte| **byte == 0).count() as u64;
+    let non_zero_bytes = transaction.input().len() as u64 - zero_bytes;
+    gas += zero_bytes * TX_DATA_ZERO_GAS + non_zero_bytes * TX_DATA_NON_ZERO_GAS;
+
+    if let Some(access_list) = transaction.access_list() {
+        for item in &access_list.0 {
+            gas += TX_ACCESS_LIST_ADDRESS_GAS + item.storage_keys.len() as u64 * TX_ACCESS_LIST_STORAGE_KEY_GAS;
+        }
+    }
+
+    gas
+}
+
+/// Returns the rewards paid at the given percentiles of the gas of a block, from the reward and
+/// the gas of each of its transactions. Percentiles are expected in increasing order.
+///
+/// The reward at a percentile is the one of the transaction reaching this share of the block gas
+/// once transactions are sorted by reward, as in geth. Empty blocks have zero rewards.
+#[must_use]
+pub fn rewards_at_percentiles(mut rewards: Vec<(U256, u64)>, percentiles: &[f64]) -> Vec<U256> {
+    if rewards.is_empty() {
+        return vec![U256::ZERO; percentiles.len()];
+    }
+    rewards.sort_unstable_by_key(|(reward, _)| *reward);
+
+    let total_gas = rewards.iter().fold(0u64, |total, (_, gas)| total.saturating_add(*gas));
+    let mut index = 0;
+    let mut cumulative_gas = rewards[0].1;
+    percentiles
+        .iter()
+        .map(|percentile| {
+            let threshold = (total_gas as f64 * percentile / 100.0) as u64;
+            while cumulative_gas < threshold && index < rewards.len() - 1 {
+                index += 1;
+                cumulative_gas = cumulative_gas.saturating_add(rewards[index].1);
+            }
+            rewards[index].0
+        })
+        .collect()
+}
+
+/// Returns whether a Starknet transaction submission failed because the exact same transaction
+/// was already received upstream.
+#[must_use]
+pub fn is_duplicate_transaction_error(error: &EthApiError) -> bool {
+    match error {
+        EthApiError::RequestError(err) => {
+            let message = err.to_string().to_lowercase();
+            DUPLICATE_TRANSACTION.iter().any(|fragment| message.contains(fragment))
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether a Starknet request failed because the node could not be reached or is
+/// overloaded, as opposed to the node rejecting the request.
+#[must_use]
+pub fn is_node_unavailable_error(error: &EthApiError) -> bool {
+    matches!(
+        error,
+        EthApiError::RequestError(
+            ProviderError::RateLimited | ProviderError::Other(JsonRpcClientError::TransportError(_))
+        )
+    )
+}
+
+/// Returns whether a Starknet request failed because the node does not know the transaction,
+/// e.g. because it was not received yet or it is still in the mempool.
+#[must_use]
+pub fn is_transaction_not_found_error(error: &EthApiError) -> bool {
+    matches!(error, EthApiError::RequestError(ProviderError::StarknetError(StarknetError::TransactionHashNotFound)))
+}
+
+/// Returns whether a Starknet request failed because no contract is deployed at the address.
+#[must_use]
+pub fn is_contract_not_found_error(error: &EthApiError) -> bool {
+    matches!(error, EthApiError::RequestError(ProviderError::StarknetError(StarknetError::ContractNotFound)))
+}
+
+/// Returns whether a Starknet request failed because the node is missing the block, e.g. because
+/// it pruned it.
+#[must_use]
+pub fn is_missing_block_error(error: &EthApiError) -> bool {
+    matches!(error, EthApiError::RequestError(ProviderError::StarknetError(StarknetError::BlockNotFound)))
+}
+
+/// Builds the calldata of an ERC-20 `balanceOf(address)` call.
 #[must_use]
-pub fn vec_felt_to_bytes(felt_vec: Vec<FieldElement>) -> Bytes {
-    let felt_vec_in_u8: Vec<u8> = felt_vec.into_iter().flat_map(|x| x.to_bytes_be()).collect();
-    Bytes::from(felt_vec_in_u8)
+pub fn erc20_balance_of_calldata(owner: Address) -> Bytes {
+    let mut calldata = ERC20_BALANCE_OF_SELECTOR.to_vec();
+    calldata.extend_from_slice(&[0u8; 12]);
+    calldata.extend_from_slice(owner.as_bytes());
+    Bytes::from(calldata)
+}
+
+/// Returns the addresses touched by a transaction, in order and without duplicates: its sender,
+/// recipient and deployed contract, the emitters of its logs, and the addresses in their topics,
+/// e.g. the sender and recipient of ERC-20 transfers.
+#[must_use]
+pub fn touched_addresses(receipt: &TransactionReceipt) -> Vec<Address> {
+    let log_addresses = receipt.logs.iter().flat_map(|log| {
+        // Addresses are left-padded with 12 zero bytes in topics
+        let topic_addresses = log
+            .topics
+            .iter()
+            .filter(|topic| topic.0[..12] == [0u8; 12] && !topic.is_zero())
+            .map(|topic| Address::from_slice(&topic.0[12..]));
+        std::iter::once(log.address).chain(topic_addresses)
+    });
+
+    let mut addresses = Vec::new();
+    for address in std::iter::once(receipt.from).chain(receipt.to).chain(receipt.contract_address).chain(log_addresses)
+    {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    addresses
+}
+
+/// Returns the bloom filter of EVM logs, in which the address and the topics of each log are
+/// accrued.
+#[must_use]
+pub fn logs_bloom(logs: &[Log]) -> Bloom {
+    let mut bloom = Bloom::default();
+    for log in logs {
+        accrue_bloom(&mut bloom, log.address.as_bytes());
+        for topic in &log.topics {
+            accrue_bloom(&mut bloom, topic.as_bytes());
+        }
+    }
+    bloom
+}
+
+/// Sets the 3 bits of the bloom filter selected by the keccak hash of the input.
+fn accrue_bloom(bloom: &mut Bloom, input: &[u8]) {
+    let hash = keccak256(input).0;
+    for i in [0, 2, 4] {
+        let bit = ((usize::from(hash[i]) << 8) | usize::from(hash[i + 1])) & 2047;
+        bloom.0[255 - bit / 8] |= 1 << (bit % 8);
+    }
+}
+
+#[must_use]
+pub fn vec_felt_to_bytes(felt_vec: &[FieldElement]) -> Bytes {
+    let mut bytes = Vec::with_capacity(felt_vec.len() * 32);
+    for felt in felt_vec {
+        bytes.extend_from_slice(&felt.to_bytes_be());
+    }
+    Bytes::from(bytes)
+}
+
+/// Rebuilds an EVM topic from the low and high 128-bit halves of its Cairo `Uint256`
+/// representation, computing `low + 2^128 * high` over 256 bits.
+#[must_use]
+pub fn topic_from_low_high(low: &FieldElement, high: &FieldElement) -> H256 {
+    let low = U256::from_be_bytes(low.to_bytes_be());
+    let high = U256::from_be_bytes(high.to_bytes_be());
+    H256::from((high << 128).wrapping_add(low).to_be_bytes::<32>())
+}
+
+/// Splits a 256-bit value into the low and high 128-bit halves of its Cairo `Uint256`
+/// representation.
+#[must_use]
+pub fn split_u256_into_low_high(value: U256) -> [FieldElement; 2] {
+    let bytes = value.to_be_bytes::<32>();
+    // safe unwraps since both halves are 16 bytes
+    let low = FieldElement::from_byte_slice_be(&bytes[16..]).unwrap();
+    let high = FieldElement::from_byte_slice_be(&bytes[..16]).unwrap();
+    [low, high]
+}
+
+/// Decodes a `Transfer(from, to, value)` event of the native token into its Starknet sender,
+/// recipient and value. Returns `None` for any other event.
+#[must_use]
+pub fn decode_native_transfer(event: &Event) -> Option<(FieldElement, FieldElement, U256)> {
+    // This FieldElement::from_hex_be cannot fail as the value is a constant
+    let native_token = FieldElement::from_hex_be(STARKNET_NATIVE_TOKEN).unwrap();
+    if event.from_address != native_token || event.keys.first() != Some(&TRANSFER) {
+        return None;
+    }
+    match event.data.as_slice() {
+        [from, to, low, high] => {
+            Some((*from, *to, U256::from_be_bytes(topic_from_low_high(low, high).to_fixed_bytes())))
+        }
+        _ => None,
+    }
+}
+
+/// Selector of `Error(string)`, which encodes the message of the EVM reverts of Solidity
+/// `require` and `revert` statements.
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Decodes a `transaction_executed(response_len, response, success, gas_used)` event, emitted by
+/// the Kakarot account of the sender with the result of the EVM execution, into whether the
+/// execution succeeded and its return data, one byte per felt. Returns `None` for any other event.
+#[must_use]
+pub fn decode_execution_result(event: &Event) -> Option<(bool, Bytes)> {
+    if event.keys.first() != Some(&TRANSACTION_EXECUTED) {
+        return None;
+    }
+    let (response_len, data) = event.data.split_first()?;
+    let response_len = usize::try_from(u64::try_from(*response_len).ok()?).ok()?;
+    match data.get(response_len..)? {
+        [success, _gas_used] => {
+            let response =
+                data[..response_len].iter().map(|felt| u8::try_from(*felt).ok()).collect::<Option<Vec<_>>>()?;
+            Some((*success != FieldElement::ZERO, Bytes::from(response)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the reason of an EVM revert from its return data: the message of an `Error(string)`
+/// revert, or else the return data itself, hex encoded.
+#[must_use]
+pub fn revert_reason(return_data: &[u8]) -> String {
+    // `Error(string)`: the selector, then the offset, the length and the bytes of the message
+    let message = return_data.strip_prefix(&ERROR_STRING_SELECTOR).and_then(|encoded| {
+        let word_at = |at: usize| {
+            let word = U256::from_be_slice(encoded.get(at..at.checked_add(32)?)?);
+            usize::try_from(u64::try_from(word).ok()?).ok()
+        };
+        let offset = word_at(0)?;
+        let start = offset.checked_add(32)?;
+        String::from_utf8(encoded.get(start..start.checked_add(word_at(offset)?)?)?.to_vec()).ok()
+    });
+    message.unwrap_or_else(|| format!("0x{}", hex::encode(return_data)))
 }
 
 /// Slice the last 20 bytes of the field element and convert it to an Ethereum address
@@ -262,6 +703,42 @@ pub fn starknet_address_to_ethereum_address(starknet_address: &FieldElement) ->
     H160::from_slice(&starknet_address.to_bytes_be()[12..32])
 }
 
+/// Returns whether the Starknet address does not fit in 20 bytes, in which case converting it to
+/// an EVM address loses information.
+#[must_use]
+pub fn is_truncated_address(starknet_address: &FieldElement) -> bool {
+    starknet_address.to_bytes_be()[..12].iter().any(|byte| *byte != 0)
+}
+
+/// Converts a Starknet address without an EVM equivalent to an EVM address, applying `policy`
+/// when the address does not fit in 20 bytes.
+///
+/// ## Arguments
+///
+/// * `starknet_address` - The Starknet address to convert.
+/// * `policy` - What to do with addresses that do not fit in 20 bytes.
+///
+/// ## Returns
+///
+/// `Ok(Address)` - The EVM address.
+/// `Err(EthApiError)` if the address does not fit in 20 bytes and `policy` is `Error`.
+pub fn starknet_address_to_ethereum_address_with_policy(
+    starknet_address: &FieldElement,
+    policy: AddressConversionPolicy,
+) -> Result<Address, EthApiError> {
+    if !is_truncated_address(starknet_address) {
+        return Ok(starknet_address_to_ethereum_address(starknet_address));
+    }
+
+    match policy {
+        AddressConversionPolicy::Truncate => Ok(starknet_address_to_ethereum_address(starknet_address)),
+        AddressConversionPolicy::ZeroAddress => Ok(Address::zero()),
+        AddressConversionPolicy::Error => Err(EthApiError::OtherError(anyhow::anyhow!(
+            "Kakarot Core: Starknet address {starknet_address:#x} does not fit in an EVM address"
+        ))),
+    }
+}
+
 #[must_use]
 pub fn create_default_transaction_receipt() -> TransactionReceipt {
     TransactionReceipt {
@@ -289,48 +766,15 @@ pub fn create_default_transaction_receipt() -> TransactionReceipt {
     }
 }
 
-pub fn bytes_to_felt_vec(bytes: &Bytes) -> Vec<FieldElement> {
-    bytes.to_vec().into_iter().map(FieldElement::from).collect()
-}
-
-/// Author: <https://github.com/xJonathanLEI/starknet-rs/blob/447182a90839a3e4f096a01afe75ef474186d911/starknet-accounts/src/account/execution.rs#L166>
-/// Constructs the calldata for a raw Starknet invoke transaction call
-/// ## Arguments
-/// * `kakarot_address` - The Kakarot contract address
-/// * `bytes` - The calldata to be passed to the contract - RLP encoded raw EVM transaction
-///
-/// ## Returns
-/// * `Vec<FieldElement>` - The calldata for the raw Starknet invoke transaction call
-pub fn raw_starknet_calldata(kakarot_address: FieldElement, bytes: Bytes) -> Vec<FieldElement> {
-    let calls: Vec<Call> =
-        vec![Call { to: kakarot_address, selector: ETH_SEND_TRANSACTION, calldata: bytes_to_felt_vec(&bytes) }];
-    let mut concated_calldata: Vec<FieldElement> = vec![];
-    let mut execute_calldata: Vec<FieldElement> = vec![calls.len().into()];
-    for call in &calls {
-        execute_calldata.push(call.to); // to
-        execute_calldata.push(call.selector); // selector
-        execute_calldata.push(concated_calldata.len().into()); // data_offset
-        execute_calldata.push(call.calldata.len().into()); // data_len
-
-        for item in &call.calldata {
-            concated_calldata.push(*item);
-        }
-    }
-    execute_calldata.push(concated_calldata.len().into()); // calldata_len
-    for item in concated_calldata {
-        execute_calldata.push(item); // calldata
-    }
-
-    execute_calldata
-}
-
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
 
-    use reth_primitives::U256;
+    use reth_primitives::{AccessList, AccessListItem, TxEip1559, U256};
+    use starknet::core::types::{InvokeTransaction, Transaction};
 
     use super::*;
+    use crate::mock::wiremock_utils::{fixture_transaction, STARKNET_CHAIN_ID};
 
     fn to_vec_field_element(vec: Vec<&str>) -> Vec<FieldElement> {
         vec.into_iter().filter_map(|f| FieldElement::from_hex_be(f).ok()).collect()
@@ -527,28 +971,6 @@ mod tests {
         assert!(signature.odd_y_parity);
     }
 
-    #[test]
-    fn test_bytes_to_felt_vec() {
-        let bytes = Bytes::from(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
-        let felt_vec = bytes_to_felt_vec(&bytes);
-        assert_eq!(felt_vec.len(), 10);
-        assert_eq!(
-            felt_vec,
-            vec![
-                FieldElement::from(1_u64),
-                FieldElement::from(2_u64),
-                FieldElement::from(3_u64),
-                FieldElement::from(4_u64),
-                FieldElement::from(5_u64),
-                FieldElement::from(6_u64),
-                FieldElement::from(7_u64),
-                FieldElement::from(8_u64),
-                FieldElement::from(9_u64),
-                FieldElement::from(10_u64)
-            ]
-        );
-    }
-
     #[test]
     fn test_decode_eth_send_transaction_return() {
         let call_result = vec![
@@ -634,4 +1056,329 @@ mod tests {
         }
         assert_eq!(result[7], FeltOrFeltArray::Felt(FieldElement::from(0x0000_000f_ffff_u64)))
     }
+
+    #[test]
+    fn test_is_duplicate_transaction_error() {
+        let not_received = EthApiError::RequestError(ProviderError::StarknetError(
+            starknet::core::types::StarknetError::FailedToReceiveTransaction,
+        ));
+        assert!(!is_duplicate_transaction_error(&not_received));
+
+        let other = EthApiError::OtherError(anyhow::anyhow!("transaction already exists"));
+        assert!(!is_duplicate_transaction_error(&other));
+    }
+
+    #[test]
+    fn test_compute_invoke_v1_transaction_hash() {
+        // The first transaction of the fixtures block, sent on SN_GOERLI
+        let transaction: Transaction = serde_json::from_value(fixture_transaction()).unwrap();
+        let Transaction::Invoke(InvokeTransaction::V1(transaction)) = transaction else {
+            panic!("the fixture transaction is not an invoke v1 transaction");
+        };
+        let request = BroadcastedInvokeTransactionV1 {
+            max_fee: transaction.max_fee,
+            signature: transaction.signature,
+            nonce: transaction.nonce,
+            sender_address: transaction.sender_address,
+            calldata: transaction.calldata,
+        };
+
+        assert_eq!(compute_invoke_v1_transaction_hash(STARKNET_CHAIN_ID, &request), transaction.transaction_hash);
+        assert_eq!(
+            transaction.transaction_hash,
+            FieldElement::from_hex_be("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_erc20_balance_of_calldata() {
+        let owner = Address::from_str("0x2e11ed82f5ec165ab8ce3cc094f025fe7527f4d1").unwrap();
+
+        let calldata = erc20_balance_of_calldata(owner);
+
+        assert_eq!(
+            calldata,
+            Bytes::from(
+                hex::decode("70a082310000000000000000000000002e11ed82f5ec165ab8ce3cc094f025fe7527f4d1").unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_is_node_unavailable_error() {
+        assert!(is_node_unavailable_error(&EthApiError::RequestError(ProviderError::RateLimited)));
+
+        let rejected = EthApiError::RequestError(ProviderError::StarknetError(
+            starknet::core::types::StarknetError::FailedToReceiveTransaction,
+        ));
+        assert!(!is_node_unavailable_error(&rejected));
+    }
+
+    #[test]
+    fn test_is_transaction_not_found_error() {
+        let not_found = EthApiError::RequestError(ProviderError::StarknetError(StarknetError::TransactionHashNotFound));
+        assert!(is_transaction_not_found_error(&not_found));
+        assert!(!is_transaction_not_found_error(&EthApiError::RequestError(ProviderError::RateLimited)));
+    }
+
+    #[test]
+    fn test_is_missing_block_error() {
+        let not_found = EthApiError::RequestError(ProviderError::StarknetError(StarknetError::BlockNotFound));
+        assert!(is_missing_block_error(&not_found));
+        assert!(!is_missing_block_error(&EthApiError::RequestError(ProviderError::RateLimited)));
+    }
+
+    #[test]
+    fn test_starknet_address_to_ethereum_address_with_policy() {
+        let short_address = FieldElement::from_hex_be("0x2e11ed82f5ec165ab8ce3cc094f025fe7527f4d1").unwrap();
+        let long_address =
+            FieldElement::from_hex_be("0x5dcd266a80b8a5f29f04d779c6b166b80150c24f2180a75e82427242dab20a9").unwrap();
+        let truncated = starknet_address_to_ethereum_address(&long_address);
+
+        for policy in
+            [AddressConversionPolicy::Truncate, AddressConversionPolicy::ZeroAddress, AddressConversionPolicy::Error]
+        {
+            assert_eq!(
+                starknet_address_to_ethereum_address_with_policy(&short_address, policy).unwrap(),
+                Address::from_str("0x2e11ed82f5ec165ab8ce3cc094f025fe7527f4d1").unwrap()
+            );
+        }
+
+        assert!(is_truncated_address(&long_address));
+        assert_eq!(
+            starknet_address_to_ethereum_address_with_policy(&long_address, AddressConversionPolicy::Truncate).unwrap(),
+            truncated
+        );
+        assert_eq!(
+            starknet_address_to_ethereum_address_with_policy(&long_address, AddressConversionPolicy::ZeroAddress)
+                .unwrap(),
+            Address::zero()
+        );
+        assert!(
+            starknet_address_to_ethereum_address_with_policy(&long_address, AddressConversionPolicy::Error).is_err()
+        );
+    }
+
+    #[test]
+    fn test_confirmation_level() {
+        assert_eq!(ConfirmationLevel::from_str("L1").unwrap(), ConfirmationLevel::AcceptedOnL1);
+        assert_eq!(ConfirmationLevel::from_str("accepted_on_l2").unwrap(), ConfirmationLevel::AcceptedOnL2);
+        assert!(ConfirmationLevel::from_str("finalized").is_err());
+
+        let l2 = ConfirmationLevel::AcceptedOnL2;
+        let l1 = ConfirmationLevel::AcceptedOnL1;
+        assert!(!l2.is_awaiting_confirmation(&StarknetTransactionStatus::AcceptedOnL2));
+        assert!(l1.is_awaiting_confirmation(&StarknetTransactionStatus::AcceptedOnL2));
+        assert!(!l1.is_awaiting_confirmation(&StarknetTransactionStatus::AcceptedOnL1));
+        assert!(!l1.is_awaiting_confirmation(&StarknetTransactionStatus::Rejected));
+    }
+
+    #[test]
+    fn test_kakarot_deployment_from_str() {
+        let deployment = KakarotDeployment::from_str("0x1234:10-20").unwrap();
+
+        assert_eq!(
+            deployment,
+            KakarotDeployment { address: FieldElement::from_hex_be("0x1234").unwrap(), from_block: 10, to_block: 20 }
+        );
+        assert!(KakarotDeployment::from_str("0x1234:20-10").is_err());
+        assert!(KakarotDeployment::from_str("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_kakarot_addresses_in_range() {
+        let current = FieldElement::from(3u8);
+        let deployments = [
+            KakarotDeployment { address: FieldElement::from(2u8), from_block: 10, to_block: 19 },
+            KakarotDeployment { address: FieldElement::from(1u8), from_block: 0, to_block: 9 },
+        ];
+
+        assert_eq!(kakarot_address_at(&deployments, current, 5), FieldElement::from(1u8));
+        assert_eq!(kakarot_address_at(&deployments, current, 20), current);
+        assert_eq!(
+            kakarot_addresses_in_range(&deployments, current, 5, 25),
+            vec![(FieldElement::from(1u8), 5..=9), (FieldElement::from(2u8), 10..=19), (current, 20..=25)]
+        );
+        assert_eq!(kakarot_addresses_in_range(&deployments, current, 12, 15), vec![(FieldElement::from(2u8), 12..=15)]);
+        assert_eq!(kakarot_addresses_in_range(&[], current, 0, 5), vec![(current, 0..=5)]);
+    }
+
+    #[test]
+    fn test_create_address() {
+        let sender = Address::from_str("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap();
+
+        assert_eq!(create_address(sender, 0), Address::from_str("0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d").unwrap());
+        assert_eq!(create_address(sender, 1), Address::from_str("0x343c43a37d37dff08ae8c4a11544c718abb4fcf8").unwrap());
+        assert_eq!(create_address(sender, 2), Address::from_str("0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91").unwrap());
+    }
+
+    #[test]
+    fn test_intrinsic_gas() {
+        let transfer = Transaction::Eip1559(TxEip1559 {
+            to: TransactionKind::Call(Address::zero()),
+            input: Bytes::from(vec![0x00, 0x01]),
+            access_list: AccessList(vec![AccessListItem {
+                address: Address::zero(),
+                storage_keys: vec![H256::zero(), H256::zero()],
+            }]),
+            ..Default::default()
+        });
+        let deployment = Transaction::Eip1559(TxEip1559 { to: TransactionKind::Create, ..Default::default() });
+
+        assert_eq!(intrinsic_gas(&transfer), 21_000 + 4 + 16 + 2_400 + 2 * 1_900);
+        assert_eq!(intrinsic_gas(&deployment), 53_000);
+    }
+
+    #[test]
+    fn test_rewards_at_percentiles() {
+        let rewards = vec![(U256::from(3), 50_000), (U256::from(1), 21_000), (U256::from(2), 29_000)];
+
+        assert_eq!(
+            rewards_at_percentiles(rewards, &[0.0, 20.0, 30.0, 50.0, 100.0]),
+            vec![U256::from(1), U256::from(1), U256::from(2), U256::from(2), U256::from(3)]
+        );
+        assert_eq!(rewards_at_percentiles(vec![], &[10.0, 90.0]), vec![U256::ZERO, U256::ZERO]);
+    }
+
+    #[test]
+    fn test_logs_bloom() {
+        let log = Log {
+            address: Address::from_str("0x22341ae42d6dd7384bc8584e50419ea3ac75b83f").unwrap(),
+            topics: vec![H256::from_str("0x04491edcd115127caedbd478e2e7895ed80c7847e903431f94f9cfa579cad47f").unwrap()],
+            data: Bytes::default(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            removed: false,
+        };
+
+        let bloom = logs_bloom(&[log]);
+
+        // The 3 bits of the address and the 3 bits of the topic
+        let expected = Bloom::from_str(concat!(
+            "0000000000000000008100000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000002000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000400000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000001000000000000000000000",
+            "0000000000000400000000000000000000000000000000000000000000000000",
+        ))
+        .unwrap();
+        assert_eq!(bloom, expected);
+        assert_eq!(logs_bloom(&[]), Bloom::default());
+    }
+
+    #[test]
+    fn test_topic_from_low_high() {
+        // keccak256("Transfer(address,address,uint256)") split into its (low, high) halves
+        let low = FieldElement::from_hex_be("0x952ba7f163c4a11628f55a4df523b3ef").unwrap();
+        let high = FieldElement::from_hex_be("0xddf252ad1be2c89b69c2b068fc378daa").unwrap();
+
+        let topic = topic_from_low_high(&low, &high);
+
+        assert_eq!(
+            topic,
+            H256::from_str("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decode_native_transfer() {
+        let native_token = FieldElement::from_hex_be(STARKNET_NATIVE_TOKEN).unwrap();
+        let (from, to) = (FieldElement::from(1_u64), FieldElement::from(2_u64));
+        let [low, high] = split_u256_into_low_high(U256::from(u128::MAX) + U256::from(3));
+        let transfer = Event { from_address: native_token, keys: vec![TRANSFER], data: vec![from, to, low, high] };
+
+        assert_eq!(decode_native_transfer(&transfer), Some((from, to, U256::from(u128::MAX) + U256::from(3))));
+        // Transfers of other tokens and other events of the native token are ignored
+        let other_token = Event { from_address: FieldElement::from(3_u64), ..transfer.clone() };
+        assert_eq!(decode_native_transfer(&other_token), None);
+        let other_event = Event { keys: vec![FieldElement::from(4_u64)], ..transfer };
+        assert_eq!(decode_native_transfer(&other_event), None);
+    }
+
+    #[test]
+    fn test_decode_execution_result() {
+        // A failed execution returning 0xdead, after using 21000 gas
+        let data = [2_u64, 0xde, 0xad, 0, 21_000].map(FieldElement::from).to_vec();
+        let failure = Event { from_address: FieldElement::ONE, keys: vec![TRANSACTION_EXECUTED], data };
+
+        assert_eq!(decode_execution_result(&failure), Some((false, Bytes::from(vec![0xde, 0xad]))));
+        // Events which do not hold a whole execution result are ignored
+        let truncated = Event { data: failure.data[..3].to_vec(), ..failure.clone() };
+        assert_eq!(decode_execution_result(&truncated), None);
+        let other_event = Event { keys: vec![TRANSFER], ..failure };
+        assert_eq!(decode_execution_result(&other_event), None);
+    }
+
+    #[test]
+    fn test_revert_reason() {
+        // `revert("Not enough balance")`
+        let message = b"Not enough balance";
+        let mut return_data = ERROR_STRING_SELECTOR.to_vec();
+        return_data.extend_from_slice(&U256::from(32).to_be_bytes::<32>());
+        return_data.extend_from_slice(&U256::from(message.len()).to_be_bytes::<32>());
+        return_data.extend_from_slice(message);
+        return_data.resize(4 + 32 * 3, 0);
+
+        assert_eq!(revert_reason(&return_data), "Not enough balance");
+        // Custom errors and truncated messages are returned as is
+        assert_eq!(revert_reason(&[0xde, 0xad]), "0xdead");
+        assert_eq!(revert_reason(&return_data[..40]), format!("0x{}", hex::encode(&return_data[..40])));
+    }
+
+    #[test]
+    fn test_touched_addresses() {
+        let (sender, token, recipient) =
+            (Address::from_low_u64_be(1), Address::from_low_u64_be(2), Address::from_low_u64_be(3));
+        let transfer = Log {
+            address: token,
+            topics: vec![
+                H256::from_str("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").unwrap(),
+                H256::from(sender),
+                H256::from(recipient),
+            ],
+            data: Bytes::default(),
+            block_hash: None,
+            block_number: None,
+            transaction_hash: None,
+            transaction_index: None,
+            log_index: None,
+            removed: false,
+        };
+        let receipt = TransactionReceipt {
+            from: sender,
+            to: Some(token),
+            logs: vec![transfer],
+            ..create_default_transaction_receipt()
+        };
+
+        assert_eq!(touched_addresses(&receipt), vec![sender, token, recipient]);
+    }
+
+    #[test]
+    fn test_split_u256_into_low_high() {
+        let value = U256::from_str("0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef").unwrap();
+
+        let [low, high] = split_u256_into_low_high(value);
+
+        assert_eq!(low, FieldElement::from_hex_be("0x952ba7f163c4a11628f55a4df523b3ef").unwrap());
+        assert_eq!(high, FieldElement::from_hex_be("0xddf252ad1be2c89b69c2b068fc378daa").unwrap());
+        assert_eq!(topic_from_low_high(&low, &high), H256::from(value.to_be_bytes::<32>()));
+    }
+
+    #[test]
+    fn test_vec_felt_to_bytes() {
+        let felts = vec![FieldElement::ONE, FieldElement::from(2_u64)];
+
+        let bytes = vec_felt_to_bytes(&felts);
+
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(bytes[31], 1);
+        assert_eq!(bytes[63], 2);
+    }
 }
@@ -1,33 +1,49 @@
+pub mod block_guard;
 pub mod client_api;
 pub mod config;
 pub mod constants;
 pub mod errors;
+pub mod events;
+pub mod filters;
+pub mod gas_oracle;
 pub mod helpers;
-
+pub mod stale_cache;
+pub mod submission_queue;
+pub mod subscriptions;
+pub mod watchlist;
+pub mod webhooks;
+
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::ops::RangeInclusive;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use async_trait::async_trait;
-use constants::selectors::BYTECODE;
+use constants::selectors::{BYTECODE, STORAGE};
 use eyre::Result;
 use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use helpers::{
-    decode_eth_call_return, ethers_block_id_to_starknet_block_id, raw_starknet_calldata,
-    starknet_address_to_ethereum_address, vec_felt_to_bytes, FeltOrFeltArray,
+    compute_invoke_v1_transaction_hash, create_address, decode_eth_call_return, decode_execution_result,
+    decode_native_transfer, erc20_balance_of_calldata, ethers_block_id_to_starknet_block_id, intrinsic_gas,
+    is_contract_not_found_error, is_duplicate_transaction_error, is_missing_block_error, is_node_unavailable_error,
+    is_transaction_not_found_error, kakarot_address_at, kakarot_addresses_in_range, logs_bloom, revert_reason,
+    rewards_at_percentiles, split_u256_into_low_high, starknet_address_to_ethereum_address_with_policy,
+    topic_from_low_high, AddressConversionPolicy, ConfirmationLevel, FeltOrFeltArray, KakarotDeployment,
 };
 // TODO: all reth_primitives::rpc types should be replaced when native reth Log is implemented
 // https://github.com/paradigmxyz/reth/issues/1396#issuecomment-1440890689
-use reth_primitives::{
-    keccak256, Address, BlockId, BlockNumberOrTag, Bloom, Bytes, Bytes as RpcBytes, TransactionSigned, H160, H256,
-    U128, U256, U64, U8,
-};
-use reth_rlp::Decodable;
+use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bloom, Bytes, TransactionKind, H256, U128, U256, U64, U8};
 use reth_rpc_types::{
-    BlockTransactions, CallRequest, FeeHistory, Index, Log, RichBlock, SyncInfo, SyncStatus,
-    Transaction as EtherTransaction, TransactionReceipt,
+    BlockTransactions, CallRequest, FeeHistory, Filter, FilterBlockOption, FilteredParams, Index, Log, RichBlock,
+    SyncInfo, SyncStatus, Transaction as EtherTransaction, TransactionReceipt,
 };
 use starknet::core::types::{
-    BlockId as StarknetBlockId, BlockTag, BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV1, FieldElement,
-    FunctionCall, InvokeTransactionReceipt, MaybePendingBlockWithTxs, MaybePendingTransactionReceipt, SyncStatusType,
+    BlockId as StarknetBlockId, BlockStatus, BlockTag, BroadcastedInvokeTransaction, BroadcastedInvokeTransactionV1,
+    BroadcastedTransaction, EmittedEvent, Event, EventFilter, FieldElement, FunctionCall, InvokeTransactionReceipt,
+    MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, MaybePendingTransactionReceipt, SyncStatusType,
     Transaction as TransactionType, TransactionReceipt as StarknetTransactionReceipt,
     TransactionStatus as StarknetTransactionStatus,
 };
@@ -35,26 +51,90 @@ use starknet::providers::jsonrpc::{HttpTransport, JsonRpcClient};
 use starknet::providers::Provider;
 use url::Url;
 
+use self::block_guard::BlockGuard;
 use self::client_api::KakarotProvider;
 use self::config::StarknetConfig;
-use self::constants::gas::{BASE_FEE_PER_GAS, MAX_PRIORITY_FEE_PER_GAS};
-use self::constants::selectors::{BALANCE_OF, COMPUTE_STARKNET_ADDRESS, GET_EVM_ADDRESS};
-use self::constants::{MAX_FEE, STARKNET_NATIVE_TOKEN};
+use self::constants::error_messages::{
+    FEE_CAP_TOO_LOW, INSUFFICIENT_FUNDS, INSUFFICIENT_FUNDS_FOR_TRANSFER, INTRINSIC_GAS_TOO_LOW, NONCE_TOO_LOW,
+    OVERSIZED_DATA, TIP_ABOVE_FEE_CAP,
+};
+use self::constants::gas::{
+    BASE_FEE_PER_GAS, GAS_ORACLE_BLOCKS, GAS_ORACLE_BLOCKS_PER_SUGGESTION, GAS_ORACLE_PERCENTILE,
+    MAX_PRIORITY_FEE_PER_GAS,
+};
+use self::constants::requests::{
+    BLOCK_GUARD_WINDOW, FILTER_TIMEOUT_SECS, MAX_BLOCK_RANGE, MAX_CONCURRENT_REQUESTS, MAX_FILTERS,
+    MAX_LOGS_BLOCK_RANGE, TRANSACTION_POSITIONS_CACHE_SIZE, WATCHLIST_HISTORY_SIZE,
+};
+use self::constants::selectors::{
+    BALANCE_OF, COMPUTE_STARKNET_ADDRESS, EVM_CONTRACT_DEPLOYED, GET_EVM_ADDRESS, TRANSFER,
+};
+use self::constants::{
+    ACCOUNT_DEPLOYMENT_GAS, ESTIMATE_GAS, GAS_LIMIT, MAX_FEE, NATIVE_CURRENCY_DECIMALS, STARKNET_NATIVE_TOKEN,
+};
 use self::errors::EthApiError;
+use self::events::{find_last_l1_accepted_block, ChainFollower, ClientEvent, EventBus};
+use self::filters::{FilterChanges, FilterKind, FilterManager};
+use self::gas_oracle::GasOracle;
+use self::submission_queue::SubmissionQueue;
+use self::watchlist::Watchlist;
 use crate::client::constants::selectors::ETH_CALL;
-use crate::models::balance::{TokenBalance, TokenBalances};
-use crate::models::block::{BlockWithTxHashes, BlockWithTxs};
-use crate::models::convertible::{ConvertibleStarknetBlock, ConvertibleStarknetTransaction};
+use crate::codec::{
+    decode_signed_transaction, raw_starknet_calldata, raw_transaction_from_starknet_calldata, SignedTransaction,
+};
+use crate::models::balance::{BalanceChange, TokenBalance, TokenBalances};
+use crate::models::block::{BlockWithTxHashes, BlockWithTxs, StarknetBlockInfo};
+use crate::models::chain_metadata::{ChainMetadata, NativeCurrency};
+use crate::models::confirmation::{L1Confirmation, L1SettlementStatus};
+use crate::models::convertible::{ConvertibleStarknetBlock, ConvertibleStarknetEvent, ConvertibleStarknetTransaction};
+use crate::models::event::{RawKakarotEvent, StarknetEvent};
 use crate::models::felt::Felt252Wrapper;
-use crate::models::transaction::{StarknetTransaction, StarknetTransactions};
+use crate::models::metadata::{GasEstimate, TransactionMetadata};
+use crate::models::transaction::{pending_eth_transaction, StarknetTransaction, StarknetTransactions};
 
 pub struct KakarotClient<StarknetClient>
 where
     StarknetClient: Provider,
 {
     starknet_provider: StarknetClient,
+    fallback_provider: Option<StarknetClient>,
     kakarot_address: FieldElement,
     proxy_account_class_hash: FieldElement,
+    events_page_size: u64,
+    events_block_span: u64,
+    expose_starknet_block_info: bool,
+    request_timeout: Duration,
+    submission_queue: SubmissionQueue,
+    filters: FilterManager,
+    gas_oracle: GasOracle,
+    block_guard: BlockGuard,
+    events: EventBus,
+    follower: ChainFollower,
+    watchlist: Watchlist,
+    chain_id: u64,
+    max_transaction_input_size: usize,
+    previous_deployments: Vec<KakarotDeployment>,
+    chain_metadata: ChainMetadata,
+    starknet_chain_id: Mutex<Option<FieldElement>>,
+    wrapped_native_token: Option<Address>,
+    address_conversion_policy: AddressConversionPolicy,
+    prevalidate_transactions: bool,
+    confirmation_level: ConfirmationLevel,
+    default_block: BlockNumberOrTag,
+    transaction_positions: Mutex<VecDeque<(FieldElement, Arc<Vec<(FieldElement, TransactionPosition)>>)>>,
+}
+
+/// The position of a transaction in its block, and the logs emitted and the gas used by the
+/// transactions preceding it.
+#[derive(Debug, Clone, Copy, Default)]
+struct TransactionPosition {
+    /// The index of the transaction among the Starknet transactions of the block, as served by
+    /// `eth_getTransactionByBlockNumberAndIndex`.
+    index: u64,
+    logs_before: u64,
+    gas_used_before: U256,
+    /// The gas used by the transaction, derived from the fee it paid.
+    gas_used: U256,
 }
 
 impl KakarotClient<JsonRpcClient<HttpTransport>> {
@@ -68,724 +148,2355 @@ impl KakarotClient<JsonRpcClient<HttpTransport>> {
     ///
     /// `Err(EthApiError)` if the operation failed.
     pub fn new(starknet_config: StarknetConfig) -> Result<Self> {
-        let StarknetConfig { starknet_rpc, kakarot_address, proxy_account_class_hash } = starknet_config;
+        let StarknetConfig {
+            starknet_rpc,
+            fallback_starknet_rpc,
+            kakarot_address,
+            proxy_account_class_hash,
+            events_page_size,
+            events_block_span,
+            expose_starknet_block_info,
+            request_timeout_ms,
+            submission_queue_capacity,
+            submission_queue_path,
+            starknet_chain_id,
+            wrapped_native_token,
+            address_conversion_policy,
+            prevalidate_transactions,
+            confirmation_level,
+            chain_id,
+            max_transaction_input_size,
+            previous_deployments,
+            chain_name,
+            rpc_urls,
+            block_explorer_urls,
+            native_currency_name,
+            native_currency_symbol,
+            watched_addresses,
+            max_watched_addresses,
+            default_block,
+        } = starknet_config;
+        if request_timeout_ms == 0 {
+            return Err(eyre::eyre!("the request timeout should be greater than 0"));
+        }
+        if watched_addresses.len() > max_watched_addresses {
+            return Err(eyre::eyre!("at most {max_watched_addresses} addresses may be watched"));
+        }
+        if submission_queue_capacity > 0 && starknet_chain_id.is_none() {
+            return Err(eyre::eyre!("the Starknet chain id should be configured to enable the submission queue"));
+        }
+        let submission_queue = match submission_queue_path {
+            Some(path) => SubmissionQueue::persisted(submission_queue_capacity, path)?,
+            None => SubmissionQueue::new(submission_queue_capacity),
+        };
         let url = Url::parse(&starknet_rpc)?;
+        let fallback_provider = match fallback_starknet_rpc {
+            Some(fallback_starknet_rpc) => {
+                Some(JsonRpcClient::new(HttpTransport::new(Url::parse(&fallback_starknet_rpc)?)))
+            }
+            None => None,
+        };
         Ok(Self {
             starknet_provider: JsonRpcClient::new(HttpTransport::new(url)),
+            fallback_provider,
             kakarot_address,
             proxy_account_class_hash,
+            events_page_size,
+            events_block_span,
+            expose_starknet_block_info,
+            request_timeout: Duration::from_millis(request_timeout_ms),
+            submission_queue,
+            filters: FilterManager::new(Duration::from_secs(FILTER_TIMEOUT_SECS), MAX_FILTERS),
+            gas_oracle: GasOracle::new(GAS_ORACLE_BLOCKS, GAS_ORACLE_PERCENTILE, MAX_PRIORITY_FEE_PER_GAS),
+            block_guard: BlockGuard::new(BLOCK_GUARD_WINDOW),
+            events: EventBus::default(),
+            follower: ChainFollower::default(),
+            watchlist: Watchlist::new(watched_addresses, max_watched_addresses, WATCHLIST_HISTORY_SIZE),
+            chain_id,
+            max_transaction_input_size,
+            previous_deployments,
+            chain_metadata: ChainMetadata {
+                chain_id: U64::from(chain_id),
+                chain_name,
+                rpc_urls,
+                native_currency: NativeCurrency {
+                    name: native_currency_name,
+                    symbol: native_currency_symbol,
+                    decimals: NATIVE_CURRENCY_DECIMALS,
+                },
+                block_explorer_urls: (!block_explorer_urls.is_empty()).then_some(block_explorer_urls),
+            },
+            starknet_chain_id: Mutex::new(starknet_chain_id),
+            wrapped_native_token,
+            address_conversion_policy,
+            prevalidate_transactions,
+            confirmation_level,
+            default_block,
+            transaction_positions: Mutex::new(VecDeque::with_capacity(TRANSACTION_POSITIONS_CACHE_SIZE)),
         })
     }
 
-    /// Get the Ethereum address of a Starknet Kakarot smart-contract by calling `get_evm_address`
-    /// on it. If the contract's `get_evm_address` errors, returns the Starknet address sliced
-    /// to 20 bytes to conform with EVM addresses formats.
-    ///
-    /// ## Arguments
-    ///
-    /// * `starknet_address` - The Starknet address of the contract.
-    /// * `starknet_block_id` - The block id to query the contract at.
-    ///
-    /// ## Returns
+    /// Sends an invoke transaction to the Starknet node.
     ///
-    /// * `eth_address` - The Ethereum address of the contract.
-    pub async fn safe_get_evm_address(
+    /// Submissions are idempotent: if the node rejects the transaction because it already received
+    /// it, e.g. on a retry, the hash of the original transaction is returned instead of an error.
+    async fn add_invoke_transaction(
         &self,
-        starknet_address: &FieldElement,
-        starknet_block_id: &StarknetBlockId,
-    ) -> Address {
-        self.get_evm_address(starknet_address, starknet_block_id)
-            .await
-            .unwrap_or_else(|_| starknet_address_to_ethereum_address(starknet_address))
+        request: &BroadcastedInvokeTransactionV1,
+    ) -> Result<FieldElement, EthApiError> {
+        let transaction_result =
+            self.starknet_provider.add_invoke_transaction(&BroadcastedInvokeTransaction::V1(request.clone())).await;
+
+        match transaction_result {
+            Ok(transaction_result) => {
+                let transaction_hash = transaction_result.transaction_hash;
+                self.events.emit(ClientEvent::TxSubmitted(H256::from(transaction_hash.to_bytes_be())));
+                Ok(transaction_hash)
+            }
+            Err(err) => {
+                let err = EthApiError::from(err);
+                if !is_duplicate_transaction_error(&err) {
+                    return Err(err);
+                }
+                let chain_id = self.starknet_chain_id().await?;
+                Ok(compute_invoke_v1_transaction_hash(chain_id, request))
+            }
+        }
     }
-}
 
-#[async_trait]
-impl KakarotProvider for KakarotClient<JsonRpcClient<HttpTransport>> {
-    fn kakarot_address(&self) -> FieldElement {
-        self.kakarot_address
+    /// Returns the Starknet chain id, which is only fetched once when it is not configured. It is
+    /// always configured when the submission queue is enabled, so that the hashes of queued
+    /// transactions are computed without the node.
+    async fn starknet_chain_id(&self) -> Result<FieldElement, EthApiError> {
+        let cached_chain_id = *self.starknet_chain_id.lock().expect("chain id lock poisoned");
+        if let Some(chain_id) = cached_chain_id {
+            return Ok(chain_id);
+        }
+        let chain_id = self.starknet_provider.chain_id().await?;
+        *self.starknet_chain_id.lock().expect("chain id lock poisoned") = Some(chain_id);
+        Ok(chain_id)
     }
 
-    fn proxy_account_class_hash(&self) -> FieldElement {
-        self.proxy_account_class_hash
+    /// Returns the transaction with this hash waiting in the submission queue, as a transaction not
+    /// yet included in a block. The node is not queried, since it is unavailable while the queue is
+    /// not empty.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if Starknet rejected the transaction once it left the queue.
+    async fn queued_transaction(&self, hash: FieldElement) -> Result<Option<EtherTransaction>, EthApiError> {
+        self.check_queued_transaction_rejection(&hash)?;
+        if self.submission_queue.is_empty() {
+            return Ok(None);
+        }
+        let chain_id = self.starknet_chain_id().await?;
+        let request =
+            match self.submission_queue.find(|request| compute_invoke_v1_transaction_hash(chain_id, request) == hash) {
+                Some(request) => request,
+                None => return Ok(None),
+            };
+
+        let raw_transaction =
+            raw_transaction_from_starknet_calldata(&request.calldata).map_err(EthApiError::TransactionDecodingError)?;
+        let SignedTransaction { transaction, signer } =
+            decode_signed_transaction(&raw_transaction).map_err(EthApiError::TransactionDecodingError)?;
+        let nonce: Felt252Wrapper = request.nonce.into();
+
+        Ok(Some(pending_eth_transaction(
+            &transaction,
+            H256::from(hash.to_bytes_be()),
+            nonce.into(),
+            signer,
+            self.chain_id,
+        )))
     }
 
-    fn starknet_provider(&self) -> &JsonRpcClient<HttpTransport> {
-        &self.starknet_provider
+    /// Fails if Starknet rejected the queued transaction `hash`, whose hash was returned to its
+    /// sender, so that it is not reported as unknown.
+    fn check_queued_transaction_rejection(&self, hash: &FieldElement) -> Result<(), EthApiError> {
+        match self.submission_queue.rejection(hash) {
+            Some(reason) => Err(EthApiError::TransactionRejected(format!(
+                "queued transaction {hash:#x} was rejected by Starknet: {reason}"
+            ))),
+            None => Ok(()),
+        }
     }
 
-    /// Get the number of transactions in a block given a block id.
-    /// The number of transactions in a block.
-    ///
-    /// ## Arguments
-    ///
-    ///
-    ///
-    /// ## Returns
-    ///
-    ///  * `block_number(u64)` - The block number.
-    ///
-    /// `Ok(ContractClass)` if the operation was successful.
-    /// `Err(EthApiError)` if the operation failed.
-    async fn block_number(&self) -> Result<U64, EthApiError> {
-        let block_number = self.starknet_provider.block_number().await?;
-        Ok(block_number.into())
+    /// Returns the balance in Starknet's native token of the Starknet account `starknet_address`.
+    async fn native_token_balance(
+        &self,
+        starknet_address: FieldElement,
+        block_id: StarknetBlockId,
+    ) -> Result<U256, EthApiError> {
+        let request = FunctionCall {
+            // This FieldElement::from_hex_be cannot fail as the value is a constant
+            contract_address: FieldElement::from_hex_be(STARKNET_NATIVE_TOKEN).unwrap(),
+            entry_point_selector: BALANCE_OF,
+            calldata: vec![starknet_address],
+        };
+
+        let balance_felt = self.query_with_fallback(|provider| provider.call(&request, block_id)).await?;
+
+        let balance = balance_felt
+            .first()
+            .ok_or_else(|| {
+                EthApiError::OtherError(anyhow::anyhow!("Kakarot Core: Failed to get native token balance"))
+            })?
+            .to_bytes_be();
+
+        Ok(U256::from_be_bytes(balance))
     }
 
-    /// Get the block given a block id.
-    /// The block.
-    /// ## Arguments
-    /// * `block_id(StarknetBlockId)` - The block id.
-    /// * `hydrated_tx(bool)` - Whether to hydrate the transactions.
-    /// ## Returns
-    /// `Ok(RichBlock)` if the operation was successful.
-    /// `Err(EthApiError)` if the operation failed.
-    async fn get_eth_block_from_starknet_block(
+    /// Returns the balance of `owner` in the EVM ERC-20 `token`, by calling its `balanceOf`.
+    async fn erc20_balance(
         &self,
+        token: Address,
+        owner: Address,
         block_id: StarknetBlockId,
-        hydrated_tx: bool,
-    ) -> Result<RichBlock, EthApiError> {
-        if hydrated_tx {
-            let block = self.starknet_provider.get_block_with_txs(block_id).await?;
-            let starknet_block = BlockWithTxs::new(block);
-            starknet_block.to_eth_block(self).await
-        } else {
-            let block = self.starknet_provider.get_block_with_tx_hashes(block_id).await?;
-            let starknet_block = BlockWithTxHashes::new(block);
-            starknet_block.to_eth_block(self).await
-        }
+    ) -> Result<U256, EthApiError> {
+        let calldata = erc20_balance_of_calldata(owner);
+        let return_data = self.call_view(token, calldata, block_id).await?;
+
+        U256::try_from_be_slice(&return_data).ok_or_else(|| {
+            EthApiError::OtherError(anyhow::anyhow!("Kakarot Core: Failed to decode the balance of ERC-20 {token}"))
+        })
     }
 
-    /// Get the number of transactions in a block given a block id.
-    /// The number of transactions in a block.
+    /// Wraps a raw EVM transaction in a Starknet invoke transaction and submits it.
     ///
     /// ## Arguments
     ///
-    ///
-    ///
-    /// ## Returns
-    ///
-    ///  * `block_number(u64)` - The block number.
-    ///
-    /// `Ok(Bytes)` if the operation was successful.
-    /// `Err(EthApiError)` if the operation failed.
-    async fn get_code(
-        &self,
-        ethereum_address: Address,
-        starknet_block_id: StarknetBlockId,
-    ) -> Result<Bytes, EthApiError> {
-        // Convert the hex-encoded string to a FieldElement
-        let ethereum_address: Felt252Wrapper = ethereum_address.into();
-        let ethereum_address = ethereum_address.into();
-
-        // Prepare the calldata for the get_starknet_contract_address function call
-        let tx_calldata_vec = vec![ethereum_address];
-        let request = FunctionCall {
-            contract_address: self.kakarot_address,
-            entry_point_selector: COMPUTE_STARKNET_ADDRESS,
-            calldata: tx_calldata_vec,
-        };
-        // Make the function call to get the Starknet contract address
-        let starknet_contract_address = self.starknet_provider.call(request, starknet_block_id).await?;
-
-        // shadow the variable to FielElement from a Vec<FieldElement>, for use in subsequent code
-        let starknet_contract_address = match starknet_contract_address.get(0) {
-            Some(x) if starknet_contract_address.len() == 1 => *x,
-            _ => {
+    /// * `bytes` - The raw RLP encoded EVM transaction.
+    /// * `prevalidate` - Whether to reject transactions which would fail before submitting them.
+    async fn send_raw_transaction(&self, bytes: Bytes, prevalidate: bool) -> Result<H256, EthApiError> {
+        let SignedTransaction { transaction, signer: evm_address } = decode_signed_transaction(&bytes)?;
+
+        // Transactions without a chain id predate EIP-155 and are not replay protected
+        if let Some(chain_id) = transaction.chain_id() {
+            if chain_id != self.chain_id {
                 return Err(EthApiError::OtherError(anyhow::anyhow!(
-                    "Kakarot get_code: starknet_contract_address is empty"
+                    "Kakarot send_transaction: invalid chain id {chain_id}, expected {}",
+                    self.chain_id
                 )));
             }
-        };
+        }
 
-        // Prepare the calldata for the bytecode function call
-        let request = FunctionCall {
-            contract_address: starknet_contract_address,
-            entry_point_selector: BYTECODE,
-            calldata: vec![],
-        };
-        // Make the function call to get the contract bytecode
-        let contract_bytecode = self.starknet_provider.call(request, starknet_block_id).await?;
-        // Convert the result of the function call to a vector of bytes
-        let contract_bytecode_in_u8: Vec<u8> = contract_bytecode.into_iter().flat_map(|x| x.to_bytes_be()).collect();
-        let bytes_result = Bytes::from(contract_bytecode_in_u8);
-        Ok(bytes_result)
-    }
+        // Reject the transactions Ethereum nodes would, with the same errors, before building calldata
+        let input_size = transaction.input().len();
+        if input_size > self.max_transaction_input_size {
+            return Err(EthApiError::OtherError(anyhow::anyhow!(
+                "{OVERSIZED_DATA}: input size {input_size}, limit {}",
+                self.max_transaction_input_size
+            )));
+        }
+        let intrinsic_gas = intrinsic_gas(&transaction.transaction);
+        if transaction.gas_limit() < intrinsic_gas {
+            return Err(EthApiError::OtherError(anyhow::anyhow!(
+                "{INTRINSIC_GAS_TOO_LOW}: have {}, want {intrinsic_gas}",
+                transaction.gas_limit()
+            )));
+        }
+        let max_fee_per_gas = transaction.max_fee_per_gas();
+        if let Some(max_priority_fee_per_gas) = transaction.max_priority_fee_per_gas() {
+            if max_priority_fee_per_gas > max_fee_per_gas {
+                return Err(EthApiError::OtherError(anyhow::anyhow!(
+                    "{TIP_ABOVE_FEE_CAP}: maxPriorityFeePerGas {max_priority_fee_per_gas}, maxFeePerGas \
+                     {max_fee_per_gas}"
+                )));
+            }
+        }
+        let base_fee = u128::from(BASE_FEE_PER_GAS);
+        if max_fee_per_gas < base_fee {
+            return Err(EthApiError::OtherError(anyhow::anyhow!(
+                "{FEE_CAP_TOO_LOW}: maxFeePerGas {max_fee_per_gas}, baseFee {base_fee}"
+            )));
+        }
 
-    // Return the bytecode as a Result<Bytes>
-    async fn call_view(
-        &self,
-        ethereum_address: Address,
-        calldata: Bytes,
-        starknet_block_id: StarknetBlockId,
-    ) -> Result<Bytes, EthApiError> {
-        let ethereum_address: Felt252Wrapper = ethereum_address.into();
-        let ethereum_address = ethereum_address.into();
+        // The transaction is sent from the account of its sender as of now, whatever block the
+        // reads default to
+        let starknet_block_id = StarknetBlockId::Tag(BlockTag::Pending);
 
-        let mut calldata_vec = calldata.clone().into_iter().map(FieldElement::from).collect::<Vec<FieldElement>>();
+        let starknet_address = self.compute_starknet_address(evm_address, &starknet_block_id).await?;
 
-        let mut call_parameters =
-            vec![ethereum_address, FieldElement::MAX, FieldElement::ZERO, FieldElement::ZERO, calldata.len().into()];
+        let nonce = FieldElement::from(transaction.nonce());
 
-        call_parameters.append(&mut calldata_vec);
+        let calldata = raw_starknet_calldata(self.kakarot_address, bytes);
 
-        let request = FunctionCall {
-            contract_address: self.kakarot_address,
-            entry_point_selector: ETH_CALL,
-            calldata: call_parameters,
-        };
+        // The gas price does not reflect the Starknet fee yet, so the fee is not bounded by the
+        // fee fields of the EVM transaction
+        let max_fee = *MAX_FEE;
 
-        let call_result: Vec<FieldElement> = self.starknet_provider.call(request, starknet_block_id).await?;
+        let signature = vec![];
 
-        // Parse and decode Kakarot's call return data (temporary solution and not scalable - will
-        // fail is Kakarot API changes)
-        // Declare Vec of Result
-        // TODO: Change to decode based on ABI or use starknet-rs future feature to decode return
-        // params
-        let segmented_result = decode_eth_call_return(&call_result)?;
+        let request =
+            BroadcastedInvokeTransactionV1 { max_fee, signature, nonce, sender_address: starknet_address, calldata };
 
-        // Convert the result of the function call to a vector of bytes
-        let return_data = segmented_result.first().ok_or_else(|| {
-            EthApiError::OtherError(anyhow::anyhow!("Cannot parse and decode last argument of Kakarot call",))
-        })?;
-        if let FeltOrFeltArray::FeltArray(felt_array) = return_data {
-            let result: Vec<u8> = felt_array.iter().filter_map(|f| u8::try_from(*f).ok()).collect();
-            let bytes_result = Bytes::from(result);
-            return Ok(bytes_result);
+        if prevalidate {
+            self.prevalidate_transaction(evm_address, U256::from(transaction.value()), &request).await?;
         }
-        Err(EthApiError::OtherError(anyhow::anyhow!("Cannot parse and decode the return data of Kakarot call")))
-    }
 
-    /// Get the syncing status of the light client
-    /// # Arguments
-    /// # Returns
-    ///  `Ok(SyncStatus)` if the operation was successful.
-    ///  `Err(EthApiError)` if the operation failed.
-    async fn syncing(&self) -> Result<SyncStatus, EthApiError> {
-        let status = self.starknet_provider.syncing().await?;
+        let starknet_transaction_hash = self.submit_starknet_transaction(request).await?;
 
-        match status {
-            SyncStatusType::NotSyncing => Ok(SyncStatus::None),
+        Ok(starknet_transaction_hash)
+    }
 
-            SyncStatusType::Syncing(data) => {
-                let starting_block: U256 = U256::from(data.starting_block_num);
-                let current_block: U256 = U256::from(data.current_block_num);
-                let highest_block: U256 = U256::from(data.highest_block_num);
-                let warp_chunks_amount: Option<U256> = None;
-                let warp_chunks_processed: Option<U256> = None;
+    /// Rejects a transaction which would fail: either its sender cannot pay its value, or its
+    /// execution fails when simulated with `starknet_estimateFee` on the pending block.
+    async fn prevalidate_transaction(
+        &self,
+        sender: Address,
+        value: U256,
+        request: &BroadcastedInvokeTransactionV1,
+    ) -> Result<(), EthApiError> {
+        let block_id = StarknetBlockId::Tag(BlockTag::Pending);
+
+        if value > U256::ZERO {
+            let balance = self.balance(sender, block_id).await?;
+            if balance < value {
+                return Err(EthApiError::OtherError(anyhow::anyhow!(
+                    "Kakarot send_transaction: insufficient funds for transfer, balance {balance} is lower than value \
+                     {value}"
+                )));
+            }
+        }
 
-                let status_info = SyncInfo {
-                    starting_block,
-                    current_block,
-                    highest_block,
-                    warp_chunks_amount,
-                    warp_chunks_processed,
-                };
+        let transaction = BroadcastedTransaction::Invoke(BroadcastedInvokeTransaction::V1(request.clone()));
+        match self.starknet_provider.estimate_fee(vec![transaction], block_id).await {
+            Ok(_) => Ok(()),
+            Err(err) => {
+                let err = EthApiError::from(err);
+                if is_node_unavailable_error(&err) {
+                    return Err(err);
+                }
+                Err(EthApiError::OtherError(anyhow::anyhow!(
+                    "Kakarot send_transaction: transaction fails in simulation: {err}"
+                )))
+            }
+        }
+    }
 
-                Ok(SyncStatus::Info(status_info))
+    /// Runs a query of historical data against the Starknet node, retrying it against the fallback
+    /// node, if any, when the block is missing from the Starknet node, e.g. because it was pruned,
+    /// or when the transaction is missing from the Starknet node while it is behind the fallback
+    /// node.
+    ///
+    /// Every Starknet call pinned to a block or a transaction goes through this method, so that all
+    /// the calls of a request about a pruned block are served by the fallback node.
+    async fn query_with_fallback<'a, T, E, F, Fut>(&'a self, query: F) -> Result<T, EthApiError>
+    where
+        F: Fn(&'a JsonRpcClient<HttpTransport>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        EthApiError: From<E>,
+    {
+        Ok(self.query_with_fallback_node(query).await?.1)
+    }
+
+    /// Runs a query as [`KakarotClient::query_with_fallback`] does, returning the node which
+    /// served it along with its result, for the queries continued on the same node.
+    async fn query_with_fallback_node<'a, T, E, F, Fut>(
+        &'a self,
+        query: F,
+    ) -> Result<(&'a JsonRpcClient<HttpTransport>, T), EthApiError>
+    where
+        F: Fn(&'a JsonRpcClient<HttpTransport>) -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+        EthApiError: From<E>,
+    {
+        let result = query(&self.starknet_provider).await.map_err(EthApiError::from);
+        let fallback_provider = match (result, &self.fallback_provider) {
+            (Err(err), Some(fallback_provider)) if is_missing_block_error(&err) => fallback_provider,
+            // A transaction unknown to the primary node is only looked up on the fallback node
+            // if the primary node is behind it, as it is unknown to both otherwise
+            (Err(err), Some(fallback_provider)) if is_transaction_not_found_error(&err) => {
+                if !self.is_behind(fallback_provider).await {
+                    return Err(err);
+                }
+                fallback_provider
             }
+            (result, _) => return Ok((&self.starknet_provider, result?)),
+        };
+        Ok((fallback_provider, query(fallback_provider).await?))
+    }
+
+    /// Returns whether the head of the primary node is behind the head of `fallback_provider`,
+    /// `false` if either head cannot be fetched.
+    async fn is_behind(&self, fallback_provider: &JsonRpcClient<HttpTransport>) -> bool {
+        match futures::join!(self.starknet_provider.block_number(), fallback_provider.block_number()) {
+            (Ok(head), Ok(fallback_head)) => head < fallback_head,
+            _ => false,
         }
     }
 
-    /// Get the number of transactions in a block given a block number.
-    /// The number of transactions in a block.
+    /// Get the number of the last block accepted on L1.
     ///
-    /// # Arguments
+    /// The block is tracked by the chain follower while the chain is followed, otherwise it is
+    /// found by bisecting the whole chain. Returns 0 if no block is accepted on L1 yet.
+    async fn last_l1_accepted_block(&self) -> Result<u64, EthApiError> {
+        if let Some(block_number) = self.follower.last_l1_accepted_block() {
+            return Ok(block_number);
+        }
+        find_last_l1_accepted_block(self, 0, self.block_number().await?.as_u64()).await
+    }
+
+    /// Get all the Kakarot events emitted between `from_block` and `to_block` (inclusive).
     ///
-    /// * `number(u64)` - The block number.
+    /// The block range is split into disjoint sub-ranges of `events_block_span` blocks, at most
+    /// `MAX_CONCURRENT_REQUESTS` of which are fetched concurrently. Within a sub-range, pages of
+    /// `events_page_size` events are requested sequentially until the node stops returning a
+    /// continuation token.
     ///
-    /// # Returns
+    /// ## Arguments
     ///
-    ///  * `transaction_count(U64)` - The number of transactions.
+    /// * `from_block` - The first block of the range.
+    /// * `to_block` - The last block of the range.
+    /// * `keys` - The Starknet event keys to filter on, if any.
     ///
-    /// `Ok(U64)` if the operation was successful.
-    /// `Err(EthApiError)` if the operation failed.
-    async fn block_transaction_count_by_number(&self, number: BlockNumberOrTag) -> Result<U64, EthApiError> {
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(BlockId::Number(number))?;
-        self.get_transaction_count_by_block(starknet_block_id).await
+    /// ## Returns
+    ///
+    /// * `events` - The emitted events, ordered by block.
+    pub async fn get_kakarot_events(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        keys: Option<Vec<Vec<FieldElement>>>,
+    ) -> Result<Vec<EmittedEvent>, EthApiError> {
+        if from_block > to_block {
+            return Ok(vec![]);
+        }
+
+        let span = self.events_block_span.max(1);
+        // Each sub-range is queried on the Kakarot deployment which served it
+        let sub_ranges =
+            kakarot_addresses_in_range(&self.previous_deployments, self.kakarot_address, from_block, to_block)
+                .into_iter()
+                .flat_map(|(address, range)| {
+                    let (from_block, to_block) = range.into_inner();
+                    (from_block..=to_block)
+                        .step_by(usize::try_from(span).unwrap_or(usize::MAX))
+                        .map(move |start| (address, start, to_block.min(start.saturating_add(span - 1))))
+                });
+
+        let handles = sub_ranges.map(|(address, start, end)| {
+            let filter = EventFilter {
+                from_block: Some(StarknetBlockId::Number(start)),
+                to_block: Some(StarknetBlockId::Number(end)),
+                address: Some(address),
+                keys: keys.clone(),
+            };
+            self.get_events_with_continuation(filter)
+        });
+
+        let mut events = Vec::new();
+        for sub_range_events in stream::iter(handles).buffered(MAX_CONCURRENT_REQUESTS).collect::<Vec<_>>().await {
+            events.extend(sub_range_events?);
+        }
+
+        Ok(events)
     }
 
-    /// Get the number of transactions in a block given a block hash.
-    /// The number of transactions in a block.
-    /// # Arguments
-    /// * `hash(H256)` - The block hash.
-    /// # Returns
+    /// Converts the receipt of a Kakarot invoke transaction into an EVM receipt.
     ///
-    ///  * `transaction_count(U64)` - The number of transactions.
+    /// ## Arguments
     ///
-    /// `Ok(U64)` if the operation was successful.
-    /// `Err(EthApiError)` if the operation failed.
-    async fn block_transaction_count_by_hash(&self, hash: H256) -> Result<U64, EthApiError> {
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(BlockId::Hash(hash.into()))?;
-        self.get_transaction_count_by_block(starknet_block_id).await
-    }
-
-    async fn get_transaction_count_by_block(&self, starknet_block_id: StarknetBlockId) -> Result<U64, EthApiError> {
-        let starknet_block = self.starknet_provider.get_block_with_txs(starknet_block_id).await?;
+    /// * `starknet_tx` - The Starknet transaction of the receipt.
+    /// * `receipt` - The Starknet receipt.
+    /// * `position` - The position of the transaction in its block.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(None)` if the transaction has not reached the confirmation level yet.
+    async fn invoke_receipt_to_eth_receipt(
+        &self,
+        starknet_tx: StarknetTransaction,
+        receipt: InvokeTransactionReceipt,
+        position: TransactionPosition,
+    ) -> Result<Option<TransactionReceipt>, EthApiError> {
+        let InvokeTransactionReceipt { transaction_hash, actual_fee, status, block_hash, block_number, events, .. } =
+            receipt;
+
+        // The transaction has no receipt until it reaches the confirmation level
+        if self.confirmation_level.is_awaiting_confirmation(&status) {
+            return Ok(None);
+        }
 
-        let block_transactions = match starknet_block {
-            MaybePendingBlockWithTxs::PendingBlock(pending_block_with_txs) => {
-                self.filter_starknet_into_eth_txs(pending_block_with_txs.transactions.into(), None, None).await?
-            }
-            MaybePendingBlockWithTxs::Block(block_with_txs) => {
-                let block_hash: Felt252Wrapper = block_with_txs.block_hash.into();
-                let block_hash = Some(block_hash.into());
-                let block_number: Felt252Wrapper = block_with_txs.block_number.into();
-                let block_number = Some(block_number.into());
-                self.filter_starknet_into_eth_txs(block_with_txs.transactions.into(), block_hash, block_number).await?
+        // Pin every lookup to the block of the transaction
+        let starknet_block_id = StarknetBlockId::Hash(block_hash);
+
+        let transaction_hash: Felt252Wrapper = transaction_hash.into();
+        let transaction_hash: Option<H256> = Some(transaction_hash.into());
+
+        let block_hash: Felt252Wrapper = block_hash.into();
+        let block_hash: Option<H256> = Some(block_hash.into());
+
+        let block_number: Felt252Wrapper = block_number.into();
+        let block_number: Option<U256> = Some(block_number.into());
+
+        let eth_tx = starknet_tx.to_eth_transaction(self, block_hash, block_number, None).await?;
+        let from = eth_tx.from;
+
+        // The recipient is the target of the original EVM transaction, and is null for contract
+        // creations, which instead have a contract address: the address announced by Kakarot for
+        // the deployed contract, or else derived from the sender and nonce as `CREATE` does
+        let transaction = starknet_tx.eth_transaction();
+        let (to, contract_address) = match &transaction {
+            Ok(transaction) => match transaction.kind() {
+                TransactionKind::Create => {
+                    let kakarot_address = self.kakarot_address_at(&starknet_block_id).await?;
+                    let deployed_address = events
+                        .iter()
+                        .find(|event| {
+                            event.from_address == kakarot_address && event.keys.first() == Some(&EVM_CONTRACT_DEPLOYED)
+                        })
+                        .and_then(|event| event.data.first());
+                    let contract_address = match deployed_address {
+                        Some(evm_address) => Felt252Wrapper::from(*evm_address).into(),
+                        None => create_address(from, transaction.nonce()),
+                    };
+                    (None, Some(contract_address))
+                }
+                TransactionKind::Call(to) => (Some(*to), None),
+            },
+            // Transactions which do not wrap an EVM transaction are served without a recipient
+            Err(_) => (None, None),
+        };
+        // Transactions which do not wrap an EVM transaction are served as legacy transactions
+        let transaction_type = transaction.map_or(0, |transaction| u8::from(transaction.tx_type()));
+
+        // Spec v0.3 receipts carry the Starknet fee, but neither the EVM gas of the transaction
+        // nor the Starknet gas price it was charged at, so the gas used is derived from the fee
+        // at the gas price the transaction is served with
+        let gas_price = self.base_fee_per_gas();
+        let gas_used = self.gas_used_by_fee(U256::from(Felt252Wrapper::from(actual_fee)));
+
+        // Spec v0.3 receipts have no reverted status: an EVM execution which fails either fails
+        // the Starknet transaction, which is rejected, or is reported by the execution result
+        // emitted by the account of the sender. Pending transactions have no status until their
+        // block is produced
+        let reverted = self.execution_result(&events).map_or(false, |(success, _)| !success);
+        let status_code = match status {
+            StarknetTransactionStatus::Rejected => Some(U64::from(0)),
+            StarknetTransactionStatus::AcceptedOnL2 | StarknetTransactionStatus::AcceptedOnL1 => {
+                Some(U64::from(u64::from(!reverted)))
             }
+            StarknetTransactionStatus::Pending => None,
         };
-        let len = match block_transactions {
-            BlockTransactions::Full(transactions) => transactions.len(),
-            BlockTransactions::Hashes(_) => 0,
-            BlockTransactions::Uncle => 0,
+
+        let transaction_index = Some(U256::from(position.index));
+
+        // Only the events emitted by Kakarot are EVM logs, the fee transfer is not. They are
+        // converted as `eth_getLogs` converts them, so that a log is the same wherever it is served
+        let mut logs = Vec::new();
+        let kakarot_events = events.into_iter().filter(|event| self.is_kakarot_address(&event.from_address));
+        for (log_index, event) in (position.logs_before..).zip(kakarot_events) {
+            let log = StarknetEvent::new(event)
+                .to_eth_log(
+                    self,
+                    block_hash,
+                    block_number,
+                    transaction_hash,
+                    Some(U256::from(log_index)),
+                    transaction_index,
+                )
+                .await?;
+            logs.push(log);
+        }
+
+        let logs_bloom = logs_bloom(&logs);
+
+        Ok(Some(TransactionReceipt {
+            transaction_hash,
+            transaction_index,
+            block_hash,
+            block_number,
+            from,
+            to,
+            cumulative_gas_used: position.gas_used_before + gas_used,
+            gas_used: Some(gas_used),
+            contract_address,
+            logs,
+            state_root: None, // TODO: Fetch real data
+            logs_bloom,
+            status_code,
+            effective_gas_price: U128::from(u128::try_from(gas_price).unwrap_or(u128::MAX)),
+            transaction_type: U8::from(transaction_type),
+        }))
+    }
+
+    /// Returns whether the EVM execution of a transaction succeeded, with its return data, from
+    /// the events of its receipt. The execution result is emitted by the account of the sender,
+    /// the events emitted by Kakarot are logs.
+    fn execution_result(&self, events: &[Event]) -> Option<(bool, Bytes)> {
+        events.iter().filter(|event| !self.is_kakarot_address(&event.from_address)).find_map(decode_execution_result)
+    }
+
+    /// Returns the gas used by a transaction which paid the Starknet fee `fee`, in wei, so that
+    /// the gas used times the effective gas price of the transaction is the fee it paid.
+    fn gas_used_by_fee(&self, fee: U256) -> U256 {
+        fee.checked_div(self.base_fee_per_gas()).unwrap_or_default()
+    }
+
+    /// Returns the Starknet fee paid by an invoke transaction included in a block, in wei and
+    /// saturated to a `u64`.
+    async fn actual_fee(&self, transaction_hash: H256) -> Result<u64, EthApiError> {
+        let transaction_hash: Felt252Wrapper = transaction_hash.try_into()?;
+        let transaction_hash = FieldElement::from(transaction_hash);
+        let actual_fee = match self
+            .query_with_fallback(|provider| provider.get_transaction_receipt(transaction_hash))
+            .await?
+        {
+            MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(receipt)) => receipt.actual_fee,
+            _ => FieldElement::ZERO,
         };
-        Ok(U64::from(len))
+        Ok(u64::try_from(U256::from(Felt252Wrapper::from(actual_fee))).unwrap_or(u64::MAX))
     }
 
-    async fn transaction_by_block_id_and_index(
+    /// Returns the position of a transaction in its block, with the logs emitted by the
+    /// transactions preceding it, which are the events emitted by Kakarot, and the gas they used.
+    async fn transaction_position(
         &self,
-        block_id: StarknetBlockId,
-        tx_index: Index,
-    ) -> Result<EtherTransaction, EthApiError> {
-        let index: u64 = usize::from(tx_index) as u64;
+        block_hash: FieldElement,
+        transaction_hash: FieldElement,
+    ) -> Result<TransactionPosition, EthApiError> {
+        let positions = self.transaction_positions(block_hash).await?;
+        find_transaction_position(&positions, block_hash, transaction_hash)
+    }
 
-        let starknet_tx: StarknetTransaction =
-            self.starknet_provider.get_transaction_by_block_id_and_index(block_id, index).await?.into();
+    /// Returns the position of every transaction of a block.
+    ///
+    /// The transactions are read in order from the block, the logs they emit from the events
+    /// emitted by Kakarot in the block, and the gas they use from the fees they transfer in the
+    /// native token, so that a block costs three Starknet queries whatever its number of
+    /// transactions. Blocks identified by their hash do not change, so the positions of the last
+    /// `TRANSACTION_POSITIONS_CACHE_SIZE` blocks are cached.
+    async fn transaction_positions(
+        &self,
+        block_hash: FieldElement,
+    ) -> Result<Arc<Vec<(FieldElement, TransactionPosition)>>, EthApiError> {
+        let cached = self
+            .transaction_positions
+            .lock()
+            .expect("transaction positions lock poisoned")
+            .iter()
+            .find(|(hash, _)| *hash == block_hash)
+            .map(|(_, positions)| positions.clone());
+        if let Some(positions) = cached {
+            return Ok(positions);
+        }
 
-        let tx_hash: FieldElement = starknet_tx.transaction_hash()?.into();
+        let block_id = StarknetBlockId::Hash(block_hash);
+        let (transactions, kakarot_block_id, pending) =
+            match self.query_with_fallback(|provider| provider.get_block_with_tx_hashes(block_id)).await? {
+                MaybePendingBlockWithTxHashes::Block(block) => {
+                    (block.transactions, StarknetBlockId::Number(block.block_number), false)
+                }
+                MaybePendingBlockWithTxHashes::PendingBlock(block) => {
+                    (block.transactions, StarknetBlockId::Tag(BlockTag::Pending), true)
+                }
+            };
+        let event_filter = EventFilter {
+            from_block: Some(block_id),
+            to_block: Some(block_id),
+            address: Some(self.kakarot_address_at(&kakarot_block_id).await?),
+            keys: None,
+        };
+        let (events, transfers) = futures::join!(
+            self.get_events_with_continuation(event_filter),
+            self.get_events_with_continuation(native_transfer_filter(block_id, block_id))
+        );
+        let (events, transfers) = (events?, transfers?);
+
+        // Events are served in the order of the transactions emitting them
+        let mut events = events.iter().filter(|event| self.is_kakarot_address(&event.from_address)).peekable();
+        let mut fees = fees_of_transfers(transfers).into_iter().peekable();
+        let (mut logs_before, mut gas_used_before) = (0, U256::ZERO);
+        let mut positions = Vec::with_capacity(transactions.len());
+        for (index, hash) in (0u64..).zip(transactions) {
+            let fee = fees.next_if(|(_, transaction_hash, _)| *transaction_hash == hash);
+            let gas_used = fee.map_or(U256::ZERO, |(_, _, fee)| self.gas_used_by_fee(fee));
+            positions.push((hash, TransactionPosition { index, logs_before, gas_used_before, gas_used }));
+            while events.next_if(|event| event.transaction_hash == hash).is_some() {
+                logs_before += 1;
+            }
+            gas_used_before += gas_used;
+        }
+        let positions = Arc::new(positions);
 
-        let tx_receipt = self.starknet_provider.get_transaction_receipt(tx_hash).await?;
-        let (block_hash, block_num) = match tx_receipt {
-            MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(tr)) => {
-                let block_hash: Felt252Wrapper = tr.block_hash.into();
-                (Some(block_hash.into()), Some(U256::from(tr.block_number)))
+        // The pending block grows until it is produced
+        if !pending {
+            let mut cache = self.transaction_positions.lock().expect("transaction positions lock poisoned");
+            if cache.len() == TRANSACTION_POSITIONS_CACHE_SIZE {
+                cache.pop_front();
             }
-            _ => (None, None), // skip all transactions other than Invoke, covers the pending case
+            cache.push_back((block_hash, positions.clone()));
+        }
+        Ok(positions)
+    }
+
+    /// Returns the address of the Kakarot deployment which served a block.
+    ///
+    /// Blocks given by hash are resolved to their number when previous deployments are
+    /// configured. Tagged blocks are always served by the current deployment.
+    pub async fn kakarot_address_at(&self, starknet_block_id: &StarknetBlockId) -> Result<FieldElement, EthApiError> {
+        if self.previous_deployments.is_empty() {
+            return Ok(self.kakarot_address);
+        }
+
+        let block_number = match starknet_block_id {
+            StarknetBlockId::Number(block_number) => *block_number,
+            StarknetBlockId::Hash(_) => {
+                match self.query_with_fallback(|provider| provider.get_block_with_tx_hashes(starknet_block_id)).await? {
+                    MaybePendingBlockWithTxHashes::Block(block) => block.block_number,
+                    MaybePendingBlockWithTxHashes::PendingBlock(_) => return Ok(self.kakarot_address),
+                }
+            }
+            StarknetBlockId::Tag(_) => return Ok(self.kakarot_address),
         };
 
-        let eth_tx = starknet_tx.to_eth_transaction(self, block_hash, block_num, Some(U256::from(index))).await?;
-        Ok(eth_tx)
+        Ok(kakarot_address_at(&self.previous_deployments, self.kakarot_address, block_number))
     }
 
-    /// Returns the Starknet address associated with a given Ethereum address.
+    /// Get all the events matching the filter, transparently following the continuation tokens
+    /// returned by `starknet_getEvents`.
+    ///
+    /// Continuation tokens are only valid on the node which issued them, so every page is fetched
+    /// from the node which served the first one.
+    async fn get_events_with_continuation(&self, filter: EventFilter) -> Result<Vec<EmittedEvent>, EthApiError> {
+        let (provider, page) = self
+            .query_with_fallback_node(|provider| provider.get_events(filter.clone(), None, self.events_page_size))
+            .await?;
+        let mut events = page.events;
+        let mut continuation_token = page.continuation_token;
+
+        while let Some(token) = continuation_token {
+            let page = provider.get_events(filter.clone(), Some(token), self.events_page_size).await?;
+            events.extend(page.events);
+            continuation_token = page.continuation_token;
+        }
+
+        Ok(events)
+    }
+
+    /// Get the Ethereum address of a Starknet Kakarot smart-contract by calling `get_evm_address`
+    /// on it. If the contract's `get_evm_address` errors, converts the Starknet address according
+    /// to the configured `AddressConversionPolicy`.
     ///
     /// ## Arguments
-    /// * `ethereum_address` - The Ethereum address to convert to a Starknet address.
-    /// * `starknet_block_id` - The block ID to use for the Starknet contract call.
-    async fn compute_starknet_address(
+    ///
+    /// * `starknet_address` - The Starknet address of the contract.
+    /// * `starknet_block_id` - The block id to query the contract at.
+    ///
+    /// ## Returns
+    ///
+    /// * `eth_address` - The Ethereum address of the contract.
+    pub async fn safe_get_evm_address(
         &self,
-        ethereum_address: Address,
+        starknet_address: &FieldElement,
         starknet_block_id: &StarknetBlockId,
-    ) -> Result<FieldElement, EthApiError> {
-        let ethereum_address: Felt252Wrapper = ethereum_address.into();
-        let ethereum_address = ethereum_address.into();
+    ) -> Result<Address, EthApiError> {
+        match self.get_evm_address(starknet_address, starknet_block_id).await {
+            Ok(address) => Ok(address),
+            Err(_) => {
+                starknet_address_to_ethereum_address_with_policy(starknet_address, self.address_conversion_policy)
+            }
+        }
+    }
 
-        let request = FunctionCall {
-            contract_address: self.kakarot_address,
-            entry_point_selector: COMPUTE_STARKNET_ADDRESS,
-            calldata: vec![ethereum_address],
+    /// Returns the EVM address of a Kakarot account, or `None` if the Starknet contract is not a
+    /// Kakarot account, e.g. the sequencer collecting the fees.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the class or the EVM address of the contract cannot be fetched.
+    pub async fn kakarot_account_evm_address(
+        &self,
+        starknet_address: &FieldElement,
+        starknet_block_id: &StarknetBlockId,
+    ) -> Result<Option<Address>, EthApiError> {
+        let class_hash = match self
+            .query_with_fallback(|provider| provider.get_class_hash_at(starknet_block_id, *starknet_address))
+            .await
+        {
+            Ok(class_hash) => class_hash,
+            Err(err) => match EthApiError::from(err) {
+                err if is_contract_not_found_error(&err) => return Ok(None),
+                err => return Err(err),
+            },
         };
+        if class_hash != self.proxy_account_class_hash {
+            return Ok(None);
+        }
 
-        let starknet_contract_address = self.starknet_provider.call(request, starknet_block_id).await?;
+        Ok(Some(self.get_evm_address(starknet_address, starknet_block_id).await?))
+    }
+}
 
-        let result = starknet_contract_address.first().ok_or_else(|| {
-            EthApiError::OtherError(anyhow::anyhow!("Kakarot Core: Failed to get Starknet address from Kakarot"))
-        })?;
+#[async_trait]
+impl KakarotProvider for KakarotClient<JsonRpcClient<HttpTransport>> {
+    fn kakarot_address(&self) -> FieldElement {
+        self.kakarot_address
+    }
 
-        Ok(*result)
+    fn is_kakarot_address(&self, address: &FieldElement) -> bool {
+        *address == self.kakarot_address
+            || self.previous_deployments.iter().any(|deployment| deployment.address == *address)
     }
 
-    async fn transaction_by_hash(&self, hash: H256) -> Result<EtherTransaction, EthApiError> {
-        let hash: Felt252Wrapper = hash.try_into()?;
+    fn proxy_account_class_hash(&self) -> FieldElement {
+        self.proxy_account_class_hash
+    }
 
-        let transaction: StarknetTransaction =
-            self.starknet_provider.get_transaction_by_hash::<FieldElement>(hash.clone().into()).await?.into();
-        let hash: FieldElement = hash.into();
-        let tx_receipt = self.starknet_provider.get_transaction_receipt(hash).await?;
-        let (block_hash, block_num) = match tx_receipt {
-            MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(tr)) => {
-                let block_hash: Felt252Wrapper = tr.block_hash.into();
-                (Some(block_hash.into()), Some(U256::from(tr.block_number)))
+    fn starknet_provider(&self) -> &JsonRpcClient<HttpTransport> {
+        &self.starknet_provider
+    }
+
+    fn expose_starknet_block_info(&self) -> bool {
+        self.expose_starknet_block_info
+    }
+
+    fn address_conversion_policy(&self) -> AddressConversionPolicy {
+        self.address_conversion_policy
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn default_block_id(&self) -> BlockId {
+        BlockId::Number(self.default_block)
+    }
+
+    fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Returns the bus broadcasting the events of the client.
+    fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Returns the guard against the upstream blocks inconsistent with the blocks served before.
+    fn block_guard(&self) -> &BlockGuard {
+        &self.block_guard
+    }
+
+    /// Returns the follower of the chain, which broadcasts the events of new blocks.
+    fn follower(&self) -> &ChainFollower {
+        &self.follower
+    }
+
+    /// Returns the addresses watched by operators, with their recent activity.
+    fn watchlist(&self) -> &Watchlist {
+        &self.watchlist
+    }
+
+    /// Returns the `wallet_addEthereumChain` parameters of the chain, from the configuration.
+    fn chain_metadata(&self) -> ChainMetadata {
+        self.chain_metadata.clone()
+    }
+
+    /// Converts an EVM block id to a Starknet block id.
+    ///
+    /// The `safe` and `finalized` tags resolve to the latest block, unless transactions are only
+    /// confirmed once accepted on L1, in which case they resolve to the last block accepted on L1.
+    async fn resolve_block_id(&self, block_id: BlockId) -> Result<StarknetBlockId, EthApiError> {
+        match block_id {
+            BlockId::Number(BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized)
+                if self.confirmation_level == ConfirmationLevel::AcceptedOnL1 =>
+            {
+                Ok(StarknetBlockId::Number(self.last_l1_accepted_block().await?))
             }
-            _ => (None, None), // skip all transactions other than Invoke, covers the pending case
+            _ => ethers_block_id_to_starknet_block_id(block_id),
+        }
+    }
+
+    /// Get the number of transactions in a block given a block id.
+    /// The number of transactions in a block.
+    ///
+    /// ## Arguments
+    ///
+    ///
+    ///
+    /// ## Returns
+    ///
+    ///  * `block_number(u64)` - The block number.
+    ///
+    /// `Ok(ContractClass)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn block_number(&self) -> Result<U64, EthApiError> {
+        let block_number = self.starknet_provider.block_number().await?;
+        Ok(block_number.into())
+    }
+
+    /// Returns whether the block `block_number` is accepted on L1.
+    async fn is_accepted_on_l1(&self, block_number: u64) -> Result<bool, EthApiError> {
+        let block = self
+            .query_with_fallback(|provider| provider.get_block_with_tx_hashes(StarknetBlockId::Number(block_number)))
+            .await?;
+        match block {
+            MaybePendingBlockWithTxHashes::Block(block) => Ok(matches!(block.status, BlockStatus::AcceptedOnL1)),
+            MaybePendingBlockWithTxHashes::PendingBlock(_) => Ok(false),
+        }
+    }
+
+    /// Get the block given a block id.
+    /// The block.
+    /// ## Arguments
+    /// * `block_id(StarknetBlockId)` - The block id.
+    /// * `hydrated_tx(bool)` - Whether to hydrate the transactions.
+    /// ## Returns
+    /// `Ok(RichBlock)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn get_eth_block_from_starknet_block(
+        &self,
+        block_id: StarknetBlockId,
+        hydrated_tx: bool,
+    ) -> Result<RichBlock, EthApiError> {
+        let block = if hydrated_tx {
+            let block = self.query_with_fallback(|provider| provider.get_block_with_txs(block_id)).await?;
+            BlockWithTxs::new(block).to_eth_block(self).await?
+        } else {
+            let block = self.query_with_fallback(|provider| provider.get_block_with_tx_hashes(block_id)).await?;
+            BlockWithTxHashes::new(block).to_eth_block(self).await?
         };
-        let eth_transaction = transaction.to_eth_transaction(self, block_hash, block_num, None).await?;
-        Ok(eth_transaction)
+
+        // Pending blocks have no number, and are not ordered with the produced blocks yet
+        if let Some(number) = block.header.number {
+            let number = u64::try_from(number).unwrap_or(u64::MAX);
+            self.block_guard.check_block(number, u64::try_from(block.header.timestamp).unwrap_or(u64::MAX))?;
+        }
+
+        Ok(block)
     }
 
-    async fn submit_starknet_transaction(&self, request: BroadcastedInvokeTransactionV1) -> Result<H256, EthApiError> {
-        let transaction_result =
-            self.starknet_provider.add_invoke_transaction(&BroadcastedInvokeTransaction::V1(request)).await?;
+    /// Get a contiguous range of EVM blocks.
+    ///
+    /// The blocks are fetched `MAX_CONCURRENT_REQUESTS` at once. The range may not exceed
+    /// `MAX_BLOCK_RANGE` blocks.
+    ///
+    /// ## Arguments
+    ///
+    /// * `from_block(u64)` - The first block of the range.
+    /// * `to_block(u64)` - The last block of the range (inclusive).
+    /// * `hydrated_tx(bool)` - Whether to hydrate the transactions.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(Vec<RichBlock>)` - The blocks, ordered by number, if the operation was successful.
+    /// `Err(EthApiError)` if the range is invalid or a block could not be fetched.
+    async fn get_eth_blocks_in_range(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        hydrated_tx: bool,
+    ) -> Result<Vec<RichBlock>, EthApiError> {
+        if to_block < from_block {
+            return Err(EthApiError::OtherError(anyhow::anyhow!(
+                "Kakarot Core: invalid block range, from block {from_block} is after to block {to_block}"
+            )));
+        }
+        if to_block - from_block >= MAX_BLOCK_RANGE {
+            return Err(EthApiError::OtherError(anyhow::anyhow!(
+                "Kakarot Core: block range exceeds the maximum of {MAX_BLOCK_RANGE} blocks"
+            )));
+        }
+
+        let handles = (from_block..=to_block).map(|block_number| {
+            self.get_eth_block_from_starknet_block(StarknetBlockId::Number(block_number), hydrated_tx)
+        });
+        stream::iter(handles)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    /// Get the Starknet block underlying an EVM block.
+    ///
+    /// ## Arguments
+    ///
+    /// * `block_id(StarknetBlockId)` - The block id.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(StarknetBlockInfo)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn starknet_block_info(&self, block_id: StarknetBlockId) -> Result<StarknetBlockInfo, EthApiError> {
+        let block = self.query_with_fallback(|provider| provider.get_block_with_tx_hashes(block_id)).await?;
+        Ok(BlockWithTxHashes::new(block).starknet_block_info())
+    }
+
+    /// Get the number of transactions in a block given a block id.
+    /// The number of transactions in a block.
+    ///
+    /// ## Arguments
+    ///
+    ///
+    ///
+    /// ## Returns
+    ///
+    ///  * `block_number(u64)` - The block number.
+    ///
+    /// `Ok(Bytes)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn get_code(
+        &self,
+        ethereum_address: Address,
+        starknet_block_id: StarknetBlockId,
+    ) -> Result<Bytes, EthApiError> {
+        // Convert the hex-encoded string to a FieldElement
+        let ethereum_address: Felt252Wrapper = ethereum_address.into();
+        let ethereum_address = ethereum_address.into();
+
+        // Prepare the calldata for the get_starknet_contract_address function call
+        let tx_calldata_vec = vec![ethereum_address];
+        let request = FunctionCall {
+            contract_address: self.kakarot_address_at(&starknet_block_id).await?,
+            entry_point_selector: COMPUTE_STARKNET_ADDRESS,
+            calldata: tx_calldata_vec,
+        };
+        // Make the function call to get the Starknet contract address
+        let starknet_contract_address =
+            self.query_with_fallback(|provider| provider.call(&request, starknet_block_id)).await?;
+
+        // shadow the variable to FielElement from a Vec<FieldElement>, for use in subsequent code
+        let starknet_contract_address = match starknet_contract_address.get(0) {
+            Some(x) if starknet_contract_address.len() == 1 => *x,
+            _ => {
+                return Err(EthApiError::OtherError(anyhow::anyhow!(
+                    "Kakarot get_code: starknet_contract_address is empty"
+                )));
+            }
+        };
+
+        // Prepare the calldata for the bytecode function call
+        let request = FunctionCall {
+            contract_address: starknet_contract_address,
+            entry_point_selector: BYTECODE,
+            calldata: vec![],
+        };
+        // Make the function call to get the contract bytecode
+        let contract_bytecode = self.query_with_fallback(|provider| provider.call(&request, starknet_block_id)).await?;
+        // Convert the result of the function call to a vector of bytes
+        let contract_bytecode_in_u8: Vec<u8> = contract_bytecode.into_iter().flat_map(|x| x.to_bytes_be()).collect();
+        let bytes_result = Bytes::from(contract_bytecode_in_u8);
+        Ok(bytes_result)
+    }
+
+    /// Get the value of a storage slot of an EVM contract.
+    ///
+    /// ## Arguments
+    ///
+    /// * `ethereum_address(Address)` - The address of the contract.
+    /// * `index(U256)` - The storage slot.
+    /// * `starknet_block_id(StarknetBlockId)` - The block to read the storage at.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(H256)` - The value of the slot, zero for contracts which are not deployed.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn storage_at(
+        &self,
+        ethereum_address: Address,
+        index: U256,
+        starknet_block_id: StarknetBlockId,
+    ) -> Result<H256, EthApiError> {
+        let starknet_contract_address = self.compute_starknet_address(ethereum_address, &starknet_block_id).await?;
+
+        // Kakarot contract accounts expose their storage as a Uint256 to Uint256 mapping
+        let request = FunctionCall {
+            contract_address: starknet_contract_address,
+            entry_point_selector: STORAGE,
+            calldata: split_u256_into_low_high(index).to_vec(),
+        };
+        let value = match self.query_with_fallback(|provider| provider.call(&request, starknet_block_id)).await {
+            Ok(value) => value,
+            Err(err) if is_contract_not_found_error(&err) => return Ok(H256::zero()),
+            Err(err) => return Err(err),
+        };
+
+        match value.as_slice() {
+            [low, high] => Ok(topic_from_low_high(low, high)),
+            _ => Err(EthApiError::OtherError(anyhow::anyhow!(
+                "Kakarot Core: storage of {ethereum_address} returned {} values instead of a Uint256",
+                value.len()
+            ))),
+        }
+    }
+
+    // Return the bytecode as a Result<Bytes>
+    async fn call_view(
+        &self,
+        ethereum_address: Address,
+        calldata: Bytes,
+        starknet_block_id: StarknetBlockId,
+    ) -> Result<Bytes, EthApiError> {
+        let ethereum_address: Felt252Wrapper = ethereum_address.into();
+        let ethereum_address = ethereum_address.into();
+
+        let mut calldata_vec = calldata.clone().into_iter().map(FieldElement::from).collect::<Vec<FieldElement>>();
+
+        let mut call_parameters =
+            vec![ethereum_address, FieldElement::MAX, FieldElement::ZERO, FieldElement::ZERO, calldata.len().into()];
+
+        call_parameters.append(&mut calldata_vec);
+
+        let request = FunctionCall {
+            contract_address: self.kakarot_address_at(&starknet_block_id).await?,
+            entry_point_selector: ETH_CALL,
+            calldata: call_parameters,
+        };
+
+        let call_result: Vec<FieldElement> =
+            self.query_with_fallback(|provider| provider.call(&request, starknet_block_id)).await?;
+
+        // Parse and decode Kakarot's call return data (temporary solution and not scalable - will
+        // fail is Kakarot API changes)
+        // Declare Vec of Result
+        // TODO: Change to decode based on ABI or use starknet-rs future feature to decode return
+        // params
+        let segmented_result = decode_eth_call_return(&call_result)?;
+
+        // Convert the result of the function call to a vector of bytes
+        let return_data = segmented_result.first().ok_or_else(|| {
+            EthApiError::OtherError(anyhow::anyhow!("Cannot parse and decode last argument of Kakarot call",))
+        })?;
+        if let FeltOrFeltArray::FeltArray(felt_array) = return_data {
+            let result: Vec<u8> = felt_array.iter().filter_map(|f| u8::try_from(*f).ok()).collect();
+            let bytes_result = Bytes::from(result);
+            return Ok(bytes_result);
+        }
+        Err(EthApiError::OtherError(anyhow::anyhow!("Cannot parse and decode the return data of Kakarot call")))
+    }
+
+    /// Get the syncing status of the light client
+    /// # Arguments
+    /// # Returns
+    ///  `Ok(SyncStatus)` if the operation was successful.
+    ///  `Err(EthApiError)` if the operation failed.
+    async fn syncing(&self) -> Result<SyncStatus, EthApiError> {
+        let status = self.starknet_provider.syncing().await?;
+
+        match status {
+            SyncStatusType::NotSyncing => Ok(SyncStatus::None),
+
+            SyncStatusType::Syncing(data) => {
+                let starting_block: U256 = U256::from(data.starting_block_num);
+                let current_block: U256 = U256::from(data.current_block_num);
+                let highest_block: U256 = U256::from(data.highest_block_num);
+                let warp_chunks_amount: Option<U256> = None;
+                let warp_chunks_processed: Option<U256> = None;
+
+                let status_info = SyncInfo {
+                    starting_block,
+                    current_block,
+                    highest_block,
+                    warp_chunks_amount,
+                    warp_chunks_processed,
+                };
+
+                Ok(SyncStatus::Info(status_info))
+            }
+        }
+    }
+
+    /// Get the number of transactions in a block given a block number.
+    /// The number of transactions in a block.
+    ///
+    /// # Arguments
+    ///
+    /// * `number(u64)` - The block number.
+    ///
+    /// # Returns
+    ///
+    ///  * `transaction_count(U64)` - The number of transactions.
+    ///
+    /// `Ok(U64)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn block_transaction_count_by_number(&self, number: BlockNumberOrTag) -> Result<U64, EthApiError> {
+        let starknet_block_id = self.resolve_block_id(BlockId::Number(number)).await?;
+        self.get_transaction_count_by_block(starknet_block_id).await
+    }
+
+    /// Get the number of transactions in a block given a block hash.
+    /// The number of transactions in a block.
+    /// # Arguments
+    /// * `hash(H256)` - The block hash.
+    /// # Returns
+    ///
+    ///  * `transaction_count(U64)` - The number of transactions.
+    ///
+    /// `Ok(U64)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn block_transaction_count_by_hash(&self, hash: H256) -> Result<U64, EthApiError> {
+        let starknet_block_id = ethers_block_id_to_starknet_block_id(BlockId::Hash(hash.into()))?;
+        self.get_transaction_count_by_block(starknet_block_id).await
+    }
+
+    async fn get_transaction_count_by_block(&self, starknet_block_id: StarknetBlockId) -> Result<U64, EthApiError> {
+        let starknet_block =
+            self.query_with_fallback(|provider| provider.get_block_with_txs(starknet_block_id)).await?;
+
+        let block_transactions = match starknet_block {
+            MaybePendingBlockWithTxs::PendingBlock(pending_block_with_txs) => {
+                self.filter_starknet_into_eth_txs(pending_block_with_txs.transactions.into(), None, None).await?
+            }
+            MaybePendingBlockWithTxs::Block(block_with_txs) => {
+                let block_hash: Felt252Wrapper = block_with_txs.block_hash.into();
+                let block_hash = Some(block_hash.into());
+                let block_number: Felt252Wrapper = block_with_txs.block_number.into();
+                let block_number = Some(block_number.into());
+                self.filter_starknet_into_eth_txs(block_with_txs.transactions.into(), block_hash, block_number).await?
+            }
+        };
+        let len = match block_transactions {
+            BlockTransactions::Full(transactions) => transactions.len(),
+            BlockTransactions::Hashes(_) => 0,
+            BlockTransactions::Uncle => 0,
+        };
+        Ok(U64::from(len))
+    }
+
+    async fn transaction_by_block_id_and_index(
+        &self,
+        block_id: StarknetBlockId,
+        tx_index: Index,
+    ) -> Result<EtherTransaction, EthApiError> {
+        let index: u64 = usize::from(tx_index) as u64;
+
+        let starknet_tx: StarknetTransaction = self
+            .query_with_fallback(|provider| provider.get_transaction_by_block_id_and_index(block_id, index))
+            .await?
+            .into();
+
+        let tx_hash: FieldElement = starknet_tx.transaction_hash()?.into();
+
+        let tx_receipt = self.query_with_fallback(|provider| provider.get_transaction_receipt(tx_hash)).await?;
+        let (block_hash, block_num) = match tx_receipt {
+            MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(tr)) => {
+                let block_hash: Felt252Wrapper = tr.block_hash.into();
+                (Some(block_hash.into()), Some(U256::from(tr.block_number)))
+            }
+            _ => (None, None), // skip all transactions other than Invoke, covers the pending case
+        };
+
+        let eth_tx = starknet_tx.to_eth_transaction(self, block_hash, block_num, Some(U256::from(index))).await?;
+        Ok(eth_tx)
+    }
+
+    /// Returns the Starknet address associated with a given Ethereum address.
+    ///
+    /// ## Arguments
+    /// * `ethereum_address` - The Ethereum address to convert to a Starknet address.
+    /// * `starknet_block_id` - The block ID to use for the Starknet contract call.
+    async fn compute_starknet_address(
+        &self,
+        ethereum_address: Address,
+        starknet_block_id: &StarknetBlockId,
+    ) -> Result<FieldElement, EthApiError> {
+        let ethereum_address: Felt252Wrapper = ethereum_address.into();
+        let ethereum_address = ethereum_address.into();
+
+        let request = FunctionCall {
+            contract_address: self.kakarot_address_at(starknet_block_id).await?,
+            entry_point_selector: COMPUTE_STARKNET_ADDRESS,
+            calldata: vec![ethereum_address],
+        };
+
+        let starknet_contract_address =
+            self.query_with_fallback(|provider| provider.call(&request, starknet_block_id)).await?;
+
+        let result = starknet_contract_address.first().ok_or_else(|| {
+            EthApiError::OtherError(anyhow::anyhow!("Kakarot Core: Failed to get Starknet address from Kakarot"))
+        })?;
+
+        Ok(*result)
+    }
+
+    /// Returns a transaction by transaction hash.
+    ///
+    /// Transactions which are not included in a block yet, because they wait in the submission
+    /// queue or in the Starknet mempool, are returned with null block fields, as geth does for
+    /// the transactions of its pool.
+    async fn transaction_by_hash(&self, hash: H256) -> Result<EtherTransaction, EthApiError> {
+        let hash: Felt252Wrapper = hash.try_into()?;
+        let hash: FieldElement = hash.into();
+
+        if let Some(transaction) = self.queued_transaction(hash).await? {
+            return Ok(transaction);
+        }
+
+        let transaction: StarknetTransaction =
+            self.query_with_fallback(|provider| provider.get_transaction_by_hash::<FieldElement>(hash)).await?.into();
+        let (block_hash, block_num) =
+            match self.query_with_fallback(|provider| provider.get_transaction_receipt(hash)).await {
+                Ok(MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(tr))) => {
+                    let block_hash: Felt252Wrapper = tr.block_hash.into();
+                    (Some(block_hash.into()), Some(U256::from(tr.block_number)))
+                }
+                Ok(_) => (None, None), // skip all transactions other than Invoke, covers the pending case
+                // Transactions in the mempool have no receipt yet
+                Err(err) => match EthApiError::from(err) {
+                    err if is_transaction_not_found_error(&err) => (None, None),
+                    err => return Err(err),
+                },
+            };
+        let eth_transaction = transaction.to_eth_transaction(self, block_hash, block_num, None).await?;
+        Ok(eth_transaction)
+    }
+
+    /// Returns the raw signed EVM transaction wrapped by a Kakarot transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash(H256)` - The transaction hash.
+    ///
+    /// # Returns
+    ///
+    /// * `Bytes` - The RLP encoded EVM transaction, as originally sent to `eth_sendRawTransaction`.
+    async fn raw_transaction_by_hash(&self, hash: H256) -> Result<Bytes, EthApiError> {
+        let hash: Felt252Wrapper = hash.try_into()?;
+        let hash = FieldElement::from(hash);
+
+        let transaction: StarknetTransaction =
+            self.query_with_fallback(|provider| provider.get_transaction_by_hash(hash)).await?.into();
+        if !transaction.is_kakarot_tx(self, StarknetBlockId::Tag(BlockTag::Pending)).await? {
+            return Err(EthApiError::OtherError(anyhow::anyhow!("Kakarot Filter: Tx is not part of Kakarot")));
+        }
+
+        transaction.raw_eth_transaction()
+    }
+
+    /// Submits an invoke transaction to Starknet.
+    ///
+    /// If the submission queue is enabled and the node is unavailable, the transaction is queued
+    /// and its hash returned right away. It is submitted by `flush_submission_queue` once the node
+    /// is back. While transactions are queued, new ones are queued behind them to keep their order.
+    async fn submit_starknet_transaction(&self, request: BroadcastedInvokeTransactionV1) -> Result<H256, EthApiError> {
+        if self.submission_queue.is_empty() {
+            match self.add_invoke_transaction(&request).await {
+                Err(err) if self.submission_queue.is_enabled() && is_node_unavailable_error(&err) => {}
+                result => return result.map(|transaction_hash| H256::from(transaction_hash.to_bytes_be())),
+            }
+        }
+
+        let chain_id = self.starknet_chain_id().await?;
+        let transaction_hash = compute_invoke_v1_transaction_hash(chain_id, &request);
+        if !self.submission_queue.push(request) {
+            return Err(EthApiError::OtherError(anyhow::anyhow!(
+                "Starknet node unavailable and submission queue full"
+            )));
+        }
+
+        Ok(H256::from(transaction_hash.to_bytes_be()))
+    }
+
+    fn is_submission_queue_enabled(&self) -> bool {
+        self.submission_queue.is_enabled()
+    }
+
+    fn submission_queue_depth(&self) -> usize {
+        self.submission_queue.len()
+    }
+
+    /// Submits the queued transactions, in order, until the queue is empty or the node is
+    /// unavailable again. Transactions rejected by the node are dropped, and their rejection is
+    /// reported to the requests about them.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(usize)` - The number of transactions removed from the queue.
+    async fn flush_submission_queue(&self) -> Result<usize, EthApiError> {
+        let mut flushed = 0;
+        while let Some(request) = self.submission_queue.front() {
+            match self.add_invoke_transaction(&request).await {
+                Err(err) if is_node_unavailable_error(&err) => break,
+                Err(err) => {
+                    let chain_id = self.starknet_chain_id().await?;
+                    let transaction_hash = compute_invoke_v1_transaction_hash(chain_id, &request);
+                    log::warn!("Dropping queued transaction {transaction_hash:#x} rejected by Starknet: {err}");
+                    self.submission_queue.record_rejection(transaction_hash, err.to_string());
+                }
+                Ok(_) => {}
+            }
+            self.submission_queue.pop_front();
+            flushed += 1;
+        }
+        Ok(flushed)
+    }
+
+    /// Returns the receipt of a transaction by transaction hash.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash(H256)` - The transaction hash.
+    ///
+    /// # Returns
+    ///
+    ///  * `transaction_receipt(TransactionReceipt)` - The transaction receipt.
+    ///
+    /// `Ok(Option<TransactionReceipt>)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>, EthApiError> {
+        // TODO: Error when trying to transform 32 bytes hash to FieldElement
+        let transaction_hash: Felt252Wrapper = hash.try_into()?;
+        let transaction_hash = FieldElement::from(transaction_hash);
+        self.check_queued_transaction_rejection(&transaction_hash)?;
+        let starknet_tx_receipt =
+            self.query_with_fallback(|provider| provider.get_transaction_receipt(transaction_hash)).await?;
+
+        match starknet_tx_receipt {
+            MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(receipt)) => {
+                let starknet_tx: StarknetTransaction = self
+                    .query_with_fallback(|provider| provider.get_transaction_by_hash(receipt.transaction_hash))
+                    .await?
+                    .into();
+                let position = self.transaction_position(receipt.block_hash, receipt.transaction_hash).await?;
+                self.invoke_receipt_to_eth_receipt(starknet_tx, receipt, position).await
+            }
+            // L1Handler, Declare, Deploy and DeployAccount transactions unsupported for now in
+            // Kakarot
+            MaybePendingTransactionReceipt::Receipt(_) => Ok(None),
+            MaybePendingTransactionReceipt::PendingReceipt(_) => Ok(None),
+        }
+    }
+
+    /// Returns the receipts of the EVM transactions of a block.
+    ///
+    /// The receipts of the transactions of the block are fetched concurrently, at most
+    /// `MAX_CONCURRENT_REQUESTS` at once, saving the round trips of one
+    /// `eth_getTransactionReceipt` per transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `starknet_block_id(StarknetBlockId)` - The block id.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<TransactionReceipt>)` if the operation was successful, empty for the pending block.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn block_receipts(&self, starknet_block_id: StarknetBlockId) -> Result<Vec<TransactionReceipt>, EthApiError> {
+        let (block_hash, transactions) =
+            match self.query_with_fallback(|provider| provider.get_block_with_txs(starknet_block_id)).await? {
+                MaybePendingBlockWithTxs::Block(block) => (block.block_hash, block.transactions),
+                // Pending transactions have no receipt
+                MaybePendingBlockWithTxs::PendingBlock(_) => return Ok(vec![]),
+            };
+        let positions = self.transaction_positions(block_hash).await?;
+        let positions = positions.as_slice();
+
+        let handles = transactions.into_iter().map(|transaction| async move {
+            let starknet_tx = StarknetTransaction::from(transaction);
+            // Only invoke transactions, which have a hash here, can be Kakarot transactions
+            let transaction_hash: FieldElement = match starknet_tx.transaction_hash() {
+                Ok(hash) => hash.into(),
+                Err(_) => return Ok::<_, EthApiError>(None),
+            };
+            // Invoke transactions which are not Kakarot transactions have no EVM receipt
+            if !starknet_tx.is_kakarot_tx(self, starknet_block_id).await? {
+                return Ok(None);
+            }
+            let position = find_transaction_position(positions, block_hash, transaction_hash)?;
+            match self.query_with_fallback(|provider| provider.get_transaction_receipt(transaction_hash)).await? {
+                MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(receipt)) => {
+                    self.invoke_receipt_to_eth_receipt(starknet_tx, receipt, position).await
+                }
+                _ => Ok(None),
+            }
+        });
+        let receipts = stream::iter(handles)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(receipts.into_iter().flatten().collect())
+    }
+
+    /// Returns the class hash of a Starknet contract at a block.
+    async fn class_hash_at(
+        &self,
+        starknet_block_id: StarknetBlockId,
+        starknet_address: FieldElement,
+    ) -> Result<FieldElement, EthApiError> {
+        self.query_with_fallback(|provider| provider.get_class_hash_at(starknet_block_id, starknet_address)).await
+    }
+
+    async fn get_evm_address(
+        &self,
+        starknet_address: &FieldElement,
+        starknet_block_id: &StarknetBlockId,
+    ) -> Result<Address, EthApiError> {
+        let request = FunctionCall {
+            contract_address: *starknet_address,
+            entry_point_selector: GET_EVM_ADDRESS,
+            calldata: vec![],
+        };
+
+        let evm_address_felt = self.query_with_fallback(|provider| provider.call(&request, starknet_block_id)).await?;
+        let evm_address = evm_address_felt
+            .first()
+            .ok_or_else(|| {
+                EthApiError::OtherError(anyhow::anyhow!(
+                    "Kakarot Core: Failed to get EVM address from smart contract on Kakarot"
+                ))
+            })?
+            .to_bytes_be();
+
+        // Workaround as .get(12..32) does not dynamically size the slice
+        let slice: &[u8] = evm_address.get(12..32).ok_or_else(|| {
+            EthApiError::OtherError(anyhow::anyhow!(
+                "Kakarot Core: Failed to cast EVM address from 32 bytes to 20 bytes EVM format"
+            ))
+        })?;
+        let mut tmp_slice = [0u8; 20];
+        tmp_slice.copy_from_slice(slice);
+        let evm_address_sliced = &tmp_slice;
+
+        Ok(Address::from(evm_address_sliced))
+    }
+
+    /// Get the nonce for a given ethereum address
+    /// Convert Ethereum address to Starknet address
+    /// Return the nonce, by calling `get_nonce` for the addresss via starknet rpc
+    /// * `ethereum_address` - The EVM address to get the nonce of
+    /// * `block_id` - The block to get the nonce at
+    ///
+    /// ### Returns
+    /// * `Result<U256, EthApiError>` - The nonce of the EVM address
+    async fn nonce(&self, ethereum_address: Address, block_id: StarknetBlockId) -> Result<U256, EthApiError> {
+        let starknet_address = self.compute_starknet_address(ethereum_address, &block_id).await?;
+
+        let nonce: Felt252Wrapper =
+            self.query_with_fallback(|provider| provider.get_nonce(block_id, starknet_address)).await?.into();
+
+        Ok(nonce.into())
+    }
+
+    /// Get the balance in Starknet's native token of a specific EVM address.
+    /// Reproduces the principle of Kakarot native coin by using Starknet's native ERC20 token
+    /// (gas-utility token), or the configured EVM wrapped native token if any.
+    /// ### Arguments
+    /// * `ethereum_address` - The EVM address to get the balance of
+    /// * `block_id` - The block to get the balance at
+    ///
+    /// ### Returns
+    /// * `Result<U256, EthApiError>` - The balance of the EVM address in Starknet's native token
+    async fn balance(&self, ethereum_address: Address, block_id: StarknetBlockId) -> Result<U256, EthApiError> {
+        if let Some(wrapped_native_token) = self.wrapped_native_token {
+            return self.erc20_balance(wrapped_native_token, ethereum_address, block_id).await;
+        }
+
+        let starknet_address = self.compute_starknet_address(ethereum_address, &block_id).await?;
+        self.native_token_balance(starknet_address, block_id).await
+    }
+
+    /// Returns token balances for a specific address given a list of contracts.
+    ///
+    /// # Arguments
+    ///
+    /// * `address(Address)` - specific address
+    /// * `contract_addresses(Vec<Address>)` - List of contract addresses
+    /// * `block_id(StarknetBlockId)` - The block to get the balances at
+    ///
+    /// # Returns
+    ///
+    ///  * `token_balances(TokenBalances)` - Token balances
+    ///
+    /// `Ok(<TokenBalances>)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn token_balances(
+        &self,
+        address: Address,
+        contract_addresses: Vec<Address>,
+        block_id: StarknetBlockId,
+    ) -> Result<TokenBalances, EthApiError> {
+        let handles = contract_addresses.into_iter().map(|token_address| async move {
+            match self.erc20_balance(token_address, address, block_id).await {
+                Ok(balance) => {
+                    TokenBalance { contract_address: token_address, token_balance: Some(balance), error: None }
+                }
+                Err(e) => TokenBalance {
+                    contract_address: token_address,
+                    token_balance: None,
+                    error: Some(format!("kakarot_getTokenBalances Error: {e}")),
+                },
+            }
+        });
+        let token_balances = join_all(handles).await;
+
+        Ok(TokenBalances { address, token_balances })
+    }
+
+    async fn filter_starknet_into_eth_txs(
+        &self,
+        initial_transactions: StarknetTransactions,
+        block_hash: Option<H256>,
+        block_number: Option<U256>,
+    ) -> Result<BlockTransactions, EthApiError> {
+        let handles = Into::<Vec<TransactionType>>::into(initial_transactions).into_iter().map(|tx| async move {
+            let tx = Into::<StarknetTransaction>::into(tx);
+            tx.to_eth_transaction(self, block_hash, block_number, None).await
+        });
+        let transactions_vec = join_all(handles).await.into_iter().filter_map(|transaction| transaction.ok()).collect();
+        Ok(BlockTransactions::Full(transactions_vec))
+    }
+
+    /// Returns the Kakarot transactions of the Starknet pending block, which stands for the
+    /// transaction pool.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<EtherTransaction>)` if the operation was successful, empty without a pending block.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn pending_transactions(&self) -> Result<Vec<EtherTransaction>, EthApiError> {
+        let transactions =
+            match self.starknet_provider.get_block_with_txs(StarknetBlockId::Tag(BlockTag::Pending)).await? {
+                MaybePendingBlockWithTxs::PendingBlock(block) => block.transactions,
+                // Nodes without a pending block return the latest block
+                MaybePendingBlockWithTxs::Block(_) => return Ok(vec![]),
+            };
+        match self.filter_starknet_into_eth_txs(transactions.into(), None, None).await? {
+            BlockTransactions::Full(transactions) => Ok(transactions),
+            _ => Ok(vec![]),
+        }
+    }
+
+    async fn send_transaction(&self, bytes: Bytes) -> Result<H256, EthApiError> {
+        self.send_raw_transaction(bytes, self.prevalidate_transactions).await
+    }
+
+    /// Sends a raw EVM transaction without simulating it first, even if pre-validation is enabled.
+    async fn send_unchecked_transaction(&self, bytes: Bytes) -> Result<H256, EthApiError> {
+        self.send_raw_transaction(bytes, false).await
+    }
+
+    /// Returns the fixed base_fee_per_gas of Kakarot
+    /// Since Starknet works on a FCFS basis (FIFO queue), it is not possible to tip miners to
+    /// incentivize faster transaction inclusion
+    /// As a result, in Kakarot, gas_price := base_fee_per_gas
+    ///
+    /// Computes the Starknet gas_price using starknet_estimateFee RPC method
+    fn base_fee_per_gas(&self) -> U256 {
+        U256::from(BASE_FEE_PER_GAS)
+    }
+
+    /// Returns the last priority fee per gas suggested by the gas oracle, without refreshing it.
+    fn max_priority_fee_per_gas(&self) -> U128 {
+        self.gas_oracle.suggestion()
+    }
+
+    /// Returns the priority fee per gas suggested by the gas oracle, after sampling the priority
+    /// fees of the blocks produced since the last suggestion, at most the
+    /// `GAS_ORACLE_BLOCKS_PER_SUGGESTION` newest ones.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(U128)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn suggest_max_priority_fee_per_gas(&self) -> Result<U128, EthApiError> {
+        let latest_block = self.block_number().await?.as_u64();
+        let missing_blocks = self.gas_oracle.missing_blocks(latest_block, GAS_ORACLE_BLOCKS_PER_SUGGESTION);
+        if missing_blocks.is_empty() {
+            return Ok(self.gas_oracle.suggestion());
+        }
+
+        let blocks = self.get_eth_blocks_in_range(*missing_blocks.start(), *missing_blocks.end(), true).await?;
+        for block in blocks {
+            let block_number = match block.header.number {
+                Some(block_number) => block_number.to::<u64>(),
+                None => continue,
+            };
+            let base_fee = block.header.base_fee_per_gas.unwrap_or_else(|| self.base_fee_per_gas());
+            let priority_fees = match &block.transactions {
+                BlockTransactions::Full(transactions) => {
+                    transactions.iter().map(|transaction| effective_priority_fee(transaction, base_fee)).collect()
+                }
+                _ => vec![],
+            };
+            self.gas_oracle.record_block(block_number, priority_fees);
+        }
+
+        Ok(self.gas_oracle.suggestion())
+    }
+
+    /// Returns the fee history of the blocks up to `newest_block`, read from the EVM blocks.
+    ///
+    /// At most `MAX_BLOCK_RANGE` blocks are returned. Kakarot has no fee market yet, so the base
+    /// fees are the fixed `BASE_FEE_PER_GAS`. The gas used ratios are the gas used by the
+    /// transactions of the blocks, derived from the fees they paid as for their receipts, over the
+    /// placeholder `GAS_LIMIT` of the blocks. The blocks are only fetched when rewards are
+    /// requested.
+    ///
+    /// # Arguments
+    ///
+    /// * `block_count(U256)` - The number of blocks requested.
+    /// * `newest_block(BlockNumberOrTag)` - The last block of the range.
+    /// * `reward_percentiles(Option<Vec<f64>>)` - The percentiles of the gas of each block at which
+    ///   the priority fees paid are sampled. The EVM gas used is unknown, so transactions are
+    ///   weighted by their Starknet fee, which is proportional to the Starknet gas they used as the
+    ///   transactions of a block pay the same gas price.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(FeeHistory)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn fee_history(
+        &self,
+        block_count: U256,
+        newest_block: BlockNumberOrTag,
+        reward_percentiles: Option<Vec<f64>>,
+    ) -> Result<FeeHistory, EthApiError> {
+        if let Some(percentiles) = &reward_percentiles {
+            let mut previous = 0.0;
+            for percentile in percentiles {
+                if !(previous..=100.0).contains(percentile) {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "invalid reward percentile {percentile}, percentiles must be increasing and between 0 and 100"
+                    )));
+                }
+                previous = *percentile;
+            }
+        }
+
+        let latest_block = self.block_number().await?.as_u64();
+        let newest_block = match newest_block {
+            BlockNumberOrTag::Number(number) => number.min(latest_block),
+            BlockNumberOrTag::Earliest => 0,
+            _ => latest_block,
+        };
+
+        let block_count = u64::try_from(block_count).unwrap_or(u64::MAX).min(MAX_BLOCK_RANGE).min(newest_block + 1);
+        if block_count == 0 {
+            return Ok(FeeHistory {
+                base_fee_per_gas: vec![],
+                gas_used_ratio: vec![],
+                oldest_block: U256::from(newest_block),
+                reward: reward_percentiles.map(|_| vec![]),
+            });
+        }
+        let oldest_block = newest_block + 1 - block_count;
+
+        // The base fee of Kakarot is fixed, so the base fee of the next block is already known
+        let base_fee_per_gas = vec![self.base_fee_per_gas(); block_count as usize + 1];
+
+        // The gas used by the blocks derives from the fees paid by their transactions, read from
+        // the transfers of the range at once
+        let transfer_filter =
+            native_transfer_filter(StarknetBlockId::Number(oldest_block), StarknetBlockId::Number(newest_block));
+        let mut gas_used = vec![U256::ZERO; block_count as usize];
+        for (block_number, _, fee) in fees_of_transfers(self.get_events_with_continuation(transfer_filter).await?) {
+            if let Some(block_gas_used) =
+                block_number.checked_sub(oldest_block).and_then(|offset| gas_used.get_mut(offset as usize))
+            {
+                *block_gas_used += self.gas_used_by_fee(fee);
+            }
+        }
+        let gas_used_ratio = gas_used.into_iter().map(|gas_used| gas_used_ratio(gas_used, *GAS_LIMIT)).collect();
+
+        // The blocks are only needed to sample the rewards
+        let blocks = match &reward_percentiles {
+            Some(_) => self.get_eth_blocks_in_range(oldest_block, newest_block, true).await?,
+            None => vec![],
+        };
+        let reward = match reward_percentiles {
+            Some(percentiles) => {
+                let mut reward = Vec::with_capacity(blocks.len());
+                for (block, base_fee) in blocks.iter().zip(&base_fee_per_gas) {
+                    let transactions = match &block.transactions {
+                        BlockTransactions::Full(transactions) => transactions.as_slice(),
+                        _ => &[],
+                    };
+                    let handles = transactions.iter().map(|transaction| async move {
+                        let fee = self.actual_fee(transaction.hash).await?;
+                        Ok::<_, EthApiError>((effective_priority_fee(transaction, *base_fee), fee))
+                    });
+                    let rewards = stream::iter(handles)
+                        .buffered(MAX_CONCURRENT_REQUESTS)
+                        .collect::<Vec<_>>()
+                        .await
+                        .into_iter()
+                        .collect::<Result<Vec<_>, _>>()?;
+                    reward.push(rewards_at_percentiles(rewards, &percentiles));
+                }
+                Some(reward)
+            }
+            None => None,
+        };
+
+        Ok(FeeHistory { base_fee_per_gas, gas_used_ratio, oldest_block: U256::from(oldest_block), reward })
+    }
+
+    /// Returns the logs matching the filter.
+    ///
+    /// Kakarot events are fetched with `starknet_getEvents`, converted to EVM logs and then
+    /// matched against the address and topics of the filter. The block range of the filter may
+    /// not exceed `MAX_LOGS_BLOCK_RANGE` blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter(Filter)` - The log filter.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<Log>)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn get_logs(&self, filter: Filter) -> Result<Vec<Log>, EthApiError> {
+        let events = match filter.block_option {
+            FilterBlockOption::AtBlockHash(hash) => {
+                let block_id = ethers_block_id_to_starknet_block_id(BlockId::Hash(hash.into()))?;
+                let event_filter = EventFilter {
+                    from_block: Some(block_id),
+                    to_block: Some(block_id),
+                    address: Some(self.kakarot_address_at(&block_id).await?),
+                    keys: None,
+                };
+                self.get_events_with_continuation(event_filter).await?
+            }
+            FilterBlockOption::Range { from_block, to_block } => {
+                let latest_block = self.starknet_provider.block_number().await?;
+                let from_block = self.log_range_bound(from_block, latest_block).await?;
+                let to_block = self.log_range_bound(to_block, latest_block).await?;
+                if from_block > to_block {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "invalid block range, from block {from_block} is after to block {to_block}"
+                    )));
+                }
+                if to_block - from_block >= MAX_LOGS_BLOCK_RANGE {
+                    return Err(EthApiError::InvalidParams(format!(
+                        "block range exceeds the maximum of {MAX_LOGS_BLOCK_RANGE} blocks"
+                    )));
+                }
+                self.get_kakarot_events(from_block, to_block, None).await?
+            }
+        };
+
+        // The positions of the transactions are fetched once per block, the blocks concurrently
+        let blocks: HashMap<u64, FieldElement> =
+            events.iter().map(|event| (event.block_number, event.block_hash)).collect();
+        let handles = blocks.into_iter().map(|(block_number, block_hash)| async move {
+            Ok::<_, EthApiError>((block_number, self.transaction_positions(block_hash).await?))
+        });
+        let positions = stream::iter(handles)
+            .buffered(MAX_CONCURRENT_REQUESTS)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        let params = FilteredParams::new(Some(filter));
+        let mut logs = Vec::new();
+        // The transaction of the previous event, and the index of its next event. Events are
+        // served in order, and the receipt logs are the events emitted by Kakarot too, so the
+        // indexes match the receipt logs.
+        let mut current_transaction: Option<(FieldElement, u64)> = None;
+        for emitted_event in events {
+            let EmittedEvent { from_address, keys, data, block_hash, block_number, transaction_hash } = emitted_event;
+            let position = find_transaction_position(&positions[&block_number], block_hash, transaction_hash)?;
+            let index_in_transaction = match current_transaction {
+                Some((hash, index)) if hash == transaction_hash => index,
+                _ => 0,
+            };
+            current_transaction = Some((transaction_hash, index_in_transaction + 1));
+
+            let block_hash: Felt252Wrapper = block_hash.into();
+            let transaction_hash: Felt252Wrapper = transaction_hash.into();
+            let log = StarknetEvent::new(Event { from_address, keys, data })
+                .to_eth_log(
+                    self,
+                    Some(block_hash.into()),
+                    Some(U256::from(block_number)),
+                    Some(transaction_hash.into()),
+                    Some(U256::from(position.logs_before + index_in_transaction)),
+                    Some(U256::from(position.index)),
+                )
+                .await?;
+            if params.filter_address(&log) && params.filter_topics(&log) {
+                logs.push(log);
+            }
+        }
+
+        Ok(logs)
+    }
+
+    /// Resolves a bound of the block range of a log filter to a block number.
+    ///
+    /// A missing bound is the latest block. The events of the pending block are not served, so a
+    /// pending bound is the latest block too.
+    async fn log_range_bound(&self, bound: Option<BlockNumberOrTag>, latest_block: u64) -> Result<u64, EthApiError> {
+        match bound {
+            Some(BlockNumberOrTag::Number(block_number)) => Ok(block_number),
+            Some(BlockNumberOrTag::Earliest) => Ok(0),
+            Some(tag @ (BlockNumberOrTag::Safe | BlockNumberOrTag::Finalized)) => {
+                match self.resolve_block_id(BlockId::Number(tag)).await? {
+                    StarknetBlockId::Number(block_number) => Ok(block_number),
+                    _ => Ok(latest_block),
+                }
+            }
+            None | Some(BlockNumberOrTag::Latest | BlockNumberOrTag::Pending) => Ok(latest_block),
+        }
+    }
+
+    /// Restricts a log filter to the blocks of `range` it covers, the blocks a poll of the filter
+    /// reports on.
+    ///
+    /// Unlike the bounds of `eth_getLogs`, a missing, latest or pending bound leaves the filter
+    /// open, as it keeps reporting the new blocks.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Some(Filter))` - The filter bounded to the blocks of `range` it covers.
+    /// `Ok(None)` if the filter covers none of them, e.g. once past its `toBlock`.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn log_filter_in_range(
+        &self,
+        filter: Filter,
+        range: RangeInclusive<u64>,
+    ) -> Result<Option<Filter>, EthApiError> {
+        let (from_block, to_block) = match filter.block_option {
+            FilterBlockOption::AtBlockHash(hash) => {
+                let block_id = ethers_block_id_to_starknet_block_id(BlockId::Hash(hash.into()))?;
+                let block_number =
+                    match self.query_with_fallback(|provider| provider.get_block_with_tx_hashes(block_id)).await? {
+                        MaybePendingBlockWithTxHashes::Block(block) => block.block_number,
+                        MaybePendingBlockWithTxHashes::PendingBlock(_) => return Ok(None),
+                    };
+                return Ok(range.contains(&block_number).then_some(filter));
+            }
+            FilterBlockOption::Range { from_block, to_block } => (from_block, to_block),
+        };
 
-        Ok(H256::from(transaction_result.transaction_hash.to_bytes_be()))
+        let from_block = self.log_range_bound(from_block, *range.start()).await?.max(*range.start());
+        let to_block = self.log_range_bound(to_block, *range.end()).await?.min(*range.end());
+        Ok((from_block <= to_block).then(|| filter.from_block(from_block).to_block(to_block)))
     }
 
-    /// Returns the receipt of a transaction by transaction hash.
+    /// Returns the bloom filter of the logs emitted in a block.
     ///
     /// # Arguments
     ///
-    /// * `hash(H256)` - The transaction hash.
+    /// * `starknet_block_id(StarknetBlockId)` - The block id.
     ///
     /// # Returns
     ///
-    ///  * `transaction_receipt(TransactionReceipt)` - The transaction receipt.
-    ///
-    /// `Ok(Option<TransactionReceipt>)` if the operation was successful.
+    /// `Ok(Bloom)` if the operation was successful.
     /// `Err(EthApiError)` if the operation failed.
-    async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>, EthApiError> {
-        // TODO: Error when trying to transform 32 bytes hash to FieldElement
-        let transaction_hash: Felt252Wrapper = hash.try_into()?;
-        let starknet_tx_receipt =
-            self.starknet_provider.get_transaction_receipt::<FieldElement>(transaction_hash.into()).await?;
+    async fn block_logs_bloom(&self, starknet_block_id: StarknetBlockId) -> Result<Bloom, EthApiError> {
+        let event_filter = EventFilter {
+            from_block: Some(starknet_block_id),
+            to_block: Some(starknet_block_id),
+            address: Some(self.kakarot_address_at(&starknet_block_id).await?),
+            keys: None,
+        };
+        let events = self.get_events_with_continuation(event_filter).await?;
+
+        // The bloom covers the same logs as `eth_getLogs`, so a block whose events fail to
+        // convert has no bloom either
+        let mut logs = Vec::with_capacity(events.len());
+        for emitted_event in events {
+            let EmittedEvent { from_address, keys, data, .. } = emitted_event;
+            let event = StarknetEvent::new(Event { from_address, keys, data });
+            logs.push(event.to_eth_log(self, None, None, None, None, None).await?);
+        }
 
-        let starknet_block_id = StarknetBlockId::Tag(BlockTag::Latest);
+        Ok(logs_bloom(&logs))
+    }
 
-        let res_receipt = match starknet_tx_receipt {
-            MaybePendingTransactionReceipt::Receipt(receipt) => match receipt {
-                StarknetTransactionReceipt::Invoke(InvokeTransactionReceipt {
-                    transaction_hash,
-                    status,
-                    block_hash,
-                    block_number,
-                    events,
-                    ..
-                }) => {
-                    let starknet_tx: StarknetTransaction =
-                        self.starknet_provider.get_transaction_by_hash(transaction_hash).await?.into();
-
-                    let starknet_sender_address: FieldElement = starknet_tx.sender_address()?.into();
-                    let contract_address =
-                        Some(self.safe_get_evm_address(&starknet_sender_address, &starknet_block_id).await);
-
-                    let transaction_hash: Felt252Wrapper = transaction_hash.into();
-                    let transaction_hash: Option<H256> = Some(transaction_hash.into());
-
-                    let block_hash: Felt252Wrapper = block_hash.into();
-                    let block_hash: Option<H256> = Some(block_hash.into());
-
-                    let block_number: Felt252Wrapper = block_number.into();
-                    let block_number: Option<U256> = Some(block_number.into());
-
-                    let eth_tx = starknet_tx.to_eth_transaction(self, None, None, None).await?;
-                    let from = eth_tx.from;
-                    let to = eth_tx.to;
-
-                    let status_code = match status {
-                        StarknetTransactionStatus::Rejected | StarknetTransactionStatus::Pending => Some(U64::from(0)),
-                        StarknetTransactionStatus::AcceptedOnL1 | StarknetTransactionStatus::AcceptedOnL2 => {
-                            Some(U64::from(1))
-                        }
-                    };
+    /// Installs a filter reporting the logs matching `filter` in the blocks produced from now on.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(U64)` - The id of the filter, if the operation was successful.
+    /// `Err(EthApiError)` if too many filters are installed or the operation failed.
+    async fn new_filter(&self, filter: Filter) -> Result<U64, EthApiError> {
+        let current_block = self.starknet_provider.block_number().await?;
+        Ok(U64::from(self.filters.install(FilterKind::Logs(filter), current_block)?))
+    }
 
-                    // Handle events -- Will error if the event is not a Kakarot event
-                    let mut logs = Vec::new();
-
-                    // Cannot use `map` because of the `await` call.
-                    for event in events {
-                        let contract_address = self.safe_get_evm_address(&event.from_address, &starknet_block_id).await;
-
-                        // event "keys" in cairo are event "topics" in solidity
-                        // they're returned as list where consecutive values are
-                        // low, high, low, high, etc. of the Uint256 Cairo representation
-                        // of the bytes32 topics. This recomputes the original topic
-                        let topics = (0..event.keys.len())
-                            .step_by(2)
-                            .map(|i| {
-                                let next_key = *event.keys.get(i + 1).unwrap_or(&FieldElement::ZERO);
-
-                                // Can unwrap here as we know 2^128 is a valid FieldElement
-                                let two_pow_16: FieldElement =
-                                    FieldElement::from_hex_be("0x100000000000000000000000000000000").unwrap();
-
-                                // TODO: May wrap around prime field - Investigate edge cases
-                                let felt_shifted_next_key = next_key * two_pow_16;
-                                event.keys[i] + felt_shifted_next_key
-                            })
-                            .map(|topic| H256::from(&topic.to_bytes_be()))
-                            .collect::<Vec<_>>();
-
-                        let data = vec_felt_to_bytes(event.data);
-
-                        let log = Log {
-                            // TODO: fetch correct address from Kakarot.
-                            // Contract Address is the account contract's address (EOA or KakarotAA)
-                            address: H160::from_slice(&contract_address.0),
-                            topics,
-                            data: RpcBytes::from(data.0),
-                            block_hash: None,
-                            block_number: None,
-                            transaction_hash: None,
-                            transaction_index: None,
-                            log_index: None,
-                            removed: false,
-                        };
-
-                        logs.push(log);
-                    }
+    /// Installs a filter reporting the hashes of the blocks produced from now on.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(U64)` - The id of the filter, if the operation was successful.
+    /// `Err(EthApiError)` if too many filters are installed or the operation failed.
+    async fn new_block_filter(&self) -> Result<U64, EthApiError> {
+        let current_block = self.starknet_provider.block_number().await?;
+        Ok(U64::from(self.filters.install(FilterKind::Blocks, current_block)?))
+    }
 
-                    TransactionReceipt {
-                        transaction_hash,
-                        transaction_index: None,
-                        block_hash,
-                        block_number,
-                        from,
-                        to,
-                        cumulative_gas_used: U256::from(1_000_000), // TODO: Fetch real data
-                        gas_used: Some(U256::from(500_000)),
-                        contract_address,
-                        logs,
-                        state_root: None,             // TODO: Fetch real data
-                        logs_bloom: Bloom::default(), // TODO: Fetch real data
-                        status_code,
-                        effective_gas_price: U128::from(1_000_000), // TODO: Fetch real data
-                        transaction_type: U8::from(0),              // TODO: Fetch real data
-                    }
-                }
-                // L1Handler, Declare, Deploy and DeployAccount transactions unsupported for now in
-                // Kakarot
-                _ => return Ok(None),
+    /// Returns the changes of an installed filter since it was last polled.
+    ///
+    /// A poll reports the changes of at most `MAX_BLOCK_RANGE` blocks for a block filter, and
+    /// `MAX_LOGS_BLOCK_RANGE` blocks for a log filter, from the oldest block not yet reported on,
+    /// the next polls reporting the following blocks. A log filter only reports the logs of its
+    /// own blocks, so it has no changes anymore once past its `toBlock`.
+    ///
+    /// # Arguments
+    ///
+    /// * `id(U64)` - The id of the filter.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(FilterChanges)` - The new logs or block hashes, if the operation was successful.
+    /// `Err(EthApiError)` if the filter is not installed or the operation failed.
+    async fn filter_changes(&self, id: U64) -> Result<FilterChanges, EthApiError> {
+        let current_block = self.starknet_provider.block_number().await?;
+        let (kind, range) = self.filters.poll(id.as_u64(), current_block).ok_or(EthApiError::FilterNotFound)?;
+
+        let changes = match kind {
+            FilterKind::Logs(_) if range.is_empty() => return Ok(FilterChanges::Logs(vec![])),
+            FilterKind::Logs(filter) => match self.log_filter_in_range(filter, range.clone()).await? {
+                Some(filter) => FilterChanges::Logs(self.get_logs(filter).await?),
+                None => FilterChanges::Logs(vec![]),
             },
-            MaybePendingTransactionReceipt::PendingReceipt(_) => {
-                return Ok(None);
+            FilterKind::Blocks if range.is_empty() => return Ok(FilterChanges::Hashes(vec![])),
+            FilterKind::Blocks => {
+                let blocks = self.get_eth_blocks_in_range(*range.start(), *range.end(), false).await?;
+                FilterChanges::Hashes(blocks.into_iter().filter_map(|block| block.header.hash).collect())
             }
         };
-
-        Ok(Some(res_receipt))
+        // The changes were fetched, so the next poll reports the following blocks
+        self.filters.mark_reported(id.as_u64(), *range.end());
+        Ok(changes)
     }
 
-    async fn get_evm_address(
-        &self,
-        starknet_address: &FieldElement,
-        starknet_block_id: &StarknetBlockId,
-    ) -> Result<Address, EthApiError> {
-        let request = FunctionCall {
-            contract_address: *starknet_address,
-            entry_point_selector: GET_EVM_ADDRESS,
-            calldata: vec![],
-        };
-
-        let evm_address_felt = self.starknet_provider.call(request, starknet_block_id).await?;
-        let evm_address = evm_address_felt
-            .first()
-            .ok_or_else(|| {
-                EthApiError::OtherError(anyhow::anyhow!(
-                    "Kakarot Core: Failed to get EVM address from smart contract on Kakarot"
-                ))
-            })?
-            .to_bytes_be();
-
-        // Workaround as .get(12..32) does not dynamically size the slice
-        let slice: &[u8] = evm_address.get(12..32).ok_or_else(|| {
-            EthApiError::OtherError(anyhow::anyhow!(
-                "Kakarot Core: Failed to cast EVM address from 32 bytes to 20 bytes EVM format"
-            ))
-        })?;
-        let mut tmp_slice = [0u8; 20];
-        tmp_slice.copy_from_slice(slice);
-        let evm_address_sliced = &tmp_slice;
+    /// Returns all the logs matching an installed log filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `id(U64)` - The id of the filter.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<Log>)` if the operation was successful.
+    /// `Err(EthApiError)` if no log filter with this id is installed or the operation failed.
+    async fn filter_logs(&self, id: U64) -> Result<Vec<Log>, EthApiError> {
+        match self.filters.get(id.as_u64()) {
+            Some(FilterKind::Logs(filter)) => self.get_logs(filter).await,
+            _ => Err(EthApiError::FilterNotFound),
+        }
+    }
 
-        Ok(Address::from(evm_address_sliced))
+    /// Uninstalls a filter.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(bool)` - `false` if no filter with this id was installed.
+    async fn uninstall_filter(&self, id: U64) -> Result<bool, EthApiError> {
+        Ok(self.filters.uninstall(id.as_u64()))
     }
 
-    /// Get the nonce for a given ethereum address
-    /// Convert Ethereum address to Starknet address
-    /// Return the nonce, by calling `get_nonce` for the addresss via starknet rpc
-    /// * `ethereum_address` - The EVM address to get the nonce of
-    /// * `block_id` - The block to get the nonce at
+    /// Get the Kakarot events emitted between `from_block` and `to_block` (inclusive), as raw
+    /// Starknet events along with their conversion to EVM logs.
     ///
-    /// ### Returns
-    /// * `Result<U256, EthApiError>` - The nonce of the EVM address
-    async fn nonce(&self, ethereum_address: Address, block_id: StarknetBlockId) -> Result<U256, EthApiError> {
-        let starknet_address = self.compute_starknet_address(ethereum_address, &block_id).await?;
+    /// # Arguments
+    ///
+    /// * `from_block(u64)` - The first block of the range.
+    /// * `to_block(u64)` - The last block of the range.
+    /// * `keys(Option<Vec<Vec<FieldElement>>>)` - The Starknet event keys to filter on, if any.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Vec<RawKakarotEvent>)` if the operation was successful. Events which fail to be
+    /// converted are returned with the conversion error instead of a log.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn raw_events(
+        &self,
+        from_block: u64,
+        to_block: u64,
+        keys: Option<Vec<Vec<FieldElement>>>,
+    ) -> Result<Vec<RawKakarotEvent>, EthApiError> {
+        let events = self.get_kakarot_events(from_block, to_block, keys).await?;
+
+        let handles = events.into_iter().map(|emitted_event| async move {
+            let block_hash: Felt252Wrapper = emitted_event.block_hash.into();
+            let transaction_hash: Felt252Wrapper = emitted_event.transaction_hash.into();
+            let log = StarknetEvent::new(Event {
+                from_address: emitted_event.from_address,
+                keys: emitted_event.keys.clone(),
+                data: emitted_event.data.clone(),
+            })
+            .to_eth_log(
+                self,
+                Some(block_hash.into()),
+                Some(U256::from(emitted_event.block_number)),
+                Some(transaction_hash.into()),
+                None,
+                None,
+            )
+            .await;
 
-        let nonce: Felt252Wrapper = self.starknet_provider.get_nonce(block_id, starknet_address).await?.into();
+            match log {
+                Ok(log) => RawKakarotEvent { event: emitted_event, log: Some(log), error: None },
+                Err(err) => RawKakarotEvent { event: emitted_event, log: None, error: Some(err.to_string()) },
+            }
+        });
 
-        Ok(nonce.into())
+        Ok(join_all(handles).await)
     }
 
-    /// Get the balance in Starknet's native token of a specific EVM address.
-    /// Reproduces the principle of Kakarot native coin by using Starknet's native ERC20 token
-    /// (gas-utility token) ### Arguments
-    /// * `ethereum_address` - The EVM address to get the balance of
-    /// * `block_id` - The block to get the balance at
+    /// Returns the L1 settlement of a transaction.
     ///
-    /// ### Returns
-    /// * `Result<U256, EthApiError>` - The balance of the EVM address in Starknet's native token
-    async fn balance(&self, ethereum_address: Address, block_id: StarknetBlockId) -> Result<U256, EthApiError> {
-        let starknet_address = self.compute_starknet_address(ethereum_address, &block_id).await?;
+    /// # Arguments
+    ///
+    /// * `hash(H256)` - The transaction hash.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Option<L1Confirmation>)` if the operation was successful, `None` for transactions
+    /// which are not Kakarot invoke transactions.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn l1_confirmation(&self, hash: H256) -> Result<Option<L1Confirmation>, EthApiError> {
+        let transaction_hash: Felt252Wrapper = hash.try_into()?;
+        let transaction_hash = FieldElement::from(transaction_hash);
+        let receipt = self.query_with_fallback(|provider| provider.get_transaction_receipt(transaction_hash)).await?;
 
-        let request = FunctionCall {
-            // This FieldElement::from_hex_be cannot fail as the value is a constant
-            contract_address: FieldElement::from_hex_be(STARKNET_NATIVE_TOKEN).unwrap(),
-            entry_point_selector: BALANCE_OF,
-            calldata: vec![starknet_address],
+        let (status, block_number) = match receipt {
+            MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(receipt)) => {
+                (L1SettlementStatus::from(receipt.status), Some(receipt.block_number))
+            }
+            MaybePendingTransactionReceipt::PendingReceipt(_) => (L1SettlementStatus::Pending, None),
+            _ => return Ok(None),
         };
+        let last_l1_accepted_block = self.last_l1_accepted_block().await?;
 
-        let balance_felt = self.starknet_provider.call(request, block_id).await?;
-
-        let balance = balance_felt
-            .first()
-            .ok_or_else(|| {
-                EthApiError::OtherError(anyhow::anyhow!("Kakarot Core: Failed to get native token balance"))
-            })?
-            .to_bytes_be();
-
-        let balance = U256::from_be_bytes(balance);
-
-        Ok(balance)
+        Ok(Some(L1Confirmation { status, block_number, last_l1_accepted_block }))
     }
 
-    /// Returns token balances for a specific address given a list of contracts.
+    /// Returns the native token balance changes of the accounts touched by a transaction, in the
+    /// order they are first touched, `None` while it is pending.
+    ///
+    /// The changes are decoded from the native token `Transfer` events of the transaction, which
+    /// cover the value sent by its internal calls and the fee paid. Only the Kakarot accounts are
+    /// reported: the other parties, such as the sequencer collecting the fee, have no EVM address.
     ///
     /// # Arguments
     ///
-    /// * `address(Address)` - specific address
-    /// * `contract_addresses(Vec<Address>)` - List of contract addresses
+    /// * `hash(H256)` - The transaction hash.
     ///
     /// # Returns
     ///
-    ///  * `token_balances(TokenBalances)` - Token balances
-    ///
-    /// `Ok(<TokenBalances>)` if the operation was successful.
+    /// `Ok(Option<Vec<BalanceChange>>)` if the operation was successful.
     /// `Err(EthApiError)` if the operation failed.
-    async fn token_balances(
-        &self,
-        address: Address,
-        contract_addresses: Vec<Address>,
-    ) -> Result<TokenBalances, EthApiError> {
-        let entrypoint: Felt252Wrapper = keccak256("balanceOf(address)").try_into()?;
-        let entrypoint: FieldElement = entrypoint.into();
-        let felt_address = FieldElement::from_str(&address.to_string()).map_err(|e| {
-            EthApiError::OtherError(anyhow::anyhow!("Failed to convert address to FieldElement: {}", e))
-        })?;
-        let handles = contract_addresses.into_iter().map(|token_address| {
-            let calldata = vec![entrypoint, felt_address];
-
-            self.call_view(
-                token_address,
-                Bytes::from(vec_felt_to_bytes(calldata).0),
-                StarknetBlockId::Tag(BlockTag::Latest),
-            )
-        });
-        let token_balances = join_all(handles)
-            .await
-            .into_iter()
-            .map(|token_address| match token_address {
-                Ok(call) => {
-                    let hex_balance = U256::from_str_radix(&call.to_string(), 16)
-                        .map_err(|e| {
-                            EthApiError::OtherError(anyhow::anyhow!("Failed to convert token balance to U256: {}", e))
-                        })
-                        .unwrap();
-                    TokenBalance { contract_address: address, token_balance: Some(hex_balance), error: None }
-                }
-                Err(e) => TokenBalance {
-                    contract_address: address,
-                    token_balance: None,
-                    error: Some(format!("kakarot_getTokenBalances Error: {e}")),
+    async fn balance_changes(&self, hash: H256) -> Result<Option<Vec<BalanceChange>>, EthApiError> {
+        let transaction_hash: Felt252Wrapper = hash.try_into()?;
+        let transaction_hash = FieldElement::from(transaction_hash);
+        let receipt =
+            match self.query_with_fallback(|provider| provider.get_transaction_receipt(transaction_hash)).await {
+                Ok(MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(receipt))) => receipt,
+                Ok(_) => return Ok(None),
+                Err(err) => match EthApiError::from(err) {
+                    err if is_transaction_not_found_error(&err) => return Ok(None),
+                    err => return Err(err),
                 },
-            })
-            .collect();
-
-        Ok(TokenBalances { address, token_balances })
-    }
+            };
+
+        let starknet_block_id = StarknetBlockId::Hash(receipt.block_hash);
+        let mut changes: Vec<BalanceChange> = Vec::new();
+        for (from, to, value) in receipt.events.iter().filter_map(decode_native_transfer) {
+            // Only the Kakarot accounts have an EVM address, the other parties are not reported
+            let from = self.kakarot_account_evm_address(&from, &starknet_block_id).await?;
+            let to = self.kakarot_account_evm_address(&to, &starknet_block_id).await?;
+            let parties = [(from, U256::ZERO, value), (to, value, U256::ZERO)];
+            for (address, received, sent) in
+                parties.into_iter().filter_map(|(address, received, sent)| Some((address?, received, sent)))
+            {
+                match changes.iter_mut().find(|change| change.address == address) {
+                    Some(change) => {
+                        change.received = change.received.saturating_add(received);
+                        change.sent = change.sent.saturating_add(sent);
+                    }
+                    None => changes.push(BalanceChange { address, received, sent }),
+                }
+            }
+        }
 
-    async fn filter_starknet_into_eth_txs(
-        &self,
-        initial_transactions: StarknetTransactions,
-        block_hash: Option<H256>,
-        block_number: Option<U256>,
-    ) -> Result<BlockTransactions, EthApiError> {
-        let handles = Into::<Vec<TransactionType>>::into(initial_transactions).into_iter().map(|tx| async move {
-            let tx = Into::<StarknetTransaction>::into(tx);
-            tx.to_eth_transaction(self, block_hash, block_number, None).await
-        });
-        let transactions_vec = join_all(handles).await.into_iter().filter_map(|transaction| transaction.ok()).collect();
-        Ok(BlockTransactions::Full(transactions_vec))
+        Ok(Some(changes))
     }
 
-    async fn send_transaction(&self, bytes: Bytes) -> Result<H256, EthApiError> {
-        let mut data = bytes.as_ref();
-
-        if data.is_empty() {
-            return Err(EthApiError::OtherError(anyhow::anyhow!(
-                "Kakarot send_transaction: Transaction bytes are empty"
-            )));
+    /// Returns the Kakarot metadata of a transaction, `None` while it is pending.
+    ///
+    /// # Arguments
+    ///
+    /// * `hash(H256)` - The transaction hash.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(Option<TransactionMetadata>)` if the operation was successful.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn transaction_metadata(&self, hash: H256) -> Result<Option<TransactionMetadata>, EthApiError> {
+        let transaction_hash: Felt252Wrapper = hash.try_into()?;
+        let transaction_hash = FieldElement::from(transaction_hash);
+        let (receipt, starknet_tx) = futures::join!(
+            self.query_with_fallback(|provider| provider.get_transaction_receipt(transaction_hash)),
+            self.query_with_fallback(|provider| provider.get_transaction_by_hash(transaction_hash)),
+        );
+        let receipt = match receipt {
+            Ok(MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(receipt))) => receipt,
+            Ok(_) => return Ok(None),
+            // Transactions not included in a block yet have no metadata
+            Err(err) => match EthApiError::from(err) {
+                err if is_transaction_not_found_error(&err) => return Ok(None),
+                err => return Err(err),
+            },
         };
 
-        let transaction = TransactionSigned::decode(&mut data).map_err(|_| {
-            EthApiError::OtherError(anyhow::anyhow!("Kakarot send_transaction: transaction bytes failed to be decoded"))
-        })?;
-
-        let evm_address = transaction.recover_signer().ok_or_else(|| {
-            EthApiError::OtherError(anyhow::anyhow!("Kakarot send_transaction: signature ecrecover failed"))
-        })?;
-
-        let starknet_block_id = StarknetBlockId::Tag(BlockTag::Latest);
-
-        let starknet_address = self.compute_starknet_address(evm_address, &starknet_block_id).await?;
-
-        let nonce = FieldElement::from(transaction.nonce());
-
-        let calldata = raw_starknet_calldata(self.kakarot_address, bytes);
-
-        // Get estimated_fee from Starknet
-        let max_fee = *MAX_FEE;
-
-        let signature = vec![];
-
-        let request =
-            BroadcastedInvokeTransactionV1 { max_fee, signature, nonce, sender_address: starknet_address, calldata };
-
-        let starknet_transaction_hash = self.submit_starknet_transaction(request).await?;
-
-        Ok(starknet_transaction_hash)
+        let starknet_tx: StarknetTransaction = starknet_tx?.into();
+        let sender_address: FieldElement = starknet_tx.sender_address()?.into();
+        let sender_class_hash = self.class_hash_at(StarknetBlockId::Hash(receipt.block_hash), sender_address).await?;
+
+        Ok(Some(TransactionMetadata {
+            starknet_transaction_hash: transaction_hash,
+            starknet_block_hash: receipt.block_hash,
+            starknet_block_number: receipt.block_number,
+            sender_class_hash,
+            actual_fee: receipt.actual_fee,
+            l1_status: receipt.status.into(),
+        }))
     }
 
-    /// Returns the fixed base_fee_per_gas of Kakarot
-    /// Since Starknet works on a FCFS basis (FIFO queue), it is not possible to tip miners to
-    /// incentivize faster transaction inclusion
-    /// As a result, in Kakarot, gas_price := base_fee_per_gas
+    /// Returns why the EVM execution of a transaction reverted, decoded from the return data of
+    /// the execution result emitted by the Kakarot account of its sender.
     ///
-    /// Computes the Starknet gas_price using starknet_estimateFee RPC method
-    fn base_fee_per_gas(&self) -> U256 {
-        U256::from(BASE_FEE_PER_GAS)
+    /// # Returns
+    ///
+    /// `Ok(None)` if the execution succeeded or has no execution result, e.g. the transaction is
+    /// pending or was rejected, as spec v0.3 receipts of rejected transactions have no events.
+    /// `Err(EthApiError)` if the operation failed.
+    async fn revert_reason(&self, hash: H256) -> Result<Option<String>, EthApiError> {
+        let transaction_hash: Felt252Wrapper = hash.try_into()?;
+        let transaction_hash = FieldElement::from(transaction_hash);
+        let receipt = self.query_with_fallback(|provider| provider.get_transaction_receipt(transaction_hash)).await?;
+        let events = match receipt {
+            MaybePendingTransactionReceipt::Receipt(StarknetTransactionReceipt::Invoke(receipt)) => receipt.events,
+            _ => return Ok(None),
+        };
+        Ok(self
+            .execution_result(&events)
+            .and_then(|(success, return_data)| (!success).then(|| revert_reason(&return_data))))
     }
 
-    fn max_priority_fee_per_gas(&self) -> U128 {
-        MAX_PRIORITY_FEE_PER_GAS
+    async fn estimate_gas(
+        &self,
+        call_request: CallRequest,
+        block_number: Option<BlockId>,
+    ) -> Result<GasEstimate, EthApiError> {
+        let block_id = block_number.unwrap_or_else(|| self.default_block_id());
+        let starknet_block_id = self.resolve_block_id(block_id).await?;
+
+        let mut estimate = GasEstimate { gas: *ESTIMATE_GAS, includes_account_deployment: false };
+        // Wallets show the errors of Ethereum nodes to explain why a transaction cannot be sent
+        if let Some(from) = call_request.from {
+            let starknet_address = self.compute_starknet_address(from, &starknet_block_id).await?;
+            // A single nonce read checks the nonce of the request and whether the account exists
+            let account_nonce = match self
+                .query_with_fallback(|provider| provider.get_nonce(starknet_block_id, starknet_address))
+                .await
+            {
+                Ok(account_nonce) => Felt252Wrapper::from(account_nonce).into(),
+                // Accounts are deployed with their first transaction, which pays for the deployment
+                Err(err) if is_contract_not_found_error(&err) => {
+                    estimate.gas = estimate.gas.saturating_add(*ACCOUNT_DEPLOYMENT_GAS);
+                    estimate.includes_account_deployment = true;
+                    U256::ZERO
+                }
+                Err(err) => return Err(err),
+            };
+            if let Some(nonce) = call_request.nonce {
+                if nonce < account_nonce {
+                    return Err(EthApiError::OtherError(anyhow::anyhow!(
+                        "{NONCE_TOO_LOW}: address {from:?}, tx: {nonce} state: {account_nonce}"
+                    )));
+                }
+            }
+
+            let balance = match self.wrapped_native_token {
+                Some(wrapped_native_token) => self.erc20_balance(wrapped_native_token, from, starknet_block_id).await?,
+                None => self.native_token_balance(starknet_address, starknet_block_id).await?,
+            };
+            let value = call_request.value.unwrap_or_default();
+            if value > balance {
+                return Err(EthApiError::OtherError(anyhow::anyhow!(
+                    "{INSUFFICIENT_FUNDS_FOR_TRANSFER}: address {from:?} have {balance} want {value}"
+                )));
+            }
+            let gas_price = call_request.max_fee_per_gas.or(call_request.gas_price).unwrap_or_default();
+            let cost = call_request.gas.unwrap_or_default().saturating_mul(gas_price).saturating_add(value);
+            if cost > balance {
+                return Err(EthApiError::OtherError(anyhow::anyhow!(
+                    "{INSUFFICIENT_FUNDS}: address {from:?} have {balance} want {cost}"
+                )));
+            }
+        }
+
+        Ok(estimate)
     }
+}
 
-    async fn fee_history(
-        &self,
-        _block_count: U256,
-        _newest_block: BlockNumberOrTag,
-        _reward_percentiles: Option<Vec<f64>>,
-    ) -> Result<FeeHistory, EthApiError> {
-        let block_count_usize = usize::from_str_radix(&_block_count.to_string(), 16).unwrap_or(1);
+/// Returns the position of a transaction among the positions of the transactions of its block.
+fn find_transaction_position(
+    positions: &[(FieldElement, TransactionPosition)],
+    block_hash: FieldElement,
+    transaction_hash: FieldElement,
+) -> Result<TransactionPosition, EthApiError> {
+    positions.iter().find(|(hash, _)| *hash == transaction_hash).map(|(_, position)| *position).ok_or_else(|| {
+        EthApiError::OtherError(anyhow::anyhow!(
+            "Kakarot Core: transaction {transaction_hash:#x} not found in its block {block_hash:#x}"
+        ))
+    })
+}
 
-        let base_fee = self.base_fee_per_gas();
+/// Returns the filter of the transfers of the native token from `from_block` to `to_block`, which
+/// include the fees paid by the transactions of the blocks.
+fn native_transfer_filter(from_block: StarknetBlockId, to_block: StarknetBlockId) -> EventFilter {
+    EventFilter {
+        from_block: Some(from_block),
+        to_block: Some(to_block),
+        // This FieldElement::from_hex_be cannot fail as the value is a constant
+        address: Some(FieldElement::from_hex_be(STARKNET_NATIVE_TOKEN).unwrap()),
+        keys: Some(vec![vec![TRANSFER]]),
+    }
+}
 
-        let base_fee_per_gas: Vec<U256> = vec![base_fee; block_count_usize + 1];
-        let newest_block = match _newest_block {
-            BlockNumberOrTag::Number(n) => n,
-            // TODO: Add Genesis block number
-            BlockNumberOrTag::Earliest => 1_u64,
-            _ => self.block_number().await?.as_u64(),
+/// Returns the fee paid by each transaction emitting the native token transfers `transfers`, along
+/// with its block number and hash, in the order of the transactions. The fee of a transaction is
+/// its last transfer, after the transfers of its execution.
+fn fees_of_transfers(transfers: Vec<EmittedEvent>) -> Vec<(u64, FieldElement, U256)> {
+    let mut fees: Vec<(u64, FieldElement, U256)> = Vec::new();
+    for EmittedEvent { from_address, keys, data, block_number, transaction_hash, .. } in transfers {
+        let value = match decode_native_transfer(&Event { from_address, keys, data }) {
+            Some((_, _, value)) => value,
+            None => continue,
         };
+        match fees.last_mut() {
+            Some((_, hash, fee)) if *hash == transaction_hash => *fee = value,
+            _ => fees.push((block_number, transaction_hash, value)),
+        }
+    }
+    fees
+}
 
-        let gas_used_ratio: Vec<f64> = vec![0.9; block_count_usize];
-        let oldest_block: U256 = U256::from(newest_block) - _block_count;
-
-        Ok(FeeHistory { base_fee_per_gas, gas_used_ratio, oldest_block, reward: None })
+/// Returns the share of the gas limit of a block used by its transactions.
+fn gas_used_ratio(gas_used: U256, gas_limit: U256) -> f64 {
+    if gas_limit == U256::ZERO {
+        return 0.0;
     }
+    let gas_used = u128::try_from(gas_used).unwrap_or(u128::MAX) as f64;
+    let gas_limit = u128::try_from(gas_limit).unwrap_or(u128::MAX) as f64;
+    (gas_used / gas_limit).min(1.0)
+}
 
-    async fn estimate_gas(
-        &self,
-        _call_request: CallRequest,
-        _block_number: Option<BlockId>,
-    ) -> Result<U256, EthApiError> {
-        todo!();
+/// Returns the priority fee per gas paid by a transaction in a block with the given base fee.
+fn effective_priority_fee(transaction: &EtherTransaction, base_fee: U256) -> U256 {
+    match (transaction.max_fee_per_gas, transaction.max_priority_fee_per_gas) {
+        (Some(max_fee), Some(max_priority_fee)) => {
+            U256::from(max_priority_fee.to::<u128>()).min(U256::from(max_fee.to::<u128>()).saturating_sub(base_fee))
+        }
+        (None, Some(max_priority_fee)) => U256::from(max_priority_fee.to::<u128>()),
+        _ => transaction
+            .gas_price
+            .map(|gas_price| U256::from(gas_price.to::<u128>()).saturating_sub(base_fee))
+            .unwrap_or_default(),
     }
 }
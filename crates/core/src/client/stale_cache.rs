@@ -0,0 +1,125 @@
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Caches a value which changes with the head of the chain, e.g. the latest block, and serves it
+/// stale while it is refreshed in the background.
+///
+/// A cached value at most `staleness_budget` old is returned right away, and a single background
+/// task fetches the new value for the next requests. Older values are fetched before returning,
+/// so responses lag the node by at most the budget, and by one refresh under load.
+pub struct StaleCache<T> {
+    staleness_budget: Duration,
+    value: Mutex<Option<(T, Instant)>>,
+    refreshing: AtomicBool,
+}
+
+impl<T: Clone + Send + 'static> StaleCache<T> {
+    #[must_use]
+    pub fn new(staleness_budget: Duration) -> Self {
+        Self { staleness_budget, value: Mutex::new(None), refreshing: AtomicBool::new(false) }
+    }
+
+    /// Returns the cached value if it is within the staleness budget, refreshing it in the
+    /// background, or the value returned by `fetch` otherwise.
+    ///
+    /// Must be called from within a Tokio runtime, which runs the refresh.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the value is not cached and `fetch` fails. Failed background
+    /// refreshes keep the cached value.
+    pub async fn get<F, Fut, E>(self: &Arc<Self>, fetch: F) -> Result<T, E>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        let cached = self.value.lock().expect("stale cache lock poisoned").clone();
+        match cached {
+            Some((value, fetched_at)) if fetched_at.elapsed() <= self.staleness_budget => {
+                if !self.refreshing.swap(true, Ordering::AcqRel) {
+                    let cache = self.clone();
+                    let refresh = fetch();
+                    tokio::spawn(async move {
+                        if let Ok(value) = refresh.await {
+                            cache.store(value);
+                        }
+                        cache.refreshing.store(false, Ordering::Release);
+                    });
+                }
+                Ok(value)
+            }
+            _ => {
+                let value = fetch().await?;
+                self.store(value.clone());
+                Ok(value)
+            }
+        }
+    }
+
+    /// Whether a value within the staleness budget is cached, which `get` returns right away.
+    pub fn is_warm(&self) -> bool {
+        let cached = self.value.lock().expect("stale cache lock poisoned");
+        cached.as_ref().map_or(false, |(_, fetched_at)| fetched_at.elapsed() <= self.staleness_budget)
+    }
+
+    fn store(&self, value: T) {
+        *self.value.lock().expect("stale cache lock poisoned") = Some((value, Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicU64;
+
+    use super::*;
+
+    fn counter_fetch(counter: &Arc<AtomicU64>) -> impl Future<Output = Result<u64, ()>> + Send + 'static {
+        let counter = counter.clone();
+        async move { Ok(counter.fetch_add(1, Ordering::SeqCst)) }
+    }
+
+    #[tokio::test]
+    async fn test_stale_cache_serves_stale_value_while_refreshing() {
+        let cache = Arc::new(StaleCache::new(Duration::from_secs(60)));
+        let counter = Arc::new(AtomicU64::new(0));
+
+        assert_eq!(cache.get(|| counter_fetch(&counter)).await, Ok(0));
+        // The cached value is served, and refreshed in the background
+        assert_eq!(cache.get(|| counter_fetch(&counter)).await, Ok(0));
+        tokio::task::yield_now().await;
+        assert_eq!(cache.get(|| counter_fetch(&counter)).await, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn test_stale_cache_fetches_values_beyond_budget() {
+        let cache = Arc::new(StaleCache::new(Duration::ZERO));
+        let counter = Arc::new(AtomicU64::new(0));
+
+        assert_eq!(cache.get(|| counter_fetch(&counter)).await, Ok(0));
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        assert_eq!(cache.get(|| counter_fetch(&counter)).await, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn test_stale_cache_keeps_value_on_failed_refresh() {
+        let cache = Arc::new(StaleCache::new(Duration::from_secs(60)));
+
+        assert_eq!(cache.get(|| async { Ok::<_, ()>(7) }).await, Ok(7));
+        assert_eq!(cache.get(|| async { Err(()) }).await, Ok(7));
+        tokio::task::yield_now().await;
+        assert_eq!(cache.get(|| async { Err(()) }).await, Ok(7));
+    }
+
+    #[tokio::test]
+    async fn test_stale_cache_is_warm_within_budget() {
+        let cache = Arc::new(StaleCache::new(Duration::from_millis(10)));
+        assert!(!cache.is_warm());
+
+        assert_eq!(cache.get(|| async { Ok::<_, ()>(7) }).await, Ok(7));
+        assert!(cache.is_warm());
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!cache.is_warm());
+    }
+}
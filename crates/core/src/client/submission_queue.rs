@@ -0,0 +1,208 @@
+use std::collections::VecDeque;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use starknet::core::types::{BroadcastedInvokeTransactionV1, FieldElement};
+
+/// Bounded FIFO of the Starknet invoke transactions that could not be submitted because the
+/// Starknet node was unavailable. They are submitted again, in order, once the node is back.
+///
+/// The hashes of queued transactions are returned to their senders before Starknet sees them, so
+/// the last `capacity` transactions rejected once submitted are remembered, with the reason.
+///
+/// A queue with a capacity of 0 is disabled.
+///
+/// A queue persisted to a file, see [`SubmissionQueue::persisted`], writes its transactions to the
+/// file whenever they change, so that they survive a restart. The rejections are not persisted.
+pub struct SubmissionQueue {
+    capacity: usize,
+    transactions: Mutex<VecDeque<BroadcastedInvokeTransactionV1>>,
+    rejections: Mutex<VecDeque<(FieldElement, String)>>,
+    path: Option<PathBuf>,
+}
+
+impl SubmissionQueue {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            transactions: Mutex::new(VecDeque::new()),
+            rejections: Mutex::new(VecDeque::new()),
+            path: None,
+        }
+    }
+
+    /// Creates a queue persisted to the file at `path`, holding the transactions left in the file
+    /// if it exists.
+    ///
+    /// ## Errors
+    /// If the file exists but cannot be read as a queue.
+    pub fn persisted(capacity: usize, path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        let transactions = match fs::read(&path) {
+            Ok(transactions) => serde_json::from_slice(&transactions)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => VecDeque::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Self { transactions: Mutex::new(transactions), path: Some(path), ..Self::new(capacity) })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.transactions.lock().expect("submission queue lock poisoned").len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Queues a transaction at the back of the queue.
+    ///
+    /// ## Returns
+    /// `false` if the queue is full and the transaction was not queued.
+    pub fn push(&self, transaction: BroadcastedInvokeTransactionV1) -> bool {
+        let mut transactions = self.transactions.lock().expect("submission queue lock poisoned");
+        if transactions.len() >= self.capacity {
+            return false;
+        }
+        transactions.push_back(transaction);
+        self.persist(&transactions);
+        true
+    }
+
+    /// Returns the oldest queued transaction, without removing it.
+    pub fn front(&self) -> Option<BroadcastedInvokeTransactionV1> {
+        self.transactions.lock().expect("submission queue lock poisoned").front().cloned()
+    }
+
+    /// Returns the oldest queued transaction matching the predicate, without removing it.
+    pub fn find(
+        &self,
+        predicate: impl Fn(&BroadcastedInvokeTransactionV1) -> bool,
+    ) -> Option<BroadcastedInvokeTransactionV1> {
+        self.transactions.lock().expect("submission queue lock poisoned").iter().find(|tx| predicate(tx)).cloned()
+    }
+
+    /// Removes the oldest queued transaction.
+    pub fn pop_front(&self) -> Option<BroadcastedInvokeTransactionV1> {
+        let mut transactions = self.transactions.lock().expect("submission queue lock poisoned");
+        let transaction = transactions.pop_front()?;
+        self.persist(&transactions);
+        Some(transaction)
+    }
+
+    /// Records that Starknet rejected the queued transaction `transaction_hash` for `reason`,
+    /// forgetting the oldest rejection if `capacity` are already recorded.
+    pub fn record_rejection(&self, transaction_hash: FieldElement, reason: String) {
+        let mut rejections = self.rejections.lock().expect("submission queue lock poisoned");
+        if rejections.len() >= self.capacity {
+            rejections.pop_front();
+        }
+        rejections.push_back((transaction_hash, reason));
+    }
+
+    /// Returns why Starknet rejected the queued transaction `transaction_hash`, if it did.
+    pub fn rejection(&self, transaction_hash: &FieldElement) -> Option<String> {
+        let rejections = self.rejections.lock().expect("submission queue lock poisoned");
+        rejections.iter().find(|(hash, _)| hash == transaction_hash).map(|(_, reason)| reason.clone())
+    }
+
+    /// Writes `transactions` to the file of the queue, if persisted. The transactions stay queued
+    /// in memory if the file cannot be written.
+    fn persist(&self, transactions: &VecDeque<BroadcastedInvokeTransactionV1>) {
+        if let Some(path) = &self.path {
+            if let Err(err) = write_atomically(path, transactions) {
+                log::warn!("Failed to persist the submission queue to {}: {err}", path.display());
+            }
+        }
+    }
+}
+
+/// Writes `transactions` to a file next to `path`, then moves it to `path`, so that the file at
+/// `path` always holds a whole queue.
+fn write_atomically(path: &Path, transactions: &VecDeque<BroadcastedInvokeTransactionV1>) -> std::io::Result<()> {
+    let written = path.with_extension("tmp");
+    fs::write(&written, serde_json::to_vec(transactions)?)?;
+    fs::rename(written, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transaction(nonce: u64) -> BroadcastedInvokeTransactionV1 {
+        BroadcastedInvokeTransactionV1 {
+            max_fee: FieldElement::ZERO,
+            signature: vec![],
+            nonce: FieldElement::from(nonce),
+            sender_address: FieldElement::ONE,
+            calldata: vec![],
+        }
+    }
+
+    #[test]
+    fn test_submission_queue_is_fifo_and_bounded() {
+        let queue = SubmissionQueue::new(2);
+
+        assert!(queue.push(transaction(0)));
+        assert!(queue.push(transaction(1)));
+        assert!(!queue.push(transaction(2)));
+        assert_eq!(queue.len(), 2);
+
+        assert_eq!(queue.front().unwrap().nonce, FieldElement::from(0_u64));
+        assert_eq!(queue.pop_front().unwrap().nonce, FieldElement::from(0_u64));
+        assert_eq!(queue.pop_front().unwrap().nonce, FieldElement::from(1_u64));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_submission_queue_find() {
+        let queue = SubmissionQueue::new(2);
+        queue.push(transaction(0));
+        queue.push(transaction(1));
+
+        assert_eq!(queue.find(|tx| tx.nonce == FieldElement::from(1_u64)).unwrap().nonce, FieldElement::from(1_u64));
+        assert!(queue.find(|tx| tx.nonce == FieldElement::from(2_u64)).is_none());
+        assert_eq!(queue.len(), 2);
+    }
+
+    #[test]
+    fn test_submission_queue_remembers_the_last_rejections() {
+        let queue = SubmissionQueue::new(2);
+        for hash in 1..=3_u64 {
+            queue.record_rejection(FieldElement::from(hash), format!("rejected {hash}"));
+        }
+
+        assert!(queue.rejection(&FieldElement::from(1_u64)).is_none());
+        assert_eq!(queue.rejection(&FieldElement::from(3_u64)), Some("rejected 3".to_string()));
+    }
+
+    #[test]
+    fn test_persisted_submission_queue_survives_a_restart() {
+        let path = std::env::temp_dir().join(format!("submission_queue_{}.json", std::process::id()));
+        let queue = SubmissionQueue::persisted(2, &path).unwrap();
+        assert!(queue.is_empty());
+        queue.push(transaction(0));
+        queue.push(transaction(1));
+        // The first transaction is flushed
+        queue.pop_front();
+
+        let queue = SubmissionQueue::persisted(2, &path).unwrap();
+
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.front().unwrap().nonce, FieldElement::from(1_u64));
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_submission_queue_disabled() {
+        let queue = SubmissionQueue::new(0);
+
+        assert!(!queue.is_enabled());
+        assert!(!queue.push(transaction(0)));
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use futures::future::join_all;
+use reth_primitives::H256;
+use starknet::core::types::{BlockId as StarknetBlockId, BlockTag, MaybePendingBlockWithTxs};
+use starknet::providers::Provider;
+use tokio::runtime::Handle;
+use tokio::sync::broadcast;
+
+use super::client_api::KakarotProvider;
+use super::errors::EthApiError;
+use super::events::ClientEvent;
+use crate::models::convertible::ConvertibleStarknetTransaction;
+use crate::models::transaction::StarknetTransaction;
+
+/// Number of transaction hashes buffered for a subscriber before it starts missing pending
+/// transactions.
+const PENDING_TRANSACTIONS_CAPACITY: usize = 1024;
+
+/// Multiplexes the polling of the Starknet node into the channels of the subscriptions.
+///
+/// The new blocks, with their receipts, are fetched once by the
+/// [`ChainFollower`](super::events::ChainFollower) of the client, which keeps following the chain
+/// once started, and are broadcast on its event bus. A single polling task fetches the new pending
+/// transactions, broadcasting them to every subscriber, which starts with the first subscription
+/// and stops once there are no subscribers left.
+#[derive(Clone)]
+pub struct SubscriptionManager {
+    inner: Arc<SubscriptionManagerInner>,
+}
+
+struct SubscriptionManagerInner {
+    kakarot_client: Arc<dyn KakarotProvider>,
+    poll_interval: Duration,
+    pending_transactions: broadcast::Sender<H256>,
+    /// Whether the pending transactions polling task is running.
+    polling_pending_transactions: Mutex<bool>,
+    /// The runtime running the polling tasks, the runtime of the first subscriber if `None`.
+    runtime: Option<Handle>,
+}
+
+impl SubscriptionManager {
+    /// Creates a subscription manager polling the client every `poll_interval`.
+    ///
+    /// The polling tasks run on `runtime` if set, which isolates them from the request handling.
+    #[must_use]
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>, poll_interval: Duration, runtime: Option<Handle>) -> Self {
+        let (pending_transactions, _) = broadcast::channel(PENDING_TRANSACTIONS_CAPACITY);
+        Self {
+            inner: Arc::new(SubscriptionManagerInner {
+                kakarot_client,
+                poll_interval,
+                pending_transactions,
+                polling_pending_transactions: Mutex::new(false),
+                runtime,
+            }),
+        }
+    }
+
+    /// Returns the client the subscriptions are served from.
+    #[must_use]
+    pub fn kakarot_client(&self) -> &Arc<dyn KakarotProvider> {
+        &self.inner.kakarot_client
+    }
+
+    /// Returns a channel receiving the events of the blocks produced from now on, i.e. the
+    /// [`ClientEvent::NewBlock`] and [`ClientEvent::ReorgDetected`] events among the others of the
+    /// client.
+    ///
+    /// Without a dedicated runtime, must be called from within a Tokio runtime, which runs the
+    /// chain follower.
+    pub fn subscribe_new_blocks(&self) -> broadcast::Receiver<ClientEvent> {
+        // Subscribing before starting the follower receives its first blocks
+        let events = self.inner.kakarot_client.events().subscribe();
+        self.inner.kakarot_client.follower().start(
+            self.inner.kakarot_client.clone(),
+            self.inner.poll_interval,
+            self.inner.runtime.as_ref(),
+        );
+        events
+    }
+
+    /// Returns a channel receiving the hashes of the Kakarot transactions entering the pending
+    /// block from now on.
+    ///
+    /// Without a dedicated runtime, must be called from within a Tokio runtime, which runs the
+    /// polling task.
+    pub fn subscribe_pending_transactions(&self) -> broadcast::Receiver<H256> {
+        let mut polling = self.inner.polling_pending_transactions.lock().expect("subscription manager lock poisoned");
+        let receiver = self.inner.pending_transactions.subscribe();
+        if !*polling {
+            *polling = true;
+            self.spawn(poll_pending_transactions(self.inner.clone()));
+        }
+        receiver
+    }
+
+    fn spawn(&self, task: impl Future<Output = ()> + Send + 'static) {
+        match &self.inner.runtime {
+            Some(runtime) => runtime.spawn(task),
+            None => tokio::spawn(task),
+        };
+    }
+}
+
+async fn poll_pending_transactions(inner: Arc<SubscriptionManagerInner>) {
+    let mut interval = tokio::time::interval(inner.poll_interval);
+    // The hashes of the transactions already seen in the current pending block
+    let mut seen = HashSet::new();
+    let mut pending_parent = None;
+
+    loop {
+        interval.tick().await;
+
+        {
+            let mut polling = inner.polling_pending_transactions.lock().expect("subscription manager lock poisoned");
+            if inner.pending_transactions.receiver_count() == 0 {
+                *polling = false;
+                return;
+            }
+        }
+
+        let block = match inner
+            .kakarot_client
+            .starknet_provider()
+            .get_block_with_txs(StarknetBlockId::Tag(BlockTag::Pending))
+            .await
+        {
+            Ok(MaybePendingBlockWithTxs::PendingBlock(block)) => block,
+            _ => continue,
+        };
+        if pending_parent != Some(block.parent_hash) {
+            pending_parent = Some(block.parent_hash);
+            seen.clear();
+        }
+
+        // Only invoke transactions can be Kakarot transactions
+        let new_transactions: Vec<_> = block
+            .transactions
+            .into_iter()
+            .map(StarknetTransaction::from)
+            .filter_map(|transaction| match transaction.transaction_hash() {
+                Ok(hash) if !seen.contains(&H256::from(hash)) => Some((H256::from(hash), transaction)),
+                _ => None,
+            })
+            .collect();
+
+        let conversions =
+            join_all(new_transactions.iter().map(|(_, transaction)| {
+                transaction.to_eth_transaction(inner.kakarot_client.as_ref(), None, None, None)
+            }))
+            .await;
+        for ((hash, _), conversion) in new_transactions.into_iter().zip(conversions) {
+            match conversion {
+                // Sending only fails when there are no subscribers, which is checked on next tick
+                Ok(transaction) => {
+                    let _ = inner.pending_transactions.send(transaction.hash);
+                }
+                // A transaction which failed to be fetched is converted again on next tick
+                Err(EthApiError::RequestError(_) | EthApiError::RequestTimeout(_)) => continue,
+                // Not a Kakarot transaction, or one which does not convert
+                Err(_) => {}
+            }
+            seen.insert(hash);
+        }
+    }
+}
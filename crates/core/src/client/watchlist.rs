@@ -0,0 +1,164 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use reth_primitives::Address;
+use thiserror::Error;
+
+use crate::models::activity::AddressActivity;
+
+/// An address which cannot be watched because the watchlist is full.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("the watchlist is full, at most {0} addresses may be watched")]
+pub struct WatchlistFull(pub usize);
+
+/// The addresses watched by operators, e.g. for custodial monitoring, with the recent activity
+/// of each of them.
+///
+/// Activities are recorded by the [`ChainFollower`](super::events::ChainFollower), which only keeps
+/// the histories of the watched addresses instead of indexing every address.
+pub struct Watchlist {
+    max_addresses: usize,
+    history_size: usize,
+    histories: Mutex<HashMap<Address, VecDeque<AddressActivity>>>,
+}
+
+impl Watchlist {
+    /// Watches `addresses`, accepting up to `max_addresses` watched addresses and keeping at most
+    /// `history_size` activities for each of them.
+    pub fn new(addresses: impl IntoIterator<Item = Address>, max_addresses: usize, history_size: usize) -> Self {
+        let histories = addresses.into_iter().map(|address| (address, VecDeque::new())).collect();
+        Self { max_addresses, history_size, histories: Mutex::new(histories) }
+    }
+
+    /// Watches an address, returning whether it was not watched yet.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the address is not watched yet and the watchlist is full.
+    pub fn watch(&self, address: Address) -> Result<bool, WatchlistFull> {
+        let mut histories = self.histories.lock().expect("watchlist lock poisoned");
+        if histories.contains_key(&address) {
+            return Ok(false);
+        }
+        if histories.len() >= self.max_addresses {
+            return Err(WatchlistFull(self.max_addresses));
+        }
+        histories.insert(address, VecDeque::new());
+        Ok(true)
+    }
+
+    /// Stops watching an address and drops its history, returning whether it was watched.
+    pub fn unwatch(&self, address: Address) -> bool {
+        self.histories.lock().expect("watchlist lock poisoned").remove(&address).is_some()
+    }
+
+    /// Returns the watched addresses, sorted.
+    pub fn addresses(&self) -> Vec<Address> {
+        let mut addresses: Vec<Address> =
+            self.histories.lock().expect("watchlist lock poisoned").keys().copied().collect();
+        addresses.sort();
+        addresses
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.histories.lock().expect("watchlist lock poisoned").is_empty()
+    }
+
+    /// Records an activity of its address, dropping the oldest one if the history is full.
+    /// Returns whether the address is watched.
+    pub fn record(&self, activity: AddressActivity) -> bool {
+        let mut histories = self.histories.lock().expect("watchlist lock poisoned");
+        let history = match histories.get_mut(&activity.address) {
+            Some(history) => history,
+            None => return false,
+        };
+        if history.len() >= self.history_size {
+            history.pop_front();
+        }
+        history.push_back(activity);
+        true
+    }
+
+    /// Drops the activities of the blocks from `from_block` on, which were reorged out, and
+    /// returns them.
+    pub fn revert(&self, from_block: u64) -> Vec<AddressActivity> {
+        let mut reverted = Vec::new();
+        for history in self.histories.lock().expect("watchlist lock poisoned").values_mut() {
+            // The activities are recorded by block, so the reverted ones are the most recent
+            let kept = history.partition_point(|activity| activity.block_number.as_u64() < from_block);
+            reverted.extend(history.drain(kept..));
+        }
+        reverted
+    }
+
+    /// Returns the recent activities of a watched address, the oldest first, or `None` if it is
+    /// not watched.
+    pub fn history(&self, address: Address) -> Option<Vec<AddressActivity>> {
+        self.histories
+            .lock()
+            .expect("watchlist lock poisoned")
+            .get(&address)
+            .map(|history| history.iter().cloned().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reth_primitives::{H256, U64};
+
+    use super::*;
+
+    fn activity(address: Address, block_number: u64) -> AddressActivity {
+        AddressActivity {
+            address,
+            block_hash: H256::from_low_u64_be(block_number),
+            block_number: U64::from(block_number),
+            transaction_hash: H256::from_low_u64_be(block_number),
+        }
+    }
+
+    #[test]
+    fn test_watchlist_only_records_watched_addresses() {
+        let (watched, other) = (Address::from_low_u64_be(1), Address::from_low_u64_be(2));
+        let watchlist = Watchlist::new([watched], 10, 10);
+
+        assert!(watchlist.record(activity(watched, 1)));
+        assert!(!watchlist.record(activity(other, 1)));
+        assert_eq!(watchlist.history(watched), Some(vec![activity(watched, 1)]));
+        assert_eq!(watchlist.history(other), None);
+
+        assert_eq!(watchlist.watch(other), Ok(true));
+        assert_eq!(watchlist.watch(other), Ok(false));
+        assert_eq!(watchlist.addresses(), vec![watched, other]);
+        assert!(watchlist.unwatch(watched));
+        assert!(!watchlist.unwatch(watched));
+        assert_eq!(watchlist.history(watched), None);
+    }
+
+    #[test]
+    fn test_watchlist_keeps_recent_activities() {
+        let address = Address::from_low_u64_be(1);
+        let watchlist = Watchlist::new([address], 10, 2);
+
+        for block_number in 1..=3 {
+            watchlist.record(activity(address, block_number));
+        }
+        assert_eq!(watchlist.history(address), Some(vec![activity(address, 2), activity(address, 3)]));
+
+        assert_eq!(watchlist.revert(3), vec![activity(address, 3)]);
+        assert_eq!(watchlist.history(address), Some(vec![activity(address, 2)]));
+    }
+
+    #[test]
+    fn test_watchlist_is_capped() {
+        let watchlist = Watchlist::new([Address::from_low_u64_be(1)], 2, 10);
+
+        assert_eq!(watchlist.watch(Address::from_low_u64_be(2)), Ok(true));
+        assert_eq!(watchlist.watch(Address::from_low_u64_be(3)), Err(WatchlistFull(2)));
+        // Watching an address already watched does not need room
+        assert_eq!(watchlist.watch(Address::from_low_u64_be(2)), Ok(false));
+
+        assert!(watchlist.unwatch(Address::from_low_u64_be(1)));
+        assert_eq!(watchlist.watch(Address::from_low_u64_be(3)), Ok(true));
+    }
+}
@@ -0,0 +1,55 @@
+use std::time::Duration;
+
+use futures::future::join_all;
+use reqwest::header::CONTENT_TYPE;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use url::Url;
+
+use super::constants::requests::WEBHOOK_TIMEOUT_MS;
+use super::events::ClientEvent;
+use crate::models::activity::WatchlistEvent;
+
+/// Posts the changes of the activity of the watched addresses broadcast on `events` to every
+/// webhook, as JSON [`WatchlistEvent`]s, until the event bus is closed.
+///
+/// The events are posted in order, an event which a webhook fails to accept in time being
+/// dropped for it.
+pub async fn deliver_webhooks(mut events: broadcast::Receiver<ClientEvent>, webhook_urls: Vec<Url>) {
+    let http_client = reqwest::Client::new();
+
+    loop {
+        let event = match events.recv().await {
+            Ok(event) => match WatchlistEvent::from_client_event(event) {
+                Some(event) => event,
+                None => continue,
+            },
+            Err(RecvError::Lagged(missed)) => {
+                log::warn!("The watchlist webhooks missed {missed} events of the client");
+                continue;
+            }
+            Err(RecvError::Closed) => return,
+        };
+        let body = match serde_json::to_vec(&event) {
+            Ok(body) => body,
+            Err(err) => {
+                log::warn!("Failed to serialize the watchlist event {event:?}: {err}");
+                continue;
+            }
+        };
+
+        let deliveries = webhook_urls.iter().map(|webhook_url| {
+            http_client
+                .post(webhook_url.clone())
+                .header(CONTENT_TYPE, "application/json")
+                .timeout(Duration::from_millis(WEBHOOK_TIMEOUT_MS))
+                .body(body.clone())
+                .send()
+        });
+        for (webhook_url, delivery) in webhook_urls.iter().zip(join_all(deliveries).await) {
+            if let Err(err) = delivery.and_then(reqwest::Response::error_for_status) {
+                log::warn!("Failed to deliver a watchlist event to {webhook_url}: {err}");
+            }
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Encoding and decoding of EVM transactions for Kakarot.
+//!
+//! These are the exact routines the adapter runs on `eth_sendRawTransaction`: decoding the raw
+//! RLP transaction, recovering its signer and building the calldata of the Starknet invoke
+//! transaction sent to Kakarot. Indexers and bots can use them to pre-validate transactions
+//! client-side.
+
+use reth_primitives::{Address, Bytes, TransactionSigned};
+use reth_rlp::Decodable;
+use starknet::accounts::Call;
+use starknet::core::types::FieldElement;
+use thiserror::Error;
+
+use crate::client::constants::selectors::ETH_SEND_TRANSACTION;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CodecError {
+    #[error("transaction bytes are empty")]
+    EmptyTransaction,
+    #[error("transaction bytes failed to be decoded: {0}")]
+    TransactionDecodingError(String),
+    #[error("signature ecrecover failed")]
+    SignerRecoveryError,
+    #[error("invalid Starknet calldata: {0}")]
+    InvalidStarknetCalldata(String),
+}
+
+/// A decoded EVM transaction along with the address that signed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTransaction {
+    pub transaction: TransactionSigned,
+    pub signer: Address,
+}
+
+/// Decodes a raw RLP encoded EVM transaction, as received by `eth_sendRawTransaction`.
+///
+/// ## Arguments
+/// * `bytes` - The raw transaction, either legacy or an EIP-2718 typed transaction envelope.
+///
+/// ## Returns
+/// * `TransactionSigned` - The decoded transaction.
+pub fn decode_transaction(bytes: &[u8]) -> Result<TransactionSigned, CodecError> {
+    if bytes.is_empty() {
+        return Err(CodecError::EmptyTransaction);
+    }
+
+    let mut data = bytes;
+    TransactionSigned::decode(&mut data).map_err(|e| CodecError::TransactionDecodingError(e.to_string()))
+}
+
+/// Recovers the EVM address that signed the transaction.
+pub fn recover_signer(transaction: &TransactionSigned) -> Result<Address, CodecError> {
+    transaction.recover_signer().ok_or(CodecError::SignerRecoveryError)
+}
+
+/// Decodes a raw RLP encoded EVM transaction and recovers its signer.
+pub fn decode_signed_transaction(bytes: &[u8]) -> Result<SignedTransaction, CodecError> {
+    let transaction = decode_transaction(bytes)?;
+    let signer = recover_signer(&transaction)?;
+    Ok(SignedTransaction { transaction, signer })
+}
+
+/// Converts bytes to felts, one felt per byte, which is how Kakarot expects EVM payloads.
+pub fn bytes_to_felt_vec(bytes: &Bytes) -> Vec<FieldElement> {
+    bytes.to_vec().into_iter().map(FieldElement::from).collect()
+}
+
+/// Author: <https://github.com/xJonathanLEI/starknet-rs/blob/447182a90839a3e4f096a01afe75ef474186d911/starknet-accounts/src/account/execution.rs#L166>
+/// Constructs the calldata for a raw Starknet invoke transaction call
+/// ## Arguments
+/// * `kakarot_address` - The Kakarot contract address
+/// * `bytes` - The calldata to be passed to the contract - RLP encoded raw EVM transaction
+///
+/// ## Returns
+/// * `Vec<FieldElement>` - The calldata for the raw Starknet invoke transaction call
+pub fn raw_starknet_calldata(kakarot_address: FieldElement, bytes: Bytes) -> Vec<FieldElement> {
+    let calls: Vec<Call> =
+        vec![Call { to: kakarot_address, selector: ETH_SEND_TRANSACTION, calldata: bytes_to_felt_vec(&bytes) }];
+    let mut concated_calldata: Vec<FieldElement> = vec![];
+    let mut execute_calldata: Vec<FieldElement> = vec![calls.len().into()];
+    for call in &calls {
+        execute_calldata.push(call.to); // to
+        execute_calldata.push(call.selector); // selector
+        execute_calldata.push(concated_calldata.len().into()); // data_offset
+        execute_calldata.push(call.calldata.len().into()); // data_len
+
+        for item in &call.calldata {
+            concated_calldata.push(*item);
+        }
+    }
+    execute_calldata.push(concated_calldata.len().into()); // calldata_len
+    for item in concated_calldata {
+        execute_calldata.push(item); // calldata
+    }
+
+    execute_calldata
+}
+
+/// Reconstructs the raw RLP encoded EVM transaction from the calldata of a Kakarot invoke
+/// transaction. This is the inverse of `raw_starknet_calldata`.
+///
+/// The EVM transaction is read from the first call of the multicall, since contract accounts only
+/// ever send a single call to Kakarot. Whether the invoke transaction belongs to Kakarot at all is
+/// decided by its sender, not by its calldata.
+///
+/// ## Arguments
+/// * `calldata` - The calldata of the Starknet invoke transaction
+///
+/// ## Returns
+/// * `Bytes` - The signed EVM transaction bytes, as originally sent to `eth_sendRawTransaction`
+pub fn raw_transaction_from_starknet_calldata(calldata: &[FieldElement]) -> Result<Bytes, CodecError> {
+    let get = |index: usize| {
+        calldata
+            .get(index)
+            .copied()
+            .ok_or_else(|| CodecError::InvalidStarknetCalldata(format!("missing element at index {index}")))
+    };
+    let to_usize = |felt: FieldElement| {
+        u32::try_from(felt)
+            .map(|value| value as usize)
+            .map_err(|_| CodecError::InvalidStarknetCalldata(format!("{felt} is not a valid length or offset")))
+    };
+
+    let calls_len = to_usize(get(0)?)?;
+    if calls_len == 0 {
+        return Err(CodecError::InvalidStarknetCalldata("multicall contains no call".to_string()));
+    }
+
+    // Each call is encoded as (to, selector, data_offset, data_len). The calls array is followed by
+    // the length of the concatenated calldata, then the calldata itself.
+    let data_offset = to_usize(get(3)?)?;
+    let data_len = to_usize(get(4)?)?;
+    let start = calls_len * 4 + 2 + data_offset;
+    let data = calldata.get(start..start + data_len).ok_or_else(|| {
+        CodecError::InvalidStarknetCalldata(format!("call data out of bounds ({start}..{})", start + data_len))
+    })?;
+
+    let bytes = data
+        .iter()
+        .map(|felt| {
+            u8::try_from(*felt).map_err(|_| CodecError::InvalidStarknetCalldata(format!("{felt} is not a valid byte")))
+        })
+        .collect::<Result<Vec<u8>, _>>()?;
+
+    Ok(Bytes::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use reth_primitives::{AccessList, AccessListItem, TxType, H256};
+
+    use super::*;
+    use crate::client::constants::CHAIN_ID;
+
+    /// EIP-1559 transaction with nonce 0xdead, calling 0x2e11..f4d1 with input 0xb3bcfa82.
+    const RAW_TRANSACTION: &str = "02f872844b4b525482dead82dead82dead843b9aca00942e11ed82f5ec165ab8ce3cc094f025fe7527f4d18084b3bcfa82c001a0889be67d59bc1a43dd803955f7917ddcb7d748ed3e9b00cdb159f294651976b8a03801702a606ffbfd60364ff897f7ca511411d6660f936dd51eb90a7d30735261";
+
+    /// EIP-2930 transaction with nonce 0xdead and gas price 1 gwei, calling 0x2e11..f4d1 with input
+    /// 0xb3bcfa82 and warming its storage slot 1.
+    ///
+    /// Its signature is the one of [`RAW_TRANSACTION`], which does not sign this payload: the
+    /// vector only exercises decoding. The signed vectors recorded from the Kakarot deployment are
+    /// in `test_vectors`.
+    const RAW_ACCESS_LIST_TRANSACTION: &str = "01f8a8844b4b525482dead843b9aca0082dead942e11ed82f5ec165ab8ce3cc094f025fe7527f4d18084b3bcfa82f838f7942e11ed82f5ec165ab8ce3cc094f025fe7527f4d1e1a0000000000000000000000000000000000000000000000000000000000000000101a0889be67d59bc1a43dd803955f7917ddcb7d748ed3e9b00cdb159f294651976b8a03801702a606ffbfd60364ff897f7ca511411d6660f936dd51eb90a7d30735261";
+
+    #[test]
+    fn test_decode_transaction() {
+        let bytes = hex::decode(RAW_TRANSACTION).unwrap();
+        let transaction = decode_transaction(&bytes).unwrap();
+
+        assert_eq!(transaction.chain_id(), Some(CHAIN_ID));
+        assert_eq!(transaction.nonce(), 0xdead);
+        assert_eq!(transaction.to(), Some(Address::from_str("0x2e11ed82f5ec165ab8ce3cc094f025fe7527f4d1").unwrap()));
+        assert_eq!(transaction.input(), &Bytes::from(vec![0xb3, 0xbc, 0xfa, 0x82]));
+    }
+
+    #[test]
+    fn test_decode_access_list_transaction() {
+        let bytes = hex::decode(RAW_ACCESS_LIST_TRANSACTION).unwrap();
+        let transaction = decode_transaction(&bytes).unwrap();
+        let to = Address::from_str("0x2e11ed82f5ec165ab8ce3cc094f025fe7527f4d1").unwrap();
+
+        assert_eq!(transaction.tx_type(), TxType::EIP2930);
+        assert_eq!(transaction.chain_id(), Some(CHAIN_ID));
+        assert_eq!(transaction.max_fee_per_gas(), 1_000_000_000);
+        assert_eq!(
+            transaction.access_list(),
+            Some(&AccessList(vec![AccessListItem { address: to, storage_keys: vec![H256::from_low_u64_be(1)] }]))
+        );
+    }
+
+    #[test]
+    fn test_decode_transaction_fails_on_empty_bytes() {
+        assert_eq!(decode_transaction(&[]).unwrap_err(), CodecError::EmptyTransaction);
+    }
+
+    #[test]
+    fn test_bytes_to_felt_vec() {
+        let bytes = Bytes::from(vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        let felt_vec = bytes_to_felt_vec(&bytes);
+        assert_eq!(felt_vec.len(), 10);
+        assert_eq!(
+            felt_vec,
+            vec![
+                FieldElement::from(1_u64),
+                FieldElement::from(2_u64),
+                FieldElement::from(3_u64),
+                FieldElement::from(4_u64),
+                FieldElement::from(5_u64),
+                FieldElement::from(6_u64),
+                FieldElement::from(7_u64),
+                FieldElement::from(8_u64),
+                FieldElement::from(9_u64),
+                FieldElement::from(10_u64)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_starknet_calldata() {
+        let kakarot_address = FieldElement::from_hex_be("0xdead").unwrap();
+        let bytes = Bytes::from(vec![1, 2, 3]);
+
+        let calldata = raw_starknet_calldata(kakarot_address, bytes);
+
+        assert_eq!(
+            calldata,
+            vec![
+                FieldElement::ONE,
+                kakarot_address,
+                ETH_SEND_TRANSACTION,
+                FieldElement::ZERO,
+                FieldElement::from(3_u64),
+                FieldElement::from(3_u64),
+                FieldElement::from(1_u64),
+                FieldElement::from(2_u64),
+                FieldElement::from(3_u64),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_raw_transaction_from_starknet_calldata() {
+        let kakarot_address = FieldElement::from_hex_be("0xdead").unwrap();
+        let bytes = Bytes::from(hex::decode(RAW_TRANSACTION).unwrap());
+
+        let calldata = raw_starknet_calldata(kakarot_address, bytes.clone());
+        let raw_transaction = raw_transaction_from_starknet_calldata(&calldata).unwrap();
+
+        assert_eq!(raw_transaction, bytes);
+        assert!(decode_transaction(&raw_transaction).is_ok());
+    }
+
+    #[test]
+    fn test_raw_transaction_from_starknet_calldata_fails_on_truncated_calldata() {
+        let kakarot_address = FieldElement::from_hex_be("0xdead").unwrap();
+        let mut calldata = raw_starknet_calldata(kakarot_address, Bytes::from(vec![1, 2, 3]));
+        calldata.pop();
+
+        assert!(matches!(
+            raw_transaction_from_starknet_calldata(&calldata).unwrap_err(),
+            CodecError::InvalidStarknetCalldata(_)
+        ));
+    }
+}
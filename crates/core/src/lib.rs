@@ -1,4 +1,8 @@
 #![feature(more_qualified_paths)]
 pub mod client;
+pub mod codec;
 pub mod mock;
 pub mod models;
+pub mod sdk;
+pub mod test_vectors;
+pub mod tx_builder;
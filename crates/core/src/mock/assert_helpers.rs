@@ -1,15 +1,13 @@
 use std::str::FromStr;
 
-use reth_primitives::{Bloom, Bytes, H160, H256, U128, U256};
+use reth_primitives::{Bloom, Bytes, H160, H256, U128, U256, U64};
 use reth_rpc_types::{Block, BlockTransactions, Rich, Signature, Transaction};
 use serde::{Deserialize, Serialize};
 use starknet::core::types::{FieldElement, InvokeTransaction, Transaction as StarknetTransaction};
 
 use crate::client::constants::gas::BASE_FEE_PER_GAS;
-use crate::client::constants::{CHAIN_ID, DIFFICULTY, GAS_LIMIT, GAS_USED, MIX_HASH, NONCE, SIZE, TOTAL_DIFFICULTY};
-use crate::client::helpers::starknet_address_to_ethereum_address;
+use crate::client::constants::{DIFFICULTY, GAS_LIMIT, GAS_USED, MIX_HASH, NONCE, SIZE, TOTAL_DIFFICULTY};
 use crate::models::felt::Felt252Wrapper;
-use crate::models::signature::StarknetSignature;
 
 #[derive(Serialize, Deserialize, Debug)]
 struct StarknetBlockTest {
@@ -48,7 +46,7 @@ pub fn assert_block(block: &Rich<Block>, starknet_res: String, starknet_txs: Str
                 assert_eq!(transactions[i].block_number, Some(U256::from(starknet_data.block_number)));
                 assert_eq!(transactions[i].block_hash, Some(H256::from_slice(&starknet_block_hash.to_bytes_be())));
 
-                assert_transaction(transactions[i].clone(), transaction.clone());
+                assert_transaction(transactions[i].clone(), fixture_eth_transaction(&transaction));
             }
         } else {
             panic!("BlockTransactions::Hashes should not be returned")
@@ -113,83 +111,82 @@ pub fn assert_block_header(block: &Rich<Block>, starknet_res: String, hydrated:
     assert_eq!(block.header.nonce, *NONCE);
 }
 
-pub fn assert_transaction(ether_tx: Transaction, starknet_tx: StarknetTransaction) {
-    assert_eq!(ether_tx.chain_id, Some(CHAIN_ID.into()));
-    assert_eq!(ether_tx.access_list, None);
-    assert_eq!(ether_tx.transaction_type, None);
+/// The init code of the contract created by the EVM transaction of the fixtures.
+const FIXTURE_INIT_CODE: &str = concat!(
+    "608060405234801561001057600080fd5b506000805561023c806100246000396000f3fe608060405234801561001057",
+    "600080fd5b50600436106100625760003560e01c806306661abd14610067578063371303c0146100825780637c507cbd",
+    "1461008c578063b3bcfa8214610094578063d826f88f1461009c578063f0707ea9146100a5575b600080fd5b61007060",
+    "005481565b60405190815260200160405180910390f35b61008a6100ad565b005b61008a6100c6565b61008a61010656",
+    "5b61008a60008055565b61008a610139565b60016000808282546100bf919061017c565b9091555050565b6000805411",
+    "6100f05760405162461bcd60e51b81526004016100e790610195565b60405180910390fd5b6000805490806100ff8361",
+    "01dc565b9190505550565b60008054116101275760405162461bcd60e51b81526004016100e790610195565b60016000",
+    "808282546100bf91906101f3565b600080541161015a5760405162461bcd60e51b81526004016100e790610195565b60",
+    "008054600019019055565b634e487b7160e01b600052601160045260246000fd5b8082018082111561018f5761018f61",
+    "0166565b92915050565b60208082526027908201527f636f756e742073686f756c64206265207374726963746c792067",
+    "7265617465726040820152660207468616e20360cc1b606082015260800190565b6000816101eb576101eb610166565b",
+    "506000190190565b8181038181111561018f5761018f61016656fea26469706673582212203091d34e6cbebc53198d4c",
+    "0d09786b51423a7ae0de314456c74c68aaccc311e364736f6c63430008110033",
+);
+
+/// Returns the EVM transaction served for the Kakarot transaction `starknet_tx` of the fixtures,
+/// outside of a block: the hash and nonce of `starknet_tx`, wrapping an EIP-1559 contract creation
+/// on chain `0x4b4b5254` signed by `0x54b2…5de1`, with the gas limit and both fees at `0xdead`.
+pub fn fixture_eth_transaction(starknet_tx: &StarknetTransaction) -> Transaction {
+    let (hash, nonce) = match starknet_tx {
+        StarknetTransaction::Invoke(InvokeTransaction::V1(v1)) => (v1.transaction_hash, v1.nonce),
+        _ => panic!("the Kakarot transactions of the fixtures are invoke v1 transactions"),
+    };
+    let nonce: Felt252Wrapper = nonce.into();
+
+    Transaction {
+        hash: H256::from_slice(&hash.to_bytes_be()),
+        // The nonce served is the one of the Starknet transaction, not the 0xdead signed
+        nonce: nonce.into(),
+        block_hash: None,
+        block_number: None,
+        transaction_index: None,
+        from: H160::from_str("0x54b288676b749def5fc10eb17244fe2c87375de1").unwrap(),
+        to: None,
+        value: U256::ZERO,
+        gas_price: None,
+        gas: U256::from(0xdead),
+        max_fee_per_gas: Some(U128::from(0xdead)),
+        max_priority_fee_per_gas: Some(U128::from(0xdead)),
+        input: Bytes::from(hex::decode(FIXTURE_INIT_CODE).unwrap()),
+        // An EIP-1559 transaction only carries the parity of the y coordinate of the signature
+        signature: Some(Signature {
+            r: U256::from_str("0x5e6a35e537e8d99c81bf2d4e7e8a410e7f6f3f8b1f07edc28bf226d3ac2cae12").unwrap(),
+            s: U256::from_str("0x1910d7b4784e7347a6c7dccf8b8051c06f091347eb4a4a2f6092f1541cb62de7").unwrap(),
+            v: U256::from(1),
+        }),
+        chain_id: Some(U64::from(0x4b4b_5254)),
+        access_list: Some(vec![]),
+        transaction_type: Some(U64::from(2)),
+    }
+}
+
+/// Asserts that `ether_tx` is the transaction `expected`, whose block is asserted by the callers.
+pub fn assert_transaction(ether_tx: Transaction, expected: Transaction) {
+    assert_eq!(ether_tx.hash, expected.hash);
+    assert_eq!(ether_tx.from, expected.from);
+    assert_eq!(ether_tx.chain_id, expected.chain_id);
+    assert_eq!(ether_tx.transaction_type, expected.transaction_type);
 
-    assert_eq!(ether_tx.to, None);
-    assert_eq!(ether_tx.value, U256::from(100));
-    assert_eq!(ether_tx.gas, U256::from(100));
-    assert_eq!(ether_tx.gas_price, None);
     let index = match ether_tx.transaction_index {
         Some(_) => Some(U256::from(0)),
         _ => None,
     };
     assert_eq!(ether_tx.transaction_index, index);
-    assert_eq!(ether_tx.max_fee_per_gas, None);
-    assert_eq!(ether_tx.max_priority_fee_per_gas, Some(U128::ZERO));
-
-    match starknet_tx {
-        StarknetTransaction::Invoke(invoke_tx) => {
-            match invoke_tx {
-                InvokeTransaction::V0(v0) => {
-                    assert_eq!(ether_tx.hash, H256::from_slice(&v0.transaction_hash.to_bytes_be()));
-                    let tx_nonce: Felt252Wrapper = v0.nonce.into();
-                    assert_eq!(ether_tx.nonce, tx_nonce.into());
-                    assert_eq!(ether_tx.from, starknet_address_to_ethereum_address(&v0.contract_address));
-                    // r and s values are extracted from the calldata of the first transaction
-                    // in the starknet_getBlockWithTxs.json file.
-                    // v value is calculated from the parity of the y coordinate of the signature,
-                    // to which we add 35 + 2 * CHAIN_ID (based on https://eips.ethereum.org/EIPS/eip-155).
-                    let signature = Signature {
-                        r: U256::from_str("0x05e6a35e537e8d99c81bf2d4e7e8a410e7f6f3f8b1f07edc28bf226d3ac2cae12")
-                            .unwrap(),
-                        s: U256::from_str("0x01910d7b4784e7347a6c7dccf8b8051c06f091347eb4a4a2f6092f1541cb62de7")
-                            .unwrap(),
-                        v: U256::from_str("0x000000000000000000000000000000000000000000000000000000009696a4cc")
-                            .unwrap(),
-                    };
-                    assert_eq!(ether_tx.signature, Some(signature));
-                }
-                InvokeTransaction::V1(v1) => {
-                    assert_eq!(ether_tx.hash, H256::from_slice(&v1.transaction_hash.to_bytes_be()));
-                    let tx_nonce: Felt252Wrapper = v1.nonce.into();
-                    assert_eq!(ether_tx.nonce, tx_nonce.into());
-                    assert_eq!(ether_tx.from, H160::from_str("0x54b288676b749def5fc10eb17244fe2c87375de1").unwrap());
-                    // r and s values are extracted from the calldata of the first transaction
-                    // in the starknet_getBlockWithTxs.json file.
-                    // v value is calculated from the parity of the y coordinate of the signature,
-                    // to which we add 35 + 2 * CHAIN_ID (based on https://eips.ethereum.org/EIPS/eip-155).
-                    let signature = Signature {
-                        r: U256::from_str("0x05e6a35e537e8d99c81bf2d4e7e8a410e7f6f3f8b1f07edc28bf226d3ac2cae12")
-                            .unwrap(),
-                        s: U256::from_str("0x01910d7b4784e7347a6c7dccf8b8051c06f091347eb4a4a2f6092f1541cb62de7")
-                            .unwrap(),
-                        v: U256::from_str("0x000000000000000000000000000000000000000000000000000000009696a4cc")
-                            .unwrap(),
-                    };
-                    assert_eq!(ether_tx.signature, Some(signature));
-                    // TODO: test ether_tx.input
-                }
-            }
-        }
-        StarknetTransaction::Deploy(deploy_tx) => {
-            assert_eq!(ether_tx.hash, H256::from_slice(&deploy_tx.transaction_hash.to_bytes_be()));
-            // TODO: nonce, from, input, v, r, s
-        }
-        StarknetTransaction::DeployAccount(deploy_account_tx) => {
-            assert_eq!(ether_tx.hash, H256::from_slice(&deploy_account_tx.transaction_hash.to_bytes_be()));
-            let tx_nonce: Felt252Wrapper = deploy_account_tx.nonce.into();
-            assert_eq!(ether_tx.nonce, tx_nonce.into());
-            let signature: StarknetSignature = deploy_account_tx.signature.into();
-            let signature: Signature = signature.try_into().unwrap();
-            assert_eq!(ether_tx.signature, Some(signature));
-            // TODO: from
-        }
-        StarknetTransaction::L1Handler(_) | StarknetTransaction::Declare(_) => {
-            // L1Handler & Declare transactions not supported for now in Kakarot
-            todo!();
-        }
-    };
+
+    assert_eq!(ether_tx.nonce, expected.nonce);
+    assert_eq!(ether_tx.gas, expected.gas);
+    assert_eq!(ether_tx.gas_price, expected.gas_price);
+    assert_eq!(ether_tx.max_fee_per_gas, expected.max_fee_per_gas);
+    assert_eq!(ether_tx.max_priority_fee_per_gas, expected.max_priority_fee_per_gas);
+    assert_eq!(ether_tx.access_list, expected.access_list);
+
+    assert_eq!(ether_tx.to, expected.to);
+    assert_eq!(ether_tx.value, expected.value);
+    assert_eq!(ether_tx.input, expected.input);
+    assert_eq!(ether_tx.signature, expected.signature);
 }
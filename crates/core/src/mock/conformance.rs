@@ -0,0 +1,286 @@
+//! Conformance of the RPC responses to the schemas of the Ethereum execution-apis
+//! (<https://github.com/ethereum/execution-apis>), to catch regressions of the conversion layer
+//! before a wallet does.
+//!
+//! The schemas of `src/schemas` are vendored in `fixtures/execution-apis`, converted from YAML to
+//! JSON. Responses are validated in their serialized form, so that renamed fields, nulls in place
+//! of values and badly encoded hex strings are reported:
+//!
+//! ```ignore
+//! let block = kakarot_rpc.block_by_number(BlockNumberOrTag::Latest, false).await?;
+//! assert_conforms(&block, BLOCK);
+//! ```
+//!
+//! Fields absent from the schemas, such as the Kakarot metadata, are ignored. The responses which
+//! knowingly depart from the schemas are listed in [`DEVIATIONS`].
+use std::collections::HashMap;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+/// The schema of `eth_getBlockByHash` and `eth_getBlockByNumber` responses.
+pub const BLOCK: &str = "Block";
+/// The schema of `eth_getTransactionByHash` and the transactions of hydrated blocks.
+pub const TRANSACTION: &str = "TransactionInfo";
+/// The schema of `eth_getTransactionReceipt` responses.
+pub const RECEIPT: &str = "ReceiptInfo";
+/// The schema of the logs of `eth_getLogs` responses and of receipts.
+pub const LOG: &str = "Log";
+
+/// How a response departs from a field of its schema.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Departure {
+    /// The field is `null` instead of a value.
+    Null,
+    /// The required field is missing.
+    Missing,
+}
+
+/// A known departure of the responses from a field of the execution-apis schemas, tolerated by
+/// [`validate`].
+#[derive(Debug, Clone, Copy)]
+pub struct Deviation {
+    /// The schema holding the field, e.g. `ReceiptInfo`.
+    pub schema: &'static str,
+    pub field: &'static str,
+    pub departure: Departure,
+    pub reason: &'static str,
+}
+
+/// The documented deviations of the responses from the execution-apis schemas.
+pub const DEVIATIONS: &[Deviation] = &[
+    Deviation {
+        schema: "Transaction1559Unsigned",
+        field: "gasPrice",
+        departure: Departure::Missing,
+        reason: "the effective gas price of EIP-1559 transactions is not served",
+    },
+    Deviation {
+        schema: "Transaction1559Unsigned",
+        field: "to",
+        departure: Departure::Null,
+        reason: "contract creations have a null recipient, as served by geth",
+    },
+    Deviation {
+        schema: "Transaction1559Signed",
+        field: "yParity",
+        departure: Departure::Missing,
+        reason: "the signature of typed transactions carries the y parity as v",
+    },
+];
+
+lazy_static! {
+    /// The vendored execution-apis schemas, by name.
+    static ref SCHEMAS: HashMap<String, Value> = [
+        include_str!("fixtures/execution-apis/base-types.json"),
+        include_str!("fixtures/execution-apis/block.json"),
+        include_str!("fixtures/execution-apis/receipt.json"),
+        include_str!("fixtures/execution-apis/transaction.json"),
+    ]
+    .into_iter()
+    .flat_map(|schemas| serde_json::from_str::<Map<String, Value>>(schemas).expect("invalid vendored schemas"))
+    .collect();
+}
+
+/// A value of a response which does not conform to its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{path}: {reason}")]
+pub struct ConformanceError {
+    /// The path of the value in the response, e.g. `$.logs[0].topics`.
+    pub path: String,
+    pub reason: String,
+}
+
+/// Returns the values of a serialized response which do not conform to the schema named `schema`,
+/// apart from the [`DEVIATIONS`].
+///
+/// `oneOf` is checked as `anyOf`, the alternatives of the execution-apis being exclusive.
+#[must_use]
+pub fn validate(response: &Value, schema: &str) -> Vec<ConformanceError> {
+    let mut errors = Vec::new();
+    validate_named(response, schema, "$", &mut errors);
+    errors
+}
+
+/// Panics with the list of non-conformities if the response does not conform to the schema named
+/// `schema`.
+pub fn assert_conforms<T: Serialize>(response: &T, schema: &str) {
+    let response = serde_json::to_value(response).expect("failed to serialize response");
+    let errors = validate(&response, schema);
+    assert!(
+        errors.is_empty(),
+        "response does not conform to {schema}:\n{}",
+        errors.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+    );
+}
+
+fn validate_named(value: &Value, name: &str, path: &str, errors: &mut Vec<ConformanceError>) {
+    let schema = SCHEMAS.get(name).unwrap_or_else(|| panic!("unknown schema {name}"));
+    validate_schema(value, schema, name, path, errors);
+}
+
+/// Validates `value` against `schema`, a part of the schema named `name`.
+fn validate_schema(value: &Value, schema: &Value, name: &str, path: &str, errors: &mut Vec<ConformanceError>) {
+    if let Some(reference) = schema["$ref"].as_str() {
+        let referenced = reference.trim_start_matches("#/components/schemas/");
+        return validate_named(value, referenced, path, errors);
+    }
+
+    if let Some(schemas) = schema["allOf"].as_array() {
+        for schema in schemas {
+            validate_schema(value, schema, name, path, errors);
+        }
+    }
+    if let Some(alternatives) = schema["oneOf"].as_array().or_else(|| schema["anyOf"].as_array()) {
+        // Without a conforming alternative, report the one the value is the closest to
+        let mut closest: Option<Vec<ConformanceError>> = None;
+        for alternative in alternatives {
+            let mut alternative_errors = Vec::new();
+            validate_schema(value, alternative, name, path, &mut alternative_errors);
+            if alternative_errors.is_empty() {
+                closest = None;
+                break;
+            }
+            if closest.as_ref().map_or(true, |closest| alternative_errors.len() < closest.len()) {
+                closest = Some(alternative_errors);
+            }
+        }
+        errors.extend(closest.into_iter().flatten());
+    }
+
+    if let Some(expected) = schema["type"].as_str() {
+        let matches = match expected {
+            "string" => value.is_string(),
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            other => panic!("unsupported type {other} in schema {name}"),
+        };
+        if !matches {
+            return errors.push(error(path, format!("expected a {expected}, got {value}")));
+        }
+    }
+
+    if let (Some(pattern), Some(string)) = (schema["pattern"].as_str(), value.as_str()) {
+        if !Regex::new(pattern).expect("invalid pattern").is_match(string) {
+            errors.push(error(path, format!("{string} does not match {pattern}")));
+        }
+    }
+
+    if let Some(object) = value.as_object() {
+        for field in schema["required"].as_array().into_iter().flatten().filter_map(Value::as_str) {
+            if !object.contains_key(field) && !deviates(name, field, Departure::Missing) {
+                errors.push(error(&format!("{path}.{field}"), "missing required field".to_string()));
+            }
+        }
+        for (field, field_schema) in schema["properties"].as_object().into_iter().flatten() {
+            match object.get(field) {
+                Some(Value::Null) if deviates(name, field, Departure::Null) => {}
+                Some(field_value) => {
+                    validate_schema(field_value, field_schema, name, &format!("{path}.{field}"), errors)
+                }
+                None => {}
+            }
+        }
+    }
+
+    if let (Some(items_schema), Some(items)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in items.iter().enumerate() {
+            validate_schema(item, items_schema, name, &format!("{path}[{index}]"), errors);
+        }
+    }
+}
+
+fn deviates(schema: &str, field: &str, departure: Departure) -> bool {
+    DEVIATIONS
+        .iter()
+        .any(|deviation| deviation.schema == schema && deviation.field == field && deviation.departure == departure)
+}
+
+fn error(path: &str, reason: String) -> ConformanceError {
+    ConformanceError { path: path.to_string(), reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn log() -> Value {
+        json!({
+            "address": "0x22341ae42d6dd7384bc8584e50419ea3ac75b83f",
+            "topics": ["0x04491edcd115127caedbd478e2e7895ed80c7847e903431f94f9cfa579cad47f"],
+            "data": "0x",
+            "blockHash": "0x0000000000000000000000000000000000000000000000000000000000000d0d",
+            "blockNumber": "0x1a",
+            "transactionHash": "0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c",
+            "transactionIndex": "0x0",
+            "logIndex": "0x0",
+            "removed": false,
+        })
+    }
+
+    #[test]
+    fn test_conforming_log() {
+        assert_eq!(validate(&log(), LOG), vec![]);
+    }
+
+    #[test]
+    fn test_non_conforming_log() {
+        let mut log = log();
+        log["blockNumber"] = json!("0x01a");
+        log["topics"] = json!(["0x04491e"]);
+        log["data"] = Value::Null;
+        log.as_object_mut().unwrap().remove("transactionHash");
+
+        let paths: Vec<_> = validate(&log, LOG).into_iter().map(|error| error.path).collect();
+
+        assert_eq!(paths, vec!["$.transactionHash", "$.blockNumber", "$.data", "$.topics[0]"]);
+    }
+
+    #[test]
+    fn test_quantities() {
+        for quantity in ["0x0", "0x1a"] {
+            assert_eq!(validate(&json!(quantity), "uint"), vec![], "{quantity}");
+        }
+        for not_quantity in ["0x", "0x00", "1a", "0xzz", "0x1A"] {
+            assert_eq!(validate(&json!(not_quantity), "uint").len(), 1, "{not_quantity}");
+        }
+    }
+
+    #[test]
+    fn test_deviations_only_tolerate_their_departure() {
+        let transaction = |gas_price: Option<Value>| {
+            let mut transaction = json!({
+                "type": "0x2",
+                "nonce": "0x0",
+                "to": null,
+                "gas": "0x5208",
+                "value": "0x0",
+                "input": "0x",
+                "maxFeePerGas": "0x1",
+                "maxPriorityFeePerGas": "0x0",
+                "chainId": "0x4b4b5254",
+            });
+            if let Some(gas_price) = gas_price {
+                transaction["gasPrice"] = gas_price;
+            }
+            transaction
+        };
+        let paths = |transaction: Value| {
+            validate(&transaction, "Transaction1559Unsigned").into_iter().map(|error| error.path).collect::<Vec<_>>()
+        };
+
+        // Transactions are served without a gas price and contract creations with a null
+        // recipient, but not with a null gas price nor a malformed one
+        assert_eq!(paths(transaction(None)), Vec::<String>::new());
+        assert_eq!(paths(transaction(Some(json!("0x1")))), Vec::<String>::new());
+        assert_eq!(paths(transaction(Some(Value::Null))), vec!["$.gasPrice"]);
+        assert_eq!(paths(transaction(Some(json!("0x01")))), vec!["$.gasPrice"]);
+    }
+}
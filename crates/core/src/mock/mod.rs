@@ -1,4 +1,6 @@
 pub mod assert_helpers;
+pub mod conformance;
 pub mod mock_starknet;
+pub mod recorder;
 pub mod serde;
 pub mod wiremock_utils;
@@ -0,0 +1,136 @@
+//! Record and replay of Starknet JSON-RPC exchanges, to reproduce conversion bugs on real blocks
+//! without a Starknet node.
+//!
+//! Exchanges are captured by wrapping the transport of a Starknet provider in a
+//! [`RecordingTransport`] and saved to a fixture file. They are replayed by mounting them on the
+//! wiremock server a `KakarotClient` is pointed at:
+//!
+//! ```ignore
+//! let transport = RecordingTransport::new(HttpTransport::new(mainnet_url));
+//! let recording = transport.recording();
+//! let provider = JsonRpcClient::new(transport);
+//! // ... issue the Starknet requests of the reported bug
+//! recording.save("fixtures/issue.json")?;
+//!
+//! let mock_server = MockServer::start().await;
+//! mount_exchanges(&mock_server, &load_exchanges("fixtures/issue.json")?).await;
+//! ```
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use starknet::providers::jsonrpc::{JsonRpcMethod, JsonRpcResponse, JsonRpcTransport};
+use thiserror::Error;
+use wiremock::matchers::{body_partial_json, method};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// A Starknet JSON-RPC request and the result returned for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedExchange {
+    pub method: Value,
+    pub params: Value,
+    pub result: Value,
+}
+
+#[derive(Debug, Error)]
+pub enum RecordingTransportError<E> {
+    #[error(transparent)]
+    Transport(E),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+/// A transport recording the successful exchanges of the transport it wraps.
+///
+/// Requests answered with a JSON-RPC error are passed through without being recorded.
+pub struct RecordingTransport<T> {
+    inner: T,
+    recording: Recording,
+}
+
+impl<T> RecordingTransport<T> {
+    pub fn new(inner: T) -> Self {
+        Self { inner, recording: Recording::default() }
+    }
+
+    /// Returns a handle on the exchanges recorded by this transport, which remains usable once
+    /// the transport is moved into a provider.
+    pub fn recording(&self) -> Recording {
+        self.recording.clone()
+    }
+}
+
+/// The exchanges recorded by a [`RecordingTransport`].
+#[derive(Debug, Clone, Default)]
+pub struct Recording(Arc<Mutex<Vec<RecordedExchange>>>);
+
+impl Recording {
+    /// Returns the exchanges recorded so far, in order.
+    pub fn exchanges(&self) -> Vec<RecordedExchange> {
+        self.0.lock().expect("recording lock poisoned").clone()
+    }
+
+    /// Writes the exchanges recorded so far to a JSON fixture file.
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let exchanges = serde_json::to_vec_pretty(&self.exchanges())?;
+        fs::write(path, exchanges)
+    }
+}
+
+#[async_trait]
+impl<T> JsonRpcTransport for RecordingTransport<T>
+where
+    T: JsonRpcTransport + Send + Sync,
+{
+    type Error = RecordingTransportError<T::Error>;
+
+    async fn send_request<P, R>(&self, method: JsonRpcMethod, params: P) -> Result<JsonRpcResponse<R>, Self::Error>
+    where
+        P: Serialize + Send,
+        R: DeserializeOwned,
+    {
+        let method_name = serde_json::to_value(&method)?;
+        let params = serde_json::to_value(params)?;
+
+        let response = self
+            .inner
+            .send_request::<_, Value>(method, params.clone())
+            .await
+            .map_err(RecordingTransportError::Transport)?;
+
+        match response {
+            JsonRpcResponse::Success { id, result } => {
+                let exchange = RecordedExchange { method: method_name, params, result: result.clone() };
+                self.recording.0.lock().expect("recording lock poisoned").push(exchange);
+                Ok(JsonRpcResponse::Success { id, result: serde_json::from_value(result)? })
+            }
+            JsonRpcResponse::Error { id, error } => Ok(JsonRpcResponse::Error { id, error }),
+        }
+    }
+}
+
+/// Reads the exchanges saved by [`Recording::save`].
+pub fn load_exchanges(path: impl AsRef<Path>) -> std::io::Result<Vec<RecordedExchange>> {
+    let exchanges = fs::read(path)?;
+    Ok(serde_json::from_slice(&exchanges)?)
+}
+
+/// Mounts the exchanges on a wiremock server, each request being answered with its recorded
+/// result.
+pub async fn mount_exchanges(mock_server: &MockServer, exchanges: &[RecordedExchange]) {
+    for exchange in exchanges {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": exchange.method, "params": exchange.params })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": exchange.result,
+            })))
+            .mount(mock_server)
+            .await;
+    }
+}
@@ -3,14 +3,17 @@ use std::str::FromStr;
 use reqwest::StatusCode;
 use reth_primitives::{BlockId, H256};
 use serde::{Deserialize, Serialize};
-use starknet::core::types::{BlockId as StarknetBlockId, BlockTag, FieldElement};
+use starknet::core::types::{BlockId as StarknetBlockId, BlockTag, FieldElement, FunctionCall};
+use starknet::macros::felt;
 use starknet::providers::jsonrpc::HttpTransport;
 use starknet::providers::JsonRpcClient;
-use wiremock::matchers::{body_json, method};
-use wiremock::{Mock, MockServer, ResponseTemplate};
+use wiremock::matchers::{body_json, body_partial_json, method};
+use wiremock::{Match, Mock, MockServer, Request, ResponseTemplate};
 
 use crate::client::client_api::KakarotProvider;
 use crate::client::config::StarknetConfig;
+use crate::client::constants::selectors::{BALANCE_OF, COMPUTE_STARKNET_ADDRESS, GET_EVM_ADDRESS};
+use crate::client::constants::STARKNET_NATIVE_TOKEN;
 use crate::client::helpers::ethers_block_id_to_starknet_block_id;
 use crate::client::KakarotClient;
 
@@ -22,6 +25,50 @@ pub struct StarknetRpcBaseData<'a, StarknetParams> {
     params: StarknetParams,
 }
 
+/// Matches a Starknet JSON-RPC request whatever the block id it targets, so that a single mock
+/// serves state reads pinned to any block, unless restricted to a block with
+/// [`StarknetRpcAtAnyBlock::only_at`].
+pub struct StarknetRpcAtAnyBlock {
+    method: &'static str,
+    params: Vec<serde_json::Value>,
+    block_id_index: usize,
+    block_id: Option<serde_json::Value>,
+}
+
+impl StarknetRpcAtAnyBlock {
+    /// `params` are the expected request params without the block id, which is expected at
+    /// `block_id_index`.
+    pub fn new(method: &'static str, params: Vec<serde_json::Value>, block_id_index: usize) -> Self {
+        Self { method, params, block_id_index, block_id: None }
+    }
+
+    /// Only matches the requests targeting `starknet_block_id`, to check that a read is pinned to
+    /// this block.
+    #[must_use]
+    pub fn only_at(self, starknet_block_id: StarknetBlockId) -> Self {
+        Self { block_id: Some(serde_json::to_value(starknet_block_id).unwrap()), ..self }
+    }
+}
+
+impl Match for StarknetRpcAtAnyBlock {
+    fn matches(&self, request: &Request) -> bool {
+        let body = match serde_json::from_slice::<serde_json::Value>(&request.body) {
+            Ok(body) => body,
+            Err(_) => return false,
+        };
+        let mut params = match body.get("params").and_then(|params| params.as_array()) {
+            Some(params) if params.len() == self.params.len() + 1 => params.clone(),
+            _ => return false,
+        };
+        let block_id = params.remove(self.block_id_index);
+        if self.block_id.as_ref().map_or(false, |expected| *expected != block_id) {
+            return false;
+        }
+
+        body.get("method").and_then(|method| method.as_str()) == Some(self.method) && params == self.params
+    }
+}
+
 #[derive(Deserialize, Debug)]
 pub struct EthJsonRpcResponse<StarknetParams> {
     pub id: usize,
@@ -73,12 +120,37 @@ impl<'a, StarknetParams> StarknetRpcBaseData<'a, StarknetParams> {
     }
 }
 
+/// The address of the Kakarot contract of the fixtures.
+pub const KAKAROT_ADDRESS: FieldElement = felt!("0x566864dbc2ae76c2d12a8a5a334913d0806f85b7a4dccea87467c3ba3616e75");
+
+/// The class hash of the proxy accounts of the fixtures.
+pub const PROXY_ACCOUNT_CLASS_HASH: FieldElement =
+    felt!("0x0775033b738dfe34c48f43a839c3d882ebe521befb3447240f2d218f14816ef5");
+
+/// The Starknet chain id of the fixtures, `SN_GOERLI`.
+pub const STARKNET_CHAIN_ID: FieldElement = felt!("0x534e5f474f45524c49");
+
+/// Returns the configuration of a client of the Kakarot deployment of the fixtures, served by
+/// `starknet_rpc`.
+#[must_use]
+pub fn mock_starknet_config(starknet_rpc: &str) -> StarknetConfig {
+    StarknetConfig::new(starknet_rpc, KAKAROT_ADDRESS, PROXY_ACCOUNT_CLASS_HASH)
+}
+
 pub async fn setup_wiremock() -> String {
+    setup_mock_server().await.uri()
+}
+
+/// Starts a mock Starknet node serving the fixtures. Tests mount their own mocks on it, with a
+/// higher priority than the fixtures when they override them.
+pub async fn setup_mock_server() -> MockServer {
     let mock_server = MockServer::start().await;
 
     mock_block_number().mount(&mock_server).await;
 
-    mock_block_with_txs().mount(&mock_server).await;
+    for mock in mock_block_with_txs() {
+        mock.mount(&mock_server).await;
+    }
 
     mock_block_with_txs_hashes().mount(&mock_server).await;
 
@@ -104,6 +176,15 @@ pub async fn setup_wiremock() -> String {
     // * test_transaction_receipt_invoke_is_ok
     mock_transaction_receipt_invoke().mount(&mock_server).await;
 
+    // block of the receipt, for the position of the transaction in the block
+    for mock in mock_block_of_receipt() {
+        mock.mount(&mock_server).await;
+    }
+
+    for mock in mock_block_receipts() {
+        mock.mount(&mock_server).await;
+    }
+
     mock_transaction_by_hash().mount(&mock_server).await;
 
     mock_get_code().mount(&mock_server).await;
@@ -112,19 +193,22 @@ pub async fn setup_wiremock() -> String {
 
     mock_get_class_hash_at().mount(&mock_server).await;
 
+    // events of the block with transaction hashes, for its logs bloom
+    let block_id =
+        serde_json::json!({ "block_hash": "0x197be2810df6b5eedd5d9e468b200d0b845b642b81a44755e19047f08cc8c6e" });
+    mock_events(serde_json::json!({ "from_block": block_id, "to_block": block_id }), serde_json::json!([]))
+        .mount(&mock_server)
+        .await;
+
     // Get kakarot contract bytecode
     // TODO: Use the latest mapping between starknet and EVM addresses
 
-    mock_server.uri()
+    mock_server
 }
 
 pub async fn setup_mock_client() -> Box<dyn KakarotProvider> {
     let starknet_rpc = setup_wiremock().await;
-    let kakarot_address =
-        FieldElement::from_hex_be("0x566864dbc2ae76c2d12a8a5a334913d0806f85b7a4dccea87467c3ba3616e75").unwrap();
-    let proxy_account_class_hash =
-        FieldElement::from_hex_be("0x0775033b738dfe34c48f43a839c3d882ebe521befb3447240f2d218f14816ef5").unwrap();
-    Box::new(KakarotClient::new(StarknetConfig::new(&starknet_rpc, kakarot_address, proxy_account_class_hash)).unwrap())
+    Box::new(KakarotClient::new(mock_starknet_config(&starknet_rpc)).unwrap())
 }
 
 pub async fn setup_mock_client_crate() -> KakarotClient<JsonRpcClient<HttpTransport>>
@@ -132,12 +216,246 @@ where
     KakarotClient<JsonRpcClient<HttpTransport>>: KakarotProvider,
 {
     let starknet_rpc = setup_wiremock().await;
-    let kakarot_address =
-        FieldElement::from_hex_be("0x566864dbc2ae76c2d12a8a5a334913d0806f85b7a4dccea87467c3ba3616e75").unwrap();
-    let proxy_account_class_hash =
-        FieldElement::from_hex_be("0x0775033b738dfe34c48f43a839c3d882ebe521befb3447240f2d218f14816ef5").unwrap();
+    KakarotClient::new(mock_starknet_config(&starknet_rpc)).unwrap()
+}
+
+/// A raw EIP-1559 transaction of the fixtures chain, calling `0x2e11...f4d1`, signed by
+/// [`SENDER_ADDRESS`].
+pub const RAW_TRANSACTION: &str = "0x02f872844b4b525482dead82dead82dead843b9aca00942e11ed82f5ec165ab8ce3cc094f025fe7527f4d18084b3bcfa82c001a0889be67d59bc1a43dd803955f7917ddcb7d748ed3e9b00cdb159f294651976b8a03801702a606ffbfd60364ff897f7ca511411d6660f936dd51eb90a7d30735261";
+
+/// The signer of [`RAW_TRANSACTION`].
+pub const SENDER_ADDRESS: &str = "0x54b288676b749def5fc10eb17244fe2c87375de1";
+
+/// The Starknet contract account of [`SENDER_ADDRESS`].
+pub const SENDER_STARKNET_ADDRESS: FieldElement =
+    felt!("0x744ed080b42c8883a7e31cd11a14b7ae9ef27698b785486bb75cd116c8f1485");
+
+/// Mocks `compute_starknet_address` of the Kakarot contract of the fixtures, at any block,
+/// returning [`SENDER_STARKNET_ADDRESS`] for [`SENDER_ADDRESS`].
+#[must_use]
+pub fn mock_compute_starknet_address() -> Mock {
+    mock_call_result(compute_starknet_address_request(), vec![SENDER_STARKNET_ADDRESS])
+}
+
+/// Mocks `compute_starknet_address` like [`mock_compute_starknet_address`], only at
+/// `starknet_block_id`.
+#[must_use]
+pub fn mock_compute_starknet_address_at(starknet_block_id: StarknetBlockId) -> Mock {
+    mock_call_result(compute_starknet_address_request().only_at(starknet_block_id), vec![SENDER_STARKNET_ADDRESS])
+}
+
+/// Mocks the native token balance of the account `starknet_address`, only at
+/// `starknet_block_id`.
+#[must_use]
+pub fn mock_native_balance_at(
+    starknet_address: FieldElement,
+    balance: FieldElement,
+    starknet_block_id: StarknetBlockId,
+) -> Mock {
+    let request = FunctionCall {
+        contract_address: FieldElement::from_hex_be(STARKNET_NATIVE_TOKEN).unwrap(),
+        entry_point_selector: BALANCE_OF,
+        calldata: vec![starknet_address],
+    };
+    let request = StarknetRpcAtAnyBlock::new("starknet_call", vec![serde_json::to_value(request).unwrap()], 1);
+    // The balance is a (low, high) uint256
+    mock_call_result(request.only_at(starknet_block_id), vec![balance, FieldElement::ZERO])
+}
 
-    KakarotClient::new(StarknetConfig::new(&starknet_rpc, kakarot_address, proxy_account_class_hash)).unwrap()
+fn compute_starknet_address_request() -> StarknetRpcAtAnyBlock {
+    let request = FunctionCall {
+        contract_address: KAKAROT_ADDRESS,
+        entry_point_selector: COMPUTE_STARKNET_ADDRESS,
+        calldata: vec![FieldElement::from_hex_be(SENDER_ADDRESS).unwrap()],
+    };
+    StarknetRpcAtAnyBlock::new("starknet_call", vec![serde_json::to_value(request).unwrap()], 1)
+}
+
+fn mock_call_result(request: StarknetRpcAtAnyBlock, result: Vec<FieldElement>) -> Mock {
+    let result: Vec<String> = result.iter().map(|felt| format!("{felt:#x}")).collect();
+    Mock::given(method("POST")).and(request).respond_with(response_template_with_status(StatusCode::OK).set_body_json(
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": result
+        }),
+    ))
+}
+
+/// Mocks `starknet_addInvokeTransaction` for the invoke transactions including `transaction`,
+/// returning `transaction_hash`.
+#[must_use]
+pub fn mock_add_invoke_transaction(transaction: serde_json::Value, transaction_hash: FieldElement) -> Mock {
+    Mock::given(method("POST"))
+        .and(body_partial_json(serde_json::json!({
+            "method": "starknet_addInvokeTransaction",
+            "params": [transaction]
+        })))
+        .respond_with(response_template_with_status(StatusCode::OK).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "transaction_hash": format!("{transaction_hash:#x}") }
+        })))
+}
+
+/// Returns the first transaction of the fixtures block, a Kakarot transaction sent by
+/// [`SENDER_STARKNET_ADDRESS`].
+#[must_use]
+pub fn fixture_transaction() -> serde_json::Value {
+    let block: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/responses/blocks/starknet_getBlockWithTxs.json")).unwrap();
+    block["result"]["transactions"][0].clone()
+}
+
+/// Mocks `starknet_getBlockWithTxs` and `starknet_getBlockWithTxHashes` for the block
+/// `starknet_block_id`: the fixtures block, holding `transactions` instead of its own, with the
+/// hash or number of the block id.
+#[must_use]
+pub fn mock_block_with_transactions(
+    starknet_block_id: StarknetBlockId,
+    transactions: Vec<serde_json::Value>,
+) -> Vec<Mock> {
+    let mut block: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/responses/blocks/starknet_getBlockWithTxs.json")).unwrap();
+    match starknet_block_id {
+        StarknetBlockId::Hash(block_hash) => {
+            block["result"]["block_hash"] = serde_json::json!(format!("{block_hash:#x}"))
+        }
+        StarknetBlockId::Number(number) => block["result"]["block_number"] = serde_json::json!(number),
+        StarknetBlockId::Tag(_) => {}
+    }
+    block["result"]["transactions"] = serde_json::Value::Array(transactions);
+    mock_block(starknet_block_id, block)
+}
+
+/// Mocks `starknet_getBlockWithTxs` for the block `starknet_block_id` returning `block`,
+/// `starknet_getBlockWithTxHashes` returning it with the hashes of its transactions, and
+/// `starknet_getEvents` returning no events for the block if it is identified by its hash.
+fn mock_block(starknet_block_id: StarknetBlockId, block: serde_json::Value) -> Vec<Mock> {
+    let mut block_with_tx_hashes = block.clone();
+    block_with_tx_hashes["result"]["transactions"] = block["result"]["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|transaction| transaction["transaction_hash"].clone())
+        .collect();
+    let mut mocks = vec![
+        Mock::given(method("POST"))
+            .and(body_json(StarknetRpcBaseData::block_with_txs([&starknet_block_id])))
+            .respond_with(response_template_with_status(StatusCode::OK).set_body_json(block)),
+        Mock::given(method("POST"))
+            .and(body_json(StarknetRpcBaseData::block_with_tx_hashes([&starknet_block_id])))
+            .respond_with(response_template_with_status(StatusCode::OK).set_body_json(block_with_tx_hashes)),
+    ];
+    if let StarknetBlockId::Hash(block_hash) = starknet_block_id {
+        let block_id = serde_json::json!({ "block_hash": format!("{block_hash:#x}") });
+        mocks.push(mock_events(
+            serde_json::json!({ "from_block": block_id, "to_block": block_id }),
+            serde_json::json!([]),
+        ));
+    }
+    mocks
+}
+
+/// Mocks `starknet_getBlockWithTxs` for the pending block: the fixtures pending block, holding
+/// `transactions` instead of its own. Overrides the pending block of [`setup_mock_server`].
+#[must_use]
+pub fn mock_pending_block(transactions: Vec<serde_json::Value>) -> Mock {
+    let mut block: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/responses/blocks/pending/starknet_getBlockWithTxs_pending.json"))
+            .unwrap();
+    block["result"]["transactions"] = serde_json::Value::Array(transactions);
+    let pending_block = StarknetBlockId::Tag(BlockTag::Pending);
+    Mock::given(method("POST"))
+        .and(body_json(StarknetRpcBaseData::block_with_txs([&pending_block])))
+        .respond_with(response_template_with_status(StatusCode::OK).set_body_json(block))
+        .with_priority(1)
+}
+
+/// Mocks `starknet_getBlockWithTxs` and `starknet_getBlockWithTxHashes` for the block `number` of
+/// a chain, by number and by hash: an empty block with hash `block_hash` and parent `parent_hash`.
+#[must_use]
+pub fn mock_chain_block(number: u64, block_hash: FieldElement, parent_hash: FieldElement) -> Vec<Mock> {
+    let mut block: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/responses/blocks/starknet_getBlockWithTxHashes.json")).unwrap();
+    block["result"]["block_number"] = serde_json::json!(number);
+    block["result"]["block_hash"] = serde_json::json!(format!("{block_hash:#x}"));
+    block["result"]["parent_hash"] = serde_json::json!(format!("{parent_hash:#x}"));
+    block["result"]["timestamp"] = serde_json::json!(1_675_496_282 + number);
+    block["result"]["transactions"] = serde_json::json!([]);
+    // The block is also read by hash, for the positions of its transactions
+    let mut mocks = mock_block(StarknetBlockId::Number(number), block.clone());
+    mocks.extend(mock_block(StarknetBlockId::Hash(block_hash), block));
+    mocks
+}
+
+/// Mocks `starknet_getEvents` for the event filters including `filter`, returning `events` in a
+/// single page.
+#[must_use]
+pub fn mock_events(filter: serde_json::Value, events: serde_json::Value) -> Mock {
+    Mock::given(method("POST"))
+        .and(body_partial_json(serde_json::json!({ "method": "starknet_getEvents", "params": [filter] })))
+        .respond_with(response_template_with_status(StatusCode::OK).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": { "events": events, "continuation_token": null },
+        })))
+}
+
+/// Mocks the Starknet JSON-RPC method `starknet_method`, failing with the Starknet error `code`.
+#[must_use]
+pub fn mock_starknet_error(starknet_method: &str, code: i64, message: &str) -> Mock {
+    Mock::given(method("POST")).and(body_partial_json(serde_json::json!({ "method": starknet_method }))).respond_with(
+        response_template_with_status(StatusCode::OK).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "error": { "code": code, "message": message }
+        })),
+    )
+}
+
+/// Mocks `get_evm_address` of the Kakarot account `starknet_address`, at any block, returning
+/// `evm_address`.
+#[must_use]
+pub fn mock_evm_address_of(starknet_address: FieldElement, evm_address: FieldElement) -> Mock {
+    mock_evm_address(evm_address_request(starknet_address), evm_address)
+}
+
+/// Mocks `get_evm_address` of the Kakarot account `starknet_address`, at `starknet_block_id`
+/// only, returning `evm_address`.
+#[must_use]
+pub fn mock_evm_address_of_at(
+    starknet_address: FieldElement,
+    evm_address: FieldElement,
+    starknet_block_id: StarknetBlockId,
+) -> Mock {
+    mock_evm_address(evm_address_request(starknet_address).only_at(starknet_block_id), evm_address)
+}
+
+fn evm_address_request(starknet_address: FieldElement) -> StarknetRpcAtAnyBlock {
+    let request =
+        FunctionCall { contract_address: starknet_address, entry_point_selector: GET_EVM_ADDRESS, calldata: vec![] };
+    StarknetRpcAtAnyBlock::new("starknet_call", vec![serde_json::to_value(request).unwrap()], 1)
+}
+
+fn mock_evm_address(request: StarknetRpcAtAnyBlock, evm_address: FieldElement) -> Mock {
+    mock_call_result(request, vec![evm_address])
+}
+
+/// Mocks `starknet_getClassHashAt` for `contract_address`, at any block, returning `class_hash`.
+#[must_use]
+pub fn mock_class_hash_at(contract_address: FieldElement, class_hash: FieldElement) -> Mock {
+    Mock::given(method("POST"))
+        .and(StarknetRpcAtAnyBlock::new(
+            "starknet_getClassHashAt",
+            vec![serde_json::json!(format!("{contract_address:#x}"))],
+            0,
+        ))
+        .respond_with(response_template_with_status(StatusCode::OK).set_body_json(serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": format!("{class_hash:#x}")
+        })))
 }
 
 fn mock_block_number() -> Mock {
@@ -147,15 +465,19 @@ fn mock_block_number() -> Mock {
     )
 }
 
-fn mock_block_with_txs() -> Mock {
+fn mock_block_with_txs() -> Vec<Mock> {
     let block_id = BlockId::Hash(
         H256::from_str("0x0449aa33ad836b65b10fa60082de99e24ac876ee2fd93e723a99190a530af0a9").unwrap().into(),
     );
     let starknet_block_id = ethers_block_id_to_starknet_block_id(block_id).unwrap();
-    Mock::given(method("POST")).and(body_json(StarknetRpcBaseData::block_with_txs([&starknet_block_id]))).respond_with(
-        response_template_with_status(StatusCode::OK)
-            .set_body_raw(include_str!("fixtures/responses/blocks/starknet_getBlockWithTxs.json"), "application/json"),
-    )
+    let block = serde_json::from_str(include_str!("fixtures/responses/blocks/starknet_getBlockWithTxs.json")).unwrap();
+    mock_block(starknet_block_id, block)
+}
+
+fn mock_block_of_receipt() -> Vec<Mock> {
+    let starknet_block_id = StarknetBlockId::Hash(FieldElement::from_hex_be("0x0d").unwrap());
+    let block = serde_json::from_str(include_str!("fixtures/responses/blocks/starknet_getBlockWithTxs.json")).unwrap();
+    mock_block(starknet_block_id, block)
 }
 
 fn mock_block_with_txs_hashes() -> Mock {
@@ -246,6 +568,33 @@ fn mock_transaction_receipt_for_transaction_by_block_hash_and_index() -> Mock {
         ))
 }
 
+/// Mocks the receipts of the transactions of the fixtures block, other than the first one which
+/// has its own fixture, as receipts emitting no event.
+fn mock_block_receipts() -> Vec<Mock> {
+    let block: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/responses/blocks/starknet_getBlockWithTxs.json")).unwrap();
+    let mut receipt: serde_json::Value =
+        serde_json::from_str(include_str!("fixtures/responses/transactions/starknet_getTransactionReceipt.json"))
+            .unwrap();
+    receipt["result"]["events"] = serde_json::json!([]);
+
+    block["result"]["transactions"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .skip(1)
+        .map(|transaction| {
+            let transaction_hash =
+                FieldElement::from_hex_be(transaction["transaction_hash"].as_str().unwrap()).unwrap();
+            let mut receipt = receipt.clone();
+            receipt["result"]["transaction_hash"] = serde_json::json!(format!("{transaction_hash:#x}"));
+            Mock::given(method("POST"))
+                .and(body_json(StarknetRpcBaseData::transaction_receipt([format!("{transaction_hash:#x}")])))
+                .respond_with(response_template_with_status(StatusCode::OK).set_body_json(receipt))
+        })
+        .collect()
+}
+
 fn mock_transaction_receipt_invoke() -> Mock {
     Mock::given(method("POST"))
         .and(body_json(StarknetRpcBaseData::transaction_receipt([
@@ -287,17 +636,13 @@ fn mock_get_code() -> Mock {
 }
 
 fn mock_get_evm_address() -> Mock {
-    let latest_block = StarknetBlockId::Tag(BlockTag::Latest);
     let get_evm_address_call_request = serde_json::json!({
         "contract_address": "0x744ed080b42c8883a7e31cd11a14b7ae9ef27698b785486bb75cd116c8f1485",
         "entry_point_selector": "0x158359fe4236681f6236a2f303f9350495f73f078c9afd1ca0890fa4143c2ed",
         "calldata": [],
     });
     Mock::given(method("POST"))
-        .and(body_json(StarknetRpcBaseData::call([
-            serde_json::to_value(get_evm_address_call_request).unwrap(),
-            serde_json::to_value(latest_block).unwrap(),
-        ])))
+        .and(StarknetRpcAtAnyBlock::new("starknet_call", vec![get_evm_address_call_request], 1))
         .respond_with(
             response_template_with_status(StatusCode::OK)
                 .set_body_raw(include_str!("fixtures/responses/kakarot_getEvmAddress.json"), "application/json"),
@@ -305,12 +650,12 @@ fn mock_get_evm_address() -> Mock {
 }
 
 fn mock_get_class_hash_at() -> Mock {
-    let latest_block = StarknetBlockId::Tag(BlockTag::Latest);
     Mock::given(method("POST"))
-        .and(body_json(StarknetRpcBaseData::class_hash_at([
-            serde_json::to_value(latest_block).unwrap(),
-            serde_json::to_value("0x744ed080b42c8883a7e31cd11a14b7ae9ef27698b785486bb75cd116c8f1485").unwrap(),
-        ])))
+        .and(StarknetRpcAtAnyBlock::new(
+            "starknet_getClassHashAt",
+            vec![serde_json::to_value("0x744ed080b42c8883a7e31cd11a14b7ae9ef27698b785486bb75cd116c8f1485").unwrap()],
+            0,
+        ))
         .respond_with(response_template_with_status(StatusCode::OK).set_body_raw(
             include_str!("fixtures/responses/transactions/starknet_getClassHashAt.json"),
             "application/json",
@@ -0,0 +1,38 @@
+use reth_primitives::{Address, H256, U64};
+use serde::{Deserialize, Serialize};
+
+use crate::client::events::ClientEvent;
+
+/// A transaction touching a watched address, i.e. sent from or to it, deploying it, or emitting
+/// a log from or about it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressActivity {
+    pub address: Address,
+    pub block_hash: H256,
+    pub block_number: U64,
+    pub transaction_hash: H256,
+}
+
+/// A change of the activity of the watched addresses, as pushed to the webhooks and to the
+/// `admin_subscribeAddressActivity` subscribers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "activity", rename_all = "camelCase")]
+pub enum WatchlistEvent {
+    /// A transaction of a new block touched a watched address.
+    Added(AddressActivity),
+    /// The block of an activity pushed before was replaced or dropped by a reorg.
+    Removed(AddressActivity),
+}
+
+impl WatchlistEvent {
+    /// Returns the change of the activity of the watched addresses broadcast by a client event,
+    /// if any.
+    #[must_use]
+    pub fn from_client_event(event: ClientEvent) -> Option<Self> {
+        match event {
+            ClientEvent::AddressActivity(activity) => Some(WatchlistEvent::Added(activity)),
+            ClientEvent::AddressActivityReverted(activity) => Some(WatchlistEvent::Removed(activity)),
+            _ => None,
+        }
+    }
+}
@@ -13,3 +13,12 @@ pub struct TokenBalances {
     pub address: Address,
     pub token_balances: Vec<TokenBalance>,
 }
+
+/// The native token balance change of an account in a transaction, including the value
+/// transferred by its internal calls and the fee paid.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BalanceChange {
+    pub address: Address,
+    pub received: U256,
+    pub sent: U256,
+}
@@ -1,13 +1,18 @@
 use async_trait::async_trait;
-use reth_primitives::{Bloom, Bytes, H256, H64, U256};
+use reth_primitives::{Bytes, H256, H64, U256};
 use reth_rpc_types::{Block, BlockTransactions, Header, RichBlock};
-use starknet::core::types::{FieldElement, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs, Transaction};
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{
+    BlockId as StarknetBlockId, BlockTag, FieldElement, MaybePendingBlockWithTxHashes, MaybePendingBlockWithTxs,
+    Transaction,
+};
 
 use super::convertible::ConvertibleStarknetBlock;
+use super::metadata::{kakarot_metadata_requested, KAKAROT_METADATA_KEY};
 use crate::client::client_api::KakarotProvider;
 use crate::client::constants::{DIFFICULTY, GAS_LIMIT, GAS_USED, MIX_HASH, NONCE, SIZE, TOTAL_DIFFICULTY};
 use crate::client::errors::EthApiError;
-use crate::client::helpers::starknet_address_to_ethereum_address;
+use crate::client::helpers::starknet_address_to_ethereum_address_with_policy;
 
 /// Implement getters for fields that are present in Starknet Blocks, both in pending and validated
 /// state. For example, `parent_hash` is present in both `PendingBlock` and `Block`.
@@ -43,6 +48,39 @@ macro_rules! implement_starknet_block_getters_not_pending {
     };
 }
 
+/// Key under which the Starknet block information is added to EVM block responses, when enabled.
+pub const STARKNET_BLOCK_INFO_KEY: &str = "starknet";
+
+/// The Starknet block underlying an EVM block, exposed so that explorers can link both
+/// representations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StarknetBlockInfo {
+    /// `None` for the pending block.
+    pub block_hash: Option<FieldElement>,
+    /// `None` for the pending block.
+    pub block_number: Option<u64>,
+    pub parent_hash: FieldElement,
+    pub sequencer_address: FieldElement,
+    pub timestamp: u64,
+}
+
+/// Adds the Starknet block information to an EVM block response, under `starknet` if the client
+/// is configured to, else as the Kakarot metadata of the block if the request asked for it.
+fn with_starknet_block_info(block: Block, info: StarknetBlockInfo, client: &dyn KakarotProvider) -> RichBlock {
+    let mut block: RichBlock = block.into();
+    let key = if client.expose_starknet_block_info() {
+        STARKNET_BLOCK_INFO_KEY
+    } else if kakarot_metadata_requested() {
+        KAKAROT_METADATA_KEY
+    } else {
+        return block;
+    };
+    if let Ok(info) = serde_json::to_value(info) {
+        block.extra_info.insert(key.to_string(), info);
+    }
+    block
+}
+
 pub struct BlockWithTxHashes(MaybePendingBlockWithTxHashes);
 
 impl BlockWithTxHashes {
@@ -61,6 +99,16 @@ impl BlockWithTxHashes {
         (MaybePendingBlockWithTxHashes, block_hash, FieldElement),
         (MaybePendingBlockWithTxHashes, block_number, u64)
     );
+
+    pub fn starknet_block_info(&self) -> StarknetBlockInfo {
+        StarknetBlockInfo {
+            block_hash: self.block_hash(),
+            block_number: self.block_number(),
+            parent_hash: self.parent_hash(),
+            sequencer_address: self.sequencer_address(),
+            timestamp: self.timestamp(),
+        }
+    }
 }
 
 pub struct BlockWithTxs(MaybePendingBlockWithTxs);
@@ -81,6 +129,16 @@ impl BlockWithTxs {
         (MaybePendingBlockWithTxs, block_hash, FieldElement),
         (MaybePendingBlockWithTxs, block_number, u64)
     );
+
+    pub fn starknet_block_info(&self) -> StarknetBlockInfo {
+        StarknetBlockInfo {
+            block_hash: self.block_hash(),
+            block_number: self.block_number(),
+            parent_hash: self.parent_hash(),
+            sequencer_address: self.sequencer_address(),
+            timestamp: self.timestamp(),
+        }
+    }
 }
 
 #[async_trait]
@@ -101,8 +159,11 @@ impl ConvertibleStarknetBlock for BlockWithTxHashes {
         // TODO: Fetch real data
         let size: Option<U256> = *SIZE;
 
-        // Bloom is a byte array of length 256
-        let logs_bloom = Bloom::default();
+        let block_id = match self.block_hash() {
+            Some(hash) => StarknetBlockId::Hash(hash),
+            None => StarknetBlockId::Tag(BlockTag::Pending),
+        };
+        let logs_bloom = client.block_logs_bloom(block_id).await?;
         let extra_data = Bytes::from(b"0x00");
 
         // TODO: Fetch real data
@@ -111,7 +172,9 @@ impl ConvertibleStarknetBlock for BlockWithTxHashes {
         let mix_hash = *MIX_HASH;
 
         let parent_hash = H256::from_slice(&self.parent_hash().to_bytes_be());
-        let sequencer = starknet_address_to_ethereum_address(&self.sequencer_address());
+        let sequencer_address = self.sequencer_address();
+        let sequencer =
+            starknet_address_to_ethereum_address_with_policy(&sequencer_address, client.address_conversion_policy())?;
         let timestamp = U256::from(self.timestamp());
 
         let hash = self.block_hash().as_ref().map(|hash| H256::from_slice(&hash.to_bytes_be()));
@@ -155,7 +218,7 @@ impl ConvertibleStarknetBlock for BlockWithTxHashes {
             size,
             withdrawals: Some(vec![]),
         };
-        Ok(block.into())
+        Ok(with_starknet_block_info(block, self.starknet_block_info(), client))
     }
 }
 
@@ -177,8 +240,11 @@ impl ConvertibleStarknetBlock for BlockWithTxs {
         // TODO: Fetch real data
         let size: Option<U256> = *SIZE;
 
-        // Bloom is a byte array of length 256
-        let logs_bloom = Bloom::default();
+        let block_id = match self.block_hash() {
+            Some(hash) => StarknetBlockId::Hash(hash),
+            None => StarknetBlockId::Tag(BlockTag::Pending),
+        };
+        let logs_bloom = client.block_logs_bloom(block_id).await?;
         let extra_data: Bytes = Bytes::from(b"0x00");
 
         // TODO: Fetch real data
@@ -188,7 +254,9 @@ impl ConvertibleStarknetBlock for BlockWithTxs {
 
         let parent_hash = H256::from_slice(&self.parent_hash().to_bytes_be());
 
-        let sequencer = starknet_address_to_ethereum_address(&self.sequencer_address());
+        let sequencer_address = self.sequencer_address();
+        let sequencer =
+            starknet_address_to_ethereum_address_with_policy(&sequencer_address, client.address_conversion_policy())?;
 
         let timestamp = U256::from(self.timestamp());
 
@@ -229,6 +297,6 @@ impl ConvertibleStarknetBlock for BlockWithTxs {
             size,
             withdrawals: Some(vec![]),
         };
-        Ok(block.into())
+        Ok(with_starknet_block_info(block, self.starknet_block_info(), client))
     }
 }
@@ -0,0 +1,24 @@
+use reth_primitives::U64;
+use serde::{Deserialize, Serialize};
+
+/// The native currency of a chain, as displayed by wallets.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NativeCurrency {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// The parameters of `wallet_addEthereumChain` (EIP-3085) for the Kakarot deployment, so that
+/// dapps can offer to add it to wallets with the data served by the adapter.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainMetadata {
+    pub chain_id: U64,
+    pub chain_name: String,
+    pub rpc_urls: Vec<String>,
+    pub native_currency: NativeCurrency,
+    /// Omitted when no block explorer is configured, as EIP-3085 rejects empty lists.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub block_explorer_urls: Option<Vec<String>>,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use starknet::core::types::TransactionStatus;
+
+/// Where a transaction stands on its way to settlement on L1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum L1SettlementStatus {
+    /// In the pending block.
+    Pending,
+    /// Accepted by the sequencer, not yet proven on L1.
+    AcceptedOnL2,
+    /// Its block is proven on L1.
+    AcceptedOnL1,
+    Rejected,
+}
+
+impl From<TransactionStatus> for L1SettlementStatus {
+    fn from(status: TransactionStatus) -> Self {
+        match status {
+            TransactionStatus::Pending => Self::Pending,
+            TransactionStatus::AcceptedOnL2 => Self::AcceptedOnL2,
+            TransactionStatus::AcceptedOnL1 => Self::AcceptedOnL1,
+            TransactionStatus::Rejected => Self::Rejected,
+        }
+    }
+}
+
+/// The L1 settlement of a transaction, for bridges and custodians waiting on L1 finality.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct L1Confirmation {
+    pub status: L1SettlementStatus,
+    /// The Starknet block of the transaction, `None` while pending.
+    pub block_number: Option<u64>,
+    /// The last Starknet block accepted on L1.
+    pub last_l1_accepted_block: u64,
+}
@@ -1,24 +1,42 @@
-use core::iter::once;
-
 use async_trait::async_trait;
-use num_bigint::BigUint;
-use reth_primitives::{Address, Bytes, H256, U256};
+use reth_primitives::{Address, H256, U256};
 use reth_rpc_types::Log;
-use starknet::core::types::Event;
+use serde::{Deserialize, Serialize};
+use starknet::core::types::{EmittedEvent, Event};
 
 use super::felt::Felt252Wrapper;
 use crate::client::client_api::KakarotProvider;
+use crate::client::constants::events::MAX_LOG_TOPICS;
 use crate::client::errors::EthApiError;
+use crate::client::helpers::{topic_from_low_high, vec_felt_to_bytes};
 use crate::models::convertible::ConvertibleStarknetEvent;
 
 pub struct StarknetEvent(Event);
 
+/// A Kakarot event as emitted on Starknet, along with its conversion to an EVM log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawKakarotEvent {
+    pub event: EmittedEvent,
+    pub log: Option<Log>,
+    pub error: Option<String>,
+}
+
 impl StarknetEvent {
     pub fn new(sn_event: Event) -> Self {
         Self(sn_event)
     }
 }
 
+/// Decodes the EVM log topics from the keys of a Kakarot event.
+///
+/// Each topic is split into a (low, high) pair of keys, the Uint256 Cairo representation of the
+/// bytes32 topic. Keys which do not form a complete pair, or pairs beyond the EVM limit of topics,
+/// are metadata appended by newer Kakarot versions and are not part of the EVM log.
+#[must_use]
+pub fn topics_from_keys(keys: &[FieldElement]) -> Vec<H256> {
+    keys.chunks_exact(2).take(MAX_LOG_TOPICS).map(|chunk| topic_from_low_high(&chunk[0], &chunk[1])).collect()
+}
+
 #[async_trait]
 impl ConvertibleStarknetEvent for StarknetEvent {
     async fn to_eth_log(
@@ -30,8 +48,8 @@ impl ConvertibleStarknetEvent for StarknetEvent {
         log_index: Option<U256>,
         transaction_index: Option<U256>,
     ) -> Result<Log, EthApiError> {
-        // If event `from_address` does not equal kakarot address, return early
-        if self.0.from_address != client.kakarot_address() {
+        // If event `from_address` is not a Kakarot deployment, return early
+        if !client.is_kakarot_address(&self.0.from_address) {
             return Err(EthApiError::OtherError(anyhow::anyhow!("Kakarot Filter: Event is not part of Kakarot")));
         }
 
@@ -45,29 +63,9 @@ impl ConvertibleStarknetEvent for StarknetEvent {
             felt_wrapper.into()
         };
 
-        let topics: Vec<H256> = keys
-            .chunks(2)
-            .map(|chunk| {
-                let low = BigUint::from_bytes_be(&chunk[0].to_bytes_be());
-                let high = match chunk.get(1) {
-                    Some(h) => BigUint::from_bytes_be(&h.to_bytes_be()),
-                    None => {
-                        return Err(anyhow::anyhow!("Not a convertible event: High value doesn't exist",));
-                    }
-                };
-                let result = low + (BigUint::from(2u128).pow(128u32) * high);
-                // Converts the result to bytes.
-                let bytes = result.to_bytes_be();
-                // If the length of bytes is less than 32, prepends it with zeros to make it 32 bytes long.
-                let bytes = once(0u8).cycle().take(32 - bytes.len()).chain(bytes.into_iter()).collect::<Vec<_>>();
-                Ok(H256::from_slice(&bytes))
-            })
-            .collect::<Result<_, _>>()?;
+        let topics = topics_from_keys(keys);
 
-        let data: Bytes = self.0.data.iter()
-            .flat_map(|felt| felt.to_bytes_be())
-            .collect::<Vec<u8>>() // Collect into Vec<u8> first
-            .into(); // Then convert into Bytes
+        let data = vec_felt_to_bytes(&self.0.data);
 
         Ok(Log {
             address,
@@ -0,0 +1,115 @@
+use std::future::Future;
+
+use reth_primitives::U256;
+use reth_rpc_types::SyncInfo;
+use serde::{Deserialize, Serialize, Serializer};
+use starknet::core::types::FieldElement;
+
+use super::confirmation::L1SettlementStatus;
+
+/// Key under which the Kakarot metadata is added to standard responses, when requested.
+///
+/// Strict clients ignore unknown fields, so the metadata is added alongside the standard fields
+/// instead of being served by dedicated methods.
+pub const KAKAROT_METADATA_KEY: &str = "kakarot";
+
+/// Key under which the reason of a reverted EVM execution is added to its receipt, as other EVM
+/// nodes do, since the standard receipt only carries the status.
+pub const REVERT_REASON_KEY: &str = "revertReason";
+
+tokio::task_local! {
+    static KAKAROT_METADATA_REQUESTED: bool;
+}
+
+/// Serves the requests run by `future` with the Kakarot metadata if `requested`, so that only
+/// the callers opting in receive it.
+pub async fn with_kakarot_metadata<F: Future>(requested: bool, future: F) -> F::Output {
+    KAKAROT_METADATA_REQUESTED.scope(requested, future).await
+}
+
+/// Whether the request being served asked for the Kakarot metadata, see
+/// [`with_kakarot_metadata`].
+#[must_use]
+pub fn kakarot_metadata_requested() -> bool {
+    KAKAROT_METADATA_REQUESTED.try_with(|requested| *requested).unwrap_or(false)
+}
+
+/// The Starknet transaction underlying an EVM transaction, added to transaction and receipt
+/// responses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionMetadata {
+    pub starknet_transaction_hash: FieldElement,
+    pub starknet_block_hash: FieldElement,
+    pub starknet_block_number: u64,
+    /// The class hash of the Starknet account which sent the transaction.
+    pub sender_class_hash: FieldElement,
+    /// The fee paid on Starknet, in the smallest unit of the fee token.
+    pub actual_fee: FieldElement,
+    pub l1_status: L1SettlementStatus,
+}
+
+/// The gas estimate of a transaction, served by `kakarot_estimateGas` next to `eth_estimateGas`,
+/// whose quantity response has no room for metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GasEstimate {
+    /// The gas of any transaction, a fixed placeholder until estimates are simulated on Starknet,
+    /// plus a fixed overhead if the transaction deploys the sender account.
+    pub gas: U256,
+    /// Whether the transaction deploys the sender account, which is deployed with its first
+    /// transaction.
+    pub includes_account_deployment: bool,
+}
+
+/// The syncing status served by `eth_syncing`, which extends the status of the Starknet node with
+/// the state of the adapter.
+///
+/// Serialized as `false` while both the node and the adapter are caught up, as the standard status,
+/// and as the progress of the node with the state of the adapter as non-standard fields otherwise.
+#[derive(Debug, Clone)]
+pub enum AdapterSyncStatus {
+    Synced,
+    Syncing { progress: SyncInfo, state: AdapterState },
+}
+
+impl Serialize for AdapterSyncStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Syncing<'a> {
+            #[serde(flatten)]
+            progress: &'a SyncInfo,
+            #[serde(flatten)]
+            state: &'a AdapterState,
+        }
+
+        match self {
+            Self::Synced => serializer.serialize_bool(false),
+            Self::Syncing { progress, state } => Syncing { progress, state }.serialize(serializer),
+        }
+    }
+}
+
+/// The state of the adapter itself, reported next to the syncing progress of the node.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdapterState {
+    /// The number of blocks the chain follower is behind the head of the node, `None` if the
+    /// chain is not followed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub follower_lag: Option<u64>,
+    /// The number of transactions waiting in the submission queue for the node to be available.
+    pub submission_queue_depth: usize,
+    /// The share of the head caches holding a value within their staleness budget, `None` if the
+    /// caching is disabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_cache_coverage: Option<f64>,
+}
+
+impl AdapterState {
+    /// Whether the adapter is catching up with the node, either following blocks behind its head
+    /// or holding transactions it did not submit yet.
+    #[must_use]
+    pub fn is_catching_up(&self) -> bool {
+        self.follower_lag.map_or(false, |lag| lag > 0) || self.submission_queue_depth > 0
+    }
+}
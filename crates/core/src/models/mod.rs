@@ -1,12 +1,17 @@
+pub mod activity;
 pub mod balance;
 pub mod block;
+pub mod chain_metadata;
+pub mod confirmation;
 pub mod convertible;
 pub mod event;
 pub mod felt;
+pub mod metadata;
 pub mod signature;
 #[cfg(test)]
 pub mod tests;
 pub mod transaction;
+pub mod txpool;
 
 use thiserror::Error;
 
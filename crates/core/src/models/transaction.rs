@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use reth_primitives::{H256, U256};
+use reth_primitives::{Address, Bytes, TransactionSigned, TxType, H256, U128, U256, U64};
 use reth_rpc_types::{Signature, Transaction as EthTransaction};
 use starknet::core::types::{BlockId as StarknetBlockId, BlockTag, FieldElement, InvokeTransaction, Transaction};
 use starknet::providers::Provider;
@@ -7,9 +7,10 @@ use starknet::providers::Provider;
 use super::felt::Felt252Wrapper;
 use super::ConversionError;
 use crate::client::client_api::KakarotProvider;
-use crate::client::constants::{self, CHAIN_ID};
+use crate::client::constants;
 use crate::client::errors::EthApiError;
-use crate::client::helpers::{decode_signature_from_tx_calldata, vec_felt_to_bytes};
+use crate::client::helpers::vec_felt_to_bytes;
+use crate::codec::{decode_transaction, raw_transaction_from_starknet_calldata};
 use crate::models::convertible::ConvertibleStarknetTransaction;
 
 pub struct StarknetTransaction(Transaction);
@@ -72,68 +73,168 @@ impl ConvertibleStarknetTransaction for StarknetTransaction {
         block_number: Option<U256>,
         transaction_index: Option<U256>,
     ) -> Result<EthTransaction, EthApiError> {
-        if !self.is_kakarot_tx(client).await? {
+        // Read the state at the block of the transaction, so that all the lookups are consistent
+        let starknet_block_id = match block_hash {
+            Some(block_hash) => {
+                let block_hash: Felt252Wrapper = block_hash.try_into()?;
+                StarknetBlockId::Hash(block_hash.into())
+            }
+            None => StarknetBlockId::Tag(BlockTag::Pending),
+        };
+
+        if !self.is_kakarot_tx(client, starknet_block_id).await? {
             return Err(EthApiError::OtherError(anyhow::anyhow!("Kakarot Filter: Tx is not part of Kakarot")));
         }
 
-        let starknet_block_latest = StarknetBlockId::Tag(BlockTag::Latest);
         let sender_address: FieldElement = self.sender_address()?.into();
 
         let hash: H256 = self.transaction_hash()?.into();
 
         let nonce: U256 = self.nonce()?.into();
 
-        let from = client.get_evm_address(&sender_address, &starknet_block_latest).await?;
-
-        let max_priority_fee_per_gas = Some(client.max_priority_fee_per_gas());
-
-        let calldata = self.calldata().unwrap_or_default();
-        let input = vec_felt_to_bytes(calldata.clone());
-
-        // TODO: wrap to abstract the following lines?
-        // Extracting the signature
-        let signature = decode_signature_from_tx_calldata(&calldata)?;
-        let v = if signature.odd_y_parity { 1 } else { 0 } + 35 + 2 * CHAIN_ID;
-        let signature = Some(Signature { r: signature.r, s: signature.s, v: U256::from_limbs_slice(&[v]) });
+        let from = client.get_evm_address(&sender_address, &starknet_block_id).await?;
+
+        // Recover the original signed EVM transaction from the Kakarot invoke calldata. A transaction
+        // which does not wrap one is still served, with its calldata as input, so that the blocks
+        // holding it can be read
+        let transaction = match self.eth_transaction() {
+            Ok(transaction) => transaction,
+            Err(err) => {
+                log::warn!("Serving the Kakarot transaction {hash:#x} without its EVM fields: {err}");
+                return Ok(EthTransaction {
+                    hash,
+                    nonce,
+                    block_hash,
+                    block_number,
+                    transaction_index,
+                    from,
+                    to: None,
+                    value: U256::ZERO,
+                    gas_price: None,
+                    gas: U256::ZERO,
+                    max_fee_per_gas: None,
+                    max_priority_fee_per_gas: None,
+                    input: vec_felt_to_bytes(&self.calldata()?),
+                    signature: None,
+                    chain_id: Some(client.chain_id().into()),
+                    access_list: None,
+                    transaction_type: None,
+                });
+            }
+        };
 
         Ok(EthTransaction {
-            hash,
-            nonce,
             block_hash,
             block_number,
             transaction_index,
-            from,
-            to: None,               // TODO fetch the to
-            value: U256::from(100), // TODO fetch the value
-            gas_price: None,        // TODO fetch the gas price
-            gas: U256::from(100),   // TODO fetch the gas amount
-            max_fee_per_gas: None,  // TODO fetch the max_fee_per_gas
-            max_priority_fee_per_gas,
-            input,
-            signature,
-            chain_id: Some(CHAIN_ID.into()),
-            access_list: None,      // TODO fetch the access list
-            transaction_type: None, // TODO fetch the transaction type
+            ..pending_eth_transaction(&transaction, hash, nonce, from, client.chain_id())
         })
     }
 }
 
+/// Converts a signed EVM transaction into an RPC transaction not yet included in a block.
+///
+/// ## Arguments
+///
+/// * `transaction` - The signed EVM transaction.
+/// * `hash` - The hash of the Starknet transaction wrapping it.
+/// * `nonce` - The nonce of the Starknet transaction wrapping it.
+/// * `from` - The EVM address of the sender.
+/// * `chain_id` - The chain id of Kakarot.
+pub(crate) fn pending_eth_transaction(
+    transaction: &TransactionSigned,
+    hash: H256,
+    nonce: U256,
+    from: Address,
+    chain_id: u64,
+) -> EthTransaction {
+    let input = transaction.input().clone();
+    let value = U256::from(transaction.value());
+
+    // Legacy and EIP-2930 transactions pay a gas price, EIP-1559 transactions a max fee and tip
+    let (gas_price, max_fee_per_gas) = match transaction.tx_type() {
+        TxType::EIP1559 => (None, Some(U128::from(transaction.max_fee_per_gas()))),
+        _ => (Some(U128::from(transaction.max_fee_per_gas())), None),
+    };
+    let max_priority_fee_per_gas = transaction.max_priority_fee_per_gas().map(U128::from);
+    // Only typed transactions carry an access list
+    let access_list = transaction.access_list().map(|access_list| access_list.0.clone());
+
+    let parity = u64::from(transaction.signature.odd_y_parity);
+    let v = match (transaction.tx_type(), transaction.chain_id()) {
+        // EIP-155 replay protected legacy transaction
+        (TxType::Legacy, Some(chain_id)) => parity + 35 + 2 * chain_id,
+        (TxType::Legacy, None) => parity + 27,
+        // Typed transactions only carry the y parity
+        _ => parity,
+    };
+    let signature =
+        Some(Signature { r: transaction.signature.r, s: transaction.signature.s, v: U256::from_limbs_slice(&[v]) });
+
+    EthTransaction {
+        hash,
+        nonce,
+        block_hash: None,
+        block_number: None,
+        transaction_index: None,
+        from,
+        to: transaction.to(),
+        value,
+        gas_price,
+        gas: U256::from(transaction.gas_limit()),
+        max_fee_per_gas,
+        max_priority_fee_per_gas,
+        input,
+        signature,
+        chain_id: Some(chain_id.into()),
+        access_list,
+        transaction_type: Some(U64::from(u8::from(transaction.tx_type()))),
+    }
+}
+
 impl StarknetTransaction {
+    /// Returns the raw signed EVM transaction wrapped by this Kakarot invoke transaction.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(Bytes)` - The RLP encoded EVM transaction, as sent to `eth_sendRawTransaction`.
+    /// `Err(EthApiError)` if the transaction is not an invoke transaction or its calldata is
+    /// invalid.
+    pub fn raw_eth_transaction(&self) -> Result<Bytes, EthApiError> {
+        let calldata = self.calldata()?;
+        raw_transaction_from_starknet_calldata(&calldata).map_err(EthApiError::TransactionDecodingError)
+    }
+
+    /// Returns the signed EVM transaction wrapped by this Kakarot invoke transaction.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(TransactionSigned)` - The EVM transaction, as sent to `eth_sendRawTransaction`.
+    /// `Err(EthApiError)` if the transaction is not an invoke transaction or does not wrap a
+    /// decodable EVM transaction.
+    pub fn eth_transaction(&self) -> Result<TransactionSigned, EthApiError> {
+        decode_transaction(&self.raw_eth_transaction()?).map_err(EthApiError::TransactionDecodingError)
+    }
+
     /// Checks if the transaction is a Kakarot transaction.
     ///
     /// ## Arguments
     ///
     /// * `client` - The Kakarot client.
+    /// * `block_id` - The block at which the sender account is looked up.
     ///
     /// ## Returns
     ///
     /// `Ok(bool)` if the operation was successful.
     /// `Err(EthApiError)` if the operation failed.
-    async fn is_kakarot_tx(&self, client: &dyn KakarotProvider) -> Result<bool, EthApiError> {
-        let starknet_block_latest = StarknetBlockId::Tag(BlockTag::Latest);
+    pub(crate) async fn is_kakarot_tx(
+        &self,
+        client: &dyn KakarotProvider,
+        block_id: StarknetBlockId,
+    ) -> Result<bool, EthApiError> {
         let sender_address: FieldElement = self.sender_address()?.into();
 
-        let class_hash = client.starknet_provider().get_class_hash_at(starknet_block_latest, sender_address).await?;
+        let class_hash = client.class_hash_at(block_id, sender_address).await?;
 
         Ok(class_hash == client.proxy_account_class_hash())
     }
@@ -0,0 +1,32 @@
+use std::collections::BTreeMap;
+
+use reth_primitives::{Address, U64};
+use reth_rpc_types::Transaction as EtherTransaction;
+use serde::{Deserialize, Serialize};
+
+/// The transactions of the pool by sender and nonce, as served by `txpool_content`.
+///
+/// Kakarot has no pool of its own: the pending transactions are the ones of the Starknet pending
+/// block, and no transaction is queued since the sequencer does not accept nonce gaps.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TxpoolContent {
+    pub pending: BTreeMap<Address, BTreeMap<String, EtherTransaction>>,
+    pub queued: BTreeMap<Address, BTreeMap<String, EtherTransaction>>,
+}
+
+impl From<Vec<EtherTransaction>> for TxpoolContent {
+    fn from(pending_transactions: Vec<EtherTransaction>) -> Self {
+        let mut pending: BTreeMap<Address, BTreeMap<String, EtherTransaction>> = BTreeMap::new();
+        for transaction in pending_transactions {
+            pending.entry(transaction.from).or_default().insert(transaction.nonce.to_string(), transaction);
+        }
+        Self { pending, queued: BTreeMap::new() }
+    }
+}
+
+/// The number of transactions of the pool, as served by `txpool_status`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxpoolStatus {
+    pub pending: U64,
+    pub queued: U64,
+}
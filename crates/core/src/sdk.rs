@@ -0,0 +1,149 @@
+//! A typed facade over [`KakarotProvider`] for Rust applications, such as bots and indexers,
+//! that use the adapter in-process.
+//!
+//! Methods take Ethereum block ids and take care of converting them to Starknet block ids.
+//! Log filters are built with the [`Filter`] builder:
+//!
+//! ```ignore
+//! let filter = Filter::new().address(token).event("Transfer(address,address,uint256)").from_block(19_000);
+//! let logs = provider.get_logs(filter).await?;
+//! ```
+use std::sync::Arc;
+use std::time::Duration;
+
+use reth_primitives::{Address, BlockId, Bytes, H256, U256, U64};
+pub use reth_rpc_types::Filter;
+use reth_rpc_types::{Log, RichBlock, Transaction as EtherTransaction, TransactionReceipt};
+
+use crate::client::client_api::KakarotProvider;
+use crate::client::errors::EthApiError;
+use crate::client::helpers::is_transaction_not_found_error;
+
+/// High level Ethereum provider backed by a [`KakarotProvider`].
+#[derive(Clone)]
+pub struct KakarotEthProvider {
+    client: Arc<dyn KakarotProvider>,
+}
+
+impl KakarotEthProvider {
+    #[must_use]
+    pub fn new(client: Arc<dyn KakarotProvider>) -> Self {
+        Self { client }
+    }
+
+    /// Returns the underlying Kakarot provider.
+    #[must_use]
+    pub fn client(&self) -> &Arc<dyn KakarotProvider> {
+        &self.client
+    }
+
+    /// Get the number of the latest block.
+    pub async fn get_block_number(&self) -> Result<U64, EthApiError> {
+        self.client.block_number().await
+    }
+
+    /// Get a block by id.
+    ///
+    /// ## Arguments
+    ///
+    /// * `block_id(BlockId)` - The block id.
+    /// * `full(bool)` - Whether to hydrate the transactions.
+    pub async fn get_block(&self, block_id: BlockId, full: bool) -> Result<RichBlock, EthApiError> {
+        let starknet_block_id = self.client.resolve_block_id(block_id).await?;
+        self.client.get_eth_block_from_starknet_block(starknet_block_id, full).await
+    }
+
+    /// Get the native token balance of an address.
+    ///
+    /// ## Arguments
+    ///
+    /// * `address(Address)` - The address to get the balance of.
+    /// * `block_id(Option<BlockId>)` - The block to read the balance at, the configured default
+    ///   block if `None`.
+    pub async fn get_balance(&self, address: Address, block_id: Option<BlockId>) -> Result<U256, EthApiError> {
+        let starknet_block_id = self.client.resolve_block_id(self.block_or_default(block_id)).await?;
+        self.client.balance(address, starknet_block_id).await
+    }
+
+    /// Get the nonce of an address.
+    ///
+    /// ## Arguments
+    ///
+    /// * `address(Address)` - The address to get the nonce of.
+    /// * `block_id(Option<BlockId>)` - The block to read the nonce at, the configured default block
+    ///   if `None`.
+    pub async fn get_transaction_count(
+        &self,
+        address: Address,
+        block_id: Option<BlockId>,
+    ) -> Result<U256, EthApiError> {
+        let starknet_block_id = self.client.resolve_block_id(self.block_or_default(block_id)).await?;
+        self.client.nonce(address, starknet_block_id).await
+    }
+
+    /// Get a transaction by hash.
+    pub async fn get_transaction(&self, hash: H256) -> Result<EtherTransaction, EthApiError> {
+        self.client.transaction_by_hash(hash).await
+    }
+
+    /// Get the receipt of a transaction, `None` if the transaction is not yet included.
+    pub async fn get_transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>, EthApiError> {
+        self.client.transaction_receipt(hash).await
+    }
+
+    /// Submit a signed, RLP encoded EVM transaction.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(H256)` - The hash of the transaction.
+    /// `Err(EthApiError)` if the transaction could not be decoded or submitted.
+    pub async fn send_raw_transaction(&self, transaction: Bytes) -> Result<H256, EthApiError> {
+        self.client.send_transaction(transaction).await
+    }
+
+    /// Poll the receipt of a transaction until it is included.
+    ///
+    /// Receipts are only returned once transactions reach the configured confirmation level, so
+    /// this waits for L1 acceptance when the client runs with `ConfirmationLevel::AcceptedOnL1`.
+    /// Transactions the node has not received yet are polled too, as just sent transactions can
+    /// be unknown to the node for a while.
+    ///
+    /// ## Arguments
+    ///
+    /// * `hash(H256)` - The hash of the transaction.
+    /// * `poll_interval(Duration)` - The delay between two receipt lookups.
+    /// * `timeout(Duration)` - How long to wait for the receipt before giving up.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(TransactionReceipt)` once the transaction is included.
+    /// `Err(EthApiError::RequestTimeout)` if the transaction is not included within `timeout`.
+    pub async fn wait_for_receipt(
+        &self,
+        hash: H256,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<TransactionReceipt, EthApiError> {
+        let poll = async {
+            loop {
+                match self.client.transaction_receipt(hash).await {
+                    Ok(Some(receipt)) => return Ok::<_, EthApiError>(receipt),
+                    Ok(None) => {}
+                    Err(err) if is_transaction_not_found_error(&err) => {}
+                    Err(err) => return Err(err),
+                }
+                tokio::time::sleep(poll_interval).await;
+            }
+        };
+        tokio::time::timeout(timeout, poll).await.map_err(|_| EthApiError::RequestTimeout(timeout))?
+    }
+
+    /// Get the logs matching a filter.
+    pub async fn get_logs(&self, filter: Filter) -> Result<Vec<Log>, EthApiError> {
+        self.client.get_logs(filter).await
+    }
+
+    fn block_or_default(&self, block_id: Option<BlockId>) -> BlockId {
+        block_id.unwrap_or_else(|| self.client.default_block_id())
+    }
+}
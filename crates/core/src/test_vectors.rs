@@ -0,0 +1,184 @@
+//! Known input and output pairs of the mapping between EVM and Starknet data.
+//!
+//! Wallets and SDKs in other languages can check their implementation against these vectors. They
+//! are all taken from the transactions of block 19612 of the Kakarot deployment the fixtures of
+//! `mock` were recorded from, whose Kakarot contract is deployed at
+//! `0x06eac8dd0d230c4b37f46bf4c20fb2dc21cd55f87791e2a76beae8059bd8e5e6`. Only EIP-1559 transactions
+//! were sent to it, so there are no vectors of the other transaction types. Values are hex
+//! encoded, as sent over JSON-RPC.
+
+/// An EVM address, and the address of its contract account on Starknet.
+///
+/// Kakarot deploys the contract account of an EVM address with the `deploy` syscall, using the
+/// proxy account class hash, the EVM address as salt, no constructor calldata and Kakarot as
+/// deployer, so the Starknet address only depends on these values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AddressVector {
+    pub evm_address: &'static str,
+    pub kakarot_address: &'static str,
+    pub proxy_account_class_hash: &'static str,
+    pub starknet_address: &'static str,
+}
+
+/// A raw EVM transaction, as received by `eth_sendRawTransaction`, the address which signed it,
+/// and the calldata of the Starknet invoke transaction which sent it to Kakarot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalldataVector {
+    pub starknet_transaction_hash: &'static str,
+    pub raw_transaction: &'static str,
+    pub sender: &'static str,
+    pub kakarot_address: &'static str,
+    pub calldata: &'static [&'static str],
+}
+
+/// Starknet addresses of the contract accounts deployed by Kakarot for EVM addresses.
+///
+/// The EVM address is the signer of the transactions of [`CALLDATA_VECTORS`], and the Starknet
+/// address the account which sent them.
+pub const ADDRESS_VECTORS: &[AddressVector] = &[AddressVector {
+    evm_address: "0x54b288676b749def5fc10eb17244fe2c87375de1",
+    kakarot_address: "0x06eac8dd0d230c4b37f46bf4c20fb2dc21cd55f87791e2a76beae8059bd8e5e6",
+    proxy_account_class_hash: "0x0775033b738dfe34c48f43a839c3d882ebe521befb3447240f2d218f14816ef5",
+    starknet_address: "0x0744ed080b42c8883a7e31cd11a14b7ae9ef27698b785486bb75cd116c8f1485",
+}];
+
+/// Calldata of the Starknet invoke transactions which sent raw EVM transactions to Kakarot, as
+/// recorded on chain.
+pub const CALLDATA_VECTORS: &[CalldataVector] = &[
+    CalldataVector {
+        starknet_transaction_hash: "0x0266c8ca758e0fc2af958ee60dabd1f94e916f471bfb0e334ec1b23f76a4ac9d",
+        raw_transaction: "0x02f872844b4b525482dead82dead82dead843b9aca00942e11ed82f5ec165ab8ce3cc094f025fe7527f4d18084b3bcfa82c001a0889be67d59bc1a43dd803955f7917ddcb7d748ed3e9b00cdb159f294651976b8a03801702a606ffbfd60364ff897f7ca511411d6660f936dd51eb90a7d30735261",
+        sender: "0x54b288676b749def5fc10eb17244fe2c87375de1",
+        kakarot_address: "0x06eac8dd0d230c4b37f46bf4c20fb2dc21cd55f87791e2a76beae8059bd8e5e6",
+        calldata: &[
+            // calls_len, to, selector, data_offset, data_len, calldata_len
+            "0x1",
+            "0x6eac8dd0d230c4b37f46bf4c20fb2dc21cd55f87791e2a76beae8059bd8e5e6",
+            "0x7099f594eb65e00576e1b940a8a735f80bf7604ac401c48627045c4cc286f0",
+            "0x0",
+            "0x75",
+            "0x75",
+            // the raw transaction, one byte per felt
+            "0x2", "0xf8", "0x72", "0x84", "0x4b", "0x4b", "0x52", "0x54", "0x82", "0xde", "0xad", "0x82",
+            "0xde", "0xad", "0x82", "0xde", "0xad", "0x84", "0x3b", "0x9a", "0xca", "0x0", "0x94", "0x2e",
+            "0x11", "0xed", "0x82", "0xf5", "0xec", "0x16", "0x5a", "0xb8", "0xce", "0x3c", "0xc0", "0x94",
+            "0xf0", "0x25", "0xfe", "0x75", "0x27", "0xf4", "0xd1", "0x80", "0x84", "0xb3", "0xbc", "0xfa",
+            "0x82", "0xc0", "0x1", "0xa0", "0x88", "0x9b", "0xe6", "0x7d", "0x59", "0xbc", "0x1a", "0x43",
+            "0xdd", "0x80", "0x39", "0x55", "0xf7", "0x91", "0x7d", "0xdc", "0xb7", "0xd7", "0x48", "0xed",
+            "0x3e", "0x9b", "0x0", "0xcd", "0xb1", "0x59", "0xf2", "0x94", "0x65", "0x19", "0x76", "0xb8",
+            "0xa0", "0x38", "0x1", "0x70", "0x2a", "0x60", "0x6f", "0xfb", "0xfd", "0x60", "0x36", "0x4f",
+            "0xf8", "0x97", "0xf7", "0xca", "0x51", "0x14", "0x11", "0xd6", "0x66", "0xf", "0x93", "0x6d",
+            "0xd5", "0x1e", "0xb9", "0xa", "0x7d", "0x30", "0x73", "0x52", "0x61",
+        ],
+    },
+    CalldataVector {
+        starknet_transaction_hash: "0x06daa99b399dc53a2029effb2dd8b2a5a0836b81f223e305784510334437f6e",
+        raw_transaction: "0x02f872844b4b525482dead82dead82dead843b9aca00942e11ed82f5ec165ab8ce3cc094f025fe7527f4d18084371303c0c001a0bda8aa747569ad0131a05cc016791788736c5a20006fd7c41e12c2860182f5fea04112df0d3765963f54e935da1c43caad574195c393a3ab71643a1d2c3b2b88e5",
+        sender: "0x54b288676b749def5fc10eb17244fe2c87375de1",
+        kakarot_address: "0x06eac8dd0d230c4b37f46bf4c20fb2dc21cd55f87791e2a76beae8059bd8e5e6",
+        calldata: &[
+            // calls_len, to, selector, data_offset, data_len, calldata_len
+            "0x1",
+            "0x6eac8dd0d230c4b37f46bf4c20fb2dc21cd55f87791e2a76beae8059bd8e5e6",
+            "0x7099f594eb65e00576e1b940a8a735f80bf7604ac401c48627045c4cc286f0",
+            "0x0",
+            "0x75",
+            "0x75",
+            // the raw transaction, one byte per felt
+            "0x2", "0xf8", "0x72", "0x84", "0x4b", "0x4b", "0x52", "0x54", "0x82", "0xde", "0xad", "0x82",
+            "0xde", "0xad", "0x82", "0xde", "0xad", "0x84", "0x3b", "0x9a", "0xca", "0x0", "0x94", "0x2e",
+            "0x11", "0xed", "0x82", "0xf5", "0xec", "0x16", "0x5a", "0xb8", "0xce", "0x3c", "0xc0", "0x94",
+            "0xf0", "0x25", "0xfe", "0x75", "0x27", "0xf4", "0xd1", "0x80", "0x84", "0x37", "0x13", "0x3",
+            "0xc0", "0xc0", "0x1", "0xa0", "0xbd", "0xa8", "0xaa", "0x74", "0x75", "0x69", "0xad", "0x1",
+            "0x31", "0xa0", "0x5c", "0xc0", "0x16", "0x79", "0x17", "0x88", "0x73", "0x6c", "0x5a", "0x20",
+            "0x0", "0x6f", "0xd7", "0xc4", "0x1e", "0x12", "0xc2", "0x86", "0x1", "0x82", "0xf5", "0xfe",
+            "0xa0", "0x41", "0x12", "0xdf", "0xd", "0x37", "0x65", "0x96", "0x3f", "0x54", "0xe9", "0x35",
+            "0xda", "0x1c", "0x43", "0xca", "0xad", "0x57", "0x41", "0x95", "0xc3", "0x93", "0xa3", "0xab",
+            "0x71", "0x64", "0x3a", "0x1d", "0x2c", "0x3b", "0x2b", "0x88", "0xe5",
+        ],
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use reth_primitives::{Address, Bytes};
+    use starknet::core::types::FieldElement;
+    use starknet::core::utils::get_contract_address;
+
+    use super::*;
+    use crate::codec::{decode_signed_transaction, raw_starknet_calldata, raw_transaction_from_starknet_calldata};
+    use crate::models::felt::Felt252Wrapper;
+
+    fn felt(value: &str) -> FieldElement {
+        FieldElement::from_hex_be(value).unwrap()
+    }
+
+    #[test]
+    fn test_address_vectors() {
+        for vector in ADDRESS_VECTORS {
+            let evm_address: Felt252Wrapper = Address::from_str(vector.evm_address).unwrap().into();
+
+            let starknet_address = get_contract_address(
+                evm_address.into(),
+                felt(vector.proxy_account_class_hash),
+                &[],
+                felt(vector.kakarot_address),
+            );
+
+            assert_eq!(starknet_address, felt(vector.starknet_address), "{}", vector.evm_address);
+        }
+    }
+
+    #[test]
+    fn test_address_vectors_are_the_senders_of_the_calldata_vectors() {
+        for vector in CALLDATA_VECTORS {
+            assert!(
+                ADDRESS_VECTORS
+                    .iter()
+                    .any(|address| address.evm_address == vector.sender
+                        && address.kakarot_address == vector.kakarot_address),
+                "{}",
+                vector.starknet_transaction_hash
+            );
+        }
+    }
+
+    #[test]
+    fn test_calldata_vectors_decode_to_their_raw_transaction() {
+        for vector in CALLDATA_VECTORS {
+            let calldata: Vec<FieldElement> = vector.calldata.iter().map(|value| felt(value)).collect();
+
+            let raw_transaction = raw_transaction_from_starknet_calldata(&calldata).unwrap();
+
+            assert_eq!(format!("0x{}", hex::encode(&raw_transaction)), vector.raw_transaction);
+        }
+    }
+
+    #[test]
+    fn test_calldata_vectors_are_signed_by_their_sender() {
+        for vector in CALLDATA_VECTORS {
+            let raw_transaction = hex::decode(&vector.raw_transaction[2..]).unwrap();
+
+            let transaction = decode_signed_transaction(&raw_transaction).unwrap();
+
+            assert_eq!(
+                transaction.signer,
+                Address::from_str(vector.sender).unwrap(),
+                "{}",
+                vector.starknet_transaction_hash
+            );
+        }
+    }
+
+    #[test]
+    fn test_calldata_vectors_are_encoded_from_their_raw_transaction() {
+        for vector in CALLDATA_VECTORS {
+            let raw_transaction = Bytes::from(hex::decode(&vector.raw_transaction[2..]).unwrap());
+            let calldata: Vec<FieldElement> = vector.calldata.iter().map(|value| felt(value)).collect();
+
+            assert_eq!(raw_starknet_calldata(felt(vector.kakarot_address), raw_transaction), calldata);
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! Builder for EVM transactions accepted by Kakarot.
+//!
+//! Kakarot only accepts transactions for its own chain id, does not run a fee market and deploys
+//! the Starknet account of a sender on its first transaction. [`TransactionBuilder`] fills the
+//! fields accordingly and produces an EIP-1559 transaction ready to be signed and sent with
+//! `eth_sendRawTransaction`.
+
+use reth_primitives::{AccessList, Address, Bytes, Transaction, TransactionKind, TxEip1559};
+use starknet::core::types::{BlockId as StarknetBlockId, BlockTag, StarknetError};
+use starknet::providers::ProviderError;
+
+use crate::client::client_api::KakarotProvider;
+use crate::client::constants::gas::{BASE_FEE_PER_GAS, DEFAULT_TRANSACTION_GAS_LIMIT, MAX_PRIORITY_FEE_PER_GAS};
+use crate::client::constants::CHAIN_ID;
+use crate::client::errors::EthApiError;
+
+/// Builds EIP-1559 transactions for Kakarot.
+///
+/// Unset fields default to:
+/// * `to` - `None`, which deploys `input` as contract bytecode.
+/// * `chain_id` - `CHAIN_ID`, or the chain id of the client when built with
+///   [`TransactionBuilder::build_for_sender`].
+/// * `nonce` - 0, or the sender's nonce when built with [`TransactionBuilder::build_for_sender`].
+/// * `gas_limit` - `DEFAULT_TRANSACTION_GAS_LIMIT`.
+/// * `max_priority_fee_per_gas` - `MAX_PRIORITY_FEE_PER_GAS`, or the priority fee suggested by the
+///   client when built with [`TransactionBuilder::build_for_sender`].
+#[derive(Debug, Clone, Default)]
+pub struct TransactionBuilder {
+    to: Option<Address>,
+    value: u128,
+    input: Bytes,
+    nonce: Option<u64>,
+    gas_limit: Option<u64>,
+    chain_id: Option<u64>,
+    max_priority_fee_per_gas: Option<u128>,
+}
+
+impl TransactionBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn to(mut self, to: Address) -> Self {
+        self.to = Some(to);
+        self
+    }
+
+    #[must_use]
+    pub fn value(mut self, value: u128) -> Self {
+        self.value = value;
+        self
+    }
+
+    #[must_use]
+    pub fn input(mut self, input: Bytes) -> Self {
+        self.input = input;
+        self
+    }
+
+    #[must_use]
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    #[must_use]
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    #[must_use]
+    pub fn chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    #[must_use]
+    pub fn max_priority_fee_per_gas(mut self, max_priority_fee_per_gas: u128) -> Self {
+        self.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+        self
+    }
+
+    /// Builds the transaction without querying the chain.
+    #[must_use]
+    pub fn build(self) -> Transaction {
+        let to = match self.to {
+            Some(to) => TransactionKind::Call(to),
+            None => TransactionKind::Create,
+        };
+        let max_priority_fee_per_gas =
+            self.max_priority_fee_per_gas.unwrap_or_else(|| MAX_PRIORITY_FEE_PER_GAS.to::<u128>());
+
+        Transaction::Eip1559(TxEip1559 {
+            chain_id: self.chain_id.unwrap_or(CHAIN_ID),
+            nonce: self.nonce.unwrap_or_default(),
+            gas_limit: self.gas_limit.unwrap_or(DEFAULT_TRANSACTION_GAS_LIMIT),
+            max_fee_per_gas: u128::from(BASE_FEE_PER_GAS) + max_priority_fee_per_gas,
+            max_priority_fee_per_gas,
+            to,
+            value: self.value,
+            access_list: AccessList::default(),
+            input: self.input,
+        })
+    }
+
+    /// Builds the transaction, reading the nonce of `sender` from the pending block, the chain id
+    /// and the priority fee from the client if they were not set.
+    ///
+    /// The Starknet account of a sender is deployed counterfactually by its first transaction, so
+    /// a sender whose account does not exist yet gets the nonce 0.
+    ///
+    /// ## Arguments
+    ///
+    /// * `client` - The Kakarot client.
+    /// * `sender` - The EVM address which will sign the transaction.
+    ///
+    /// ## Returns
+    ///
+    /// `Ok(Transaction)` if the operation was successful.
+    /// `Err(EthApiError)` if the nonce or the priority fee could not be fetched.
+    pub async fn build_for_sender(
+        mut self,
+        client: &dyn KakarotProvider,
+        sender: Address,
+    ) -> Result<Transaction, EthApiError> {
+        if self.nonce.is_none() {
+            let nonce = match client.nonce(sender, StarknetBlockId::Tag(BlockTag::Pending)).await {
+                Ok(nonce) => u64::try_from(nonce).map_err(|_| {
+                    EthApiError::OtherError(anyhow::anyhow!("Kakarot Core: nonce of {sender} overflows u64"))
+                })?,
+                Err(EthApiError::RequestError(ProviderError::StarknetError(StarknetError::ContractNotFound))) => 0,
+                Err(err) => return Err(err),
+            };
+            self.nonce = Some(nonce);
+        }
+        self.chain_id = self.chain_id.or(Some(client.chain_id()));
+        if self.max_priority_fee_per_gas.is_none() {
+            let max_priority_fee_per_gas = client.suggest_max_priority_fee_per_gas().await?;
+            self.max_priority_fee_per_gas = Some(max_priority_fee_per_gas.to::<u128>());
+        }
+
+        Ok(self.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_build_fills_kakarot_defaults() {
+        let to = Address::from_str("0x2e11ed82f5ec165ab8ce3cc094f025fe7527f4d1").unwrap();
+        let transaction = TransactionBuilder::new().to(to).input(Bytes::from(vec![0xb3, 0xbc, 0xfa, 0x82])).build();
+
+        assert_eq!(transaction.chain_id(), Some(CHAIN_ID));
+        assert_eq!(transaction.nonce(), 0);
+        assert_eq!(transaction.gas_limit(), DEFAULT_TRANSACTION_GAS_LIMIT);
+        assert_eq!(transaction.max_fee_per_gas(), u128::from(BASE_FEE_PER_GAS));
+        assert_eq!(transaction.to(), Some(to));
+    }
+
+    #[test]
+    fn test_build_without_recipient_is_a_deployment() {
+        let transaction = TransactionBuilder::new().nonce(7).gas_limit(21_000).chain_id(1).build();
+
+        assert_eq!(transaction.kind(), &TransactionKind::Create);
+        assert_eq!(transaction.nonce(), 7);
+        assert_eq!(transaction.gas_limit(), 21_000);
+        assert_eq!(transaction.chain_id(), Some(1));
+    }
+}
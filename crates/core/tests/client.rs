@@ -2,17 +2,53 @@
 mod tests {
 
     use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
 
     use kakarot_rpc_core::client::client_api::KakarotProvider;
-    use kakarot_rpc_core::mock::wiremock_utils::setup_mock_client_crate;
+    use kakarot_rpc_core::client::config::StarknetConfig;
+    use kakarot_rpc_core::client::constants::gas::BASE_FEE_PER_GAS;
+    use kakarot_rpc_core::client::constants::requests::MAX_LOGS_BLOCK_RANGE;
+    use kakarot_rpc_core::client::constants::selectors::TRANSACTION_EXECUTED;
+    use kakarot_rpc_core::client::constants::{
+        CHAIN_ID, DEFAULT_CHAIN_NAME, DEFAULT_NATIVE_CURRENCY_NAME, DEFAULT_NATIVE_CURRENCY_SYMBOL, MAX_FEE,
+        NATIVE_CURRENCY_DECIMALS, STARKNET_NATIVE_TOKEN,
+    };
+    use kakarot_rpc_core::client::errors::EthApiError;
+    use kakarot_rpc_core::client::events::ClientEvent;
+    use kakarot_rpc_core::client::filters::FilterChanges;
+    use kakarot_rpc_core::client::helpers::{compute_invoke_v1_transaction_hash, is_transaction_not_found_error};
+    use kakarot_rpc_core::client::subscriptions::SubscriptionManager;
+    use kakarot_rpc_core::client::webhooks::deliver_webhooks;
+    use kakarot_rpc_core::client::KakarotClient;
+    use kakarot_rpc_core::codec::{decode_transaction, raw_starknet_calldata};
+    use kakarot_rpc_core::mock::recorder::{load_exchanges, mount_exchanges, RecordingTransport};
+    use kakarot_rpc_core::mock::wiremock_utils::{
+        fixture_transaction, mock_add_invoke_transaction, mock_block_with_transactions, mock_chain_block,
+        mock_class_hash_at, mock_compute_starknet_address, mock_compute_starknet_address_at, mock_events,
+        mock_evm_address_of, mock_evm_address_of_at, mock_pending_block, mock_starknet_config, mock_starknet_error,
+        setup_mock_client_crate, setup_mock_server, setup_wiremock, StarknetRpcAtAnyBlock, KAKAROT_ADDRESS,
+        PROXY_ACCOUNT_CLASS_HASH, RAW_TRANSACTION, SENDER_STARKNET_ADDRESS, STARKNET_CHAIN_ID,
+    };
+    use kakarot_rpc_core::models::activity::{AddressActivity, WatchlistEvent};
     use kakarot_rpc_core::models::block::BlockWithTxs;
-    use kakarot_rpc_core::models::convertible::{ConvertibleStarknetBlock, ConvertibleStarknetEvent};
+    use kakarot_rpc_core::models::convertible::{
+        ConvertibleStarknetBlock, ConvertibleStarknetEvent, ConvertibleStarknetTransaction,
+    };
     use kakarot_rpc_core::models::event::StarknetEvent;
-    use reth_primitives::{Address, Bytes, H256};
-    use reth_rpc_types::Log;
-    use starknet::core::types::{BlockId, BlockTag, Event, FieldElement};
+    use kakarot_rpc_core::models::transaction::StarknetTransaction;
+    use kakarot_rpc_core::sdk::KakarotEthProvider;
+    use reth_primitives::{Address, BlockNumberOrTag, Bloom, Bytes, H256, U256, U64};
+    use reth_rpc_types::{Filter, Log};
+    use starknet::core::types::{BlockId, BlockTag, BroadcastedInvokeTransactionV1, Event, FieldElement, Transaction};
     use starknet::core::utils::get_selector_from_name;
-    use starknet::providers::Provider;
+    use starknet::macros::felt;
+    use starknet::providers::jsonrpc::HttpTransport;
+    use starknet::providers::{JsonRpcClient, Provider};
+    use tokio::sync::broadcast;
+    use url::Url;
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     #[tokio::test]
     async fn test_starknet_block_to_eth_block() {
@@ -150,6 +186,51 @@ mod tests {
         assert_eq!(expected_eth_log4, resultant_eth_log4);
     }
 
+    #[tokio::test]
+    async fn test_starknet_event_to_eth_log_ignores_incomplete_key_pairs() {
+        let client = setup_mock_client_crate().await;
+        let kakarot_address =
+            FieldElement::from_hex_be("0x566864dbc2ae76c2d12a8a5a334913d0806f85b7a4dccea87467c3ba3616e75").unwrap();
+        let evm_contract_address =
+            FieldElement::from_dec_str("247666869351872231004050922759157890085502224190").unwrap();
+
+        // given an anonymous event, without any topic
+        let anonymous_event = Event { from_address: kakarot_address, keys: vec![evm_contract_address], data: vec![] };
+        // and an event with a trailing key which is not part of a (low, high) topic pair
+        let event_with_trailing_key = Event {
+            from_address: kakarot_address,
+            keys: vec![
+                FieldElement::from_dec_str("10").unwrap(),
+                FieldElement::ZERO,
+                FieldElement::from_dec_str("42").unwrap(),
+                evm_contract_address,
+            ],
+            data: vec![],
+        };
+
+        // when
+        let anonymous_log = StarknetEvent::new(anonymous_event)
+            .to_eth_log(&client, Option::None, Option::None, Option::None, Option::None, Option::None)
+            .await
+            .unwrap();
+        let log_with_trailing_key = StarknetEvent::new(event_with_trailing_key)
+            .to_eth_log(&client, Option::None, Option::None, Option::None, Option::None, Option::None)
+            .await
+            .unwrap();
+
+        // then
+        let address = Address::from_str("0x2B61c43A85bD35987C5311215e8288b823A6873E").unwrap();
+        assert_eq!(anonymous_log.address, address);
+        assert!(anonymous_log.topics.is_empty());
+        assert_eq!(log_with_trailing_key.address, address);
+        assert_eq!(
+            log_with_trailing_key.topics,
+            vec![H256::from_slice(
+                &hex::decode("000000000000000000000000000000000000000000000000000000000000000a").unwrap()
+            )]
+        );
+    }
+
     #[tokio::test]
     async fn test_starknet_event_to_eth_log_failure_from_address_not_kkrt_address() {
         let client = setup_mock_client_crate().await;
@@ -188,4 +269,1116 @@ mod tests {
             .await;
         assert!(starknet_tx.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_raw_transaction_by_hash() {
+        let client = setup_mock_client_crate().await;
+        let raw_transaction = client
+            .raw_transaction_by_hash(
+                H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let transaction = decode_transaction(&raw_transaction).unwrap();
+        assert_eq!(transaction.chain_id(), Some(CHAIN_ID));
+        assert_eq!(transaction.nonce(), 0xdead);
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_for_another_chain_is_rejected() {
+        let starknet_rpc = setup_wiremock().await;
+        let config = StarknetConfig { chain_id: 1, ..mock_starknet_config(&starknet_rpc) };
+        let client = KakarotClient::new(config).unwrap();
+        let raw_transaction = client
+            .raw_transaction_by_hash(
+                H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let err = client.send_transaction(raw_transaction).await.unwrap_err();
+
+        assert!(err.to_string().contains(&format!("invalid chain id {CHAIN_ID}, expected 1")));
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_with_oversized_data_is_rejected() {
+        let starknet_rpc = setup_wiremock().await;
+        let config = StarknetConfig { max_transaction_input_size: 0, ..mock_starknet_config(&starknet_rpc) };
+        let client = KakarotClient::new(config).unwrap();
+        let raw_transaction = client
+            .raw_transaction_by_hash(
+                H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap(),
+            )
+            .await
+            .unwrap();
+
+        let err = client.send_transaction(raw_transaction).await.unwrap_err();
+
+        assert!(err.to_string().starts_with("oversized data"));
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_sends_max_fee_upstream() {
+        let mock_server = setup_mock_server().await;
+        mock_compute_starknet_address().mount(&mock_server).await;
+        let transaction_hash = FieldElement::from(0xabcu64);
+        mock_add_invoke_transaction(
+            serde_json::json!({ "max_fee": format!("{:#x}", *MAX_FEE), "sender_address": format!("{SENDER_STARKNET_ADDRESS:#x}") }),
+            transaction_hash,
+        )
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        let hash = client.send_transaction(Bytes::from_str(RAW_TRANSACTION).unwrap()).await.unwrap();
+
+        assert_eq!(hash, H256::from(transaction_hash.to_bytes_be()));
+    }
+
+    #[tokio::test]
+    async fn test_send_transaction_resolves_the_sender_at_pending_whatever_the_default_block() {
+        let mock_server = setup_mock_server().await;
+        mock_compute_starknet_address_at(BlockId::Tag(BlockTag::Pending)).expect(1).mount(&mock_server).await;
+        let transaction_hash = FieldElement::from(0xabcu64);
+        mock_add_invoke_transaction(
+            serde_json::json!({ "sender_address": format!("{SENDER_STARKNET_ADDRESS:#x}") }),
+            transaction_hash,
+        )
+        .mount(&mock_server)
+        .await;
+        let config =
+            StarknetConfig { default_block: BlockNumberOrTag::Earliest, ..mock_starknet_config(&mock_server.uri()) };
+        let client = KakarotClient::new(config).unwrap();
+
+        let hash = client.send_transaction(Bytes::from_str(RAW_TRANSACTION).unwrap()).await.unwrap();
+
+        assert_eq!(hash, H256::from(transaction_hash.to_bytes_be()));
+    }
+
+    #[tokio::test]
+    async fn test_queued_transaction_rejected_once_submitted_is_reported() {
+        let mock_server = setup_mock_server().await;
+        mock_compute_starknet_address().mount(&mock_server).await;
+        // The node is unavailable when the transaction is sent, and rejects it once back
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "starknet_addInvokeTransaction" })))
+            .respond_with(ResponseTemplate::new(503))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        mock_starknet_error("starknet_addInvokeTransaction", 1, "Failed to receive transaction")
+            .with_priority(2)
+            .mount(&mock_server)
+            .await;
+        let config = StarknetConfig {
+            submission_queue_capacity: 1,
+            starknet_chain_id: Some(STARKNET_CHAIN_ID),
+            ..mock_starknet_config(&mock_server.uri())
+        };
+        let client = KakarotClient::new(config).unwrap();
+
+        let hash = client.send_transaction(Bytes::from_str(RAW_TRANSACTION).unwrap()).await.unwrap();
+        assert_eq!(client.transaction_by_hash(hash).await.unwrap().block_hash, None);
+
+        assert_eq!(client.flush_submission_queue().await.unwrap(), 1);
+
+        let err = client.transaction_by_hash(hash).await.unwrap_err();
+        assert!(matches!(err, EthApiError::TransactionRejected(_)), "{err}");
+        let err = client.transaction_receipt(hash).await.unwrap_err();
+        assert!(matches!(err, EthApiError::TransactionRejected(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_transaction_is_queued_while_every_upstream_method_is_unavailable() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST")).respond_with(ResponseTemplate::new(503)).mount(&mock_server).await;
+        let config = StarknetConfig {
+            submission_queue_capacity: 1,
+            starknet_chain_id: Some(STARKNET_CHAIN_ID),
+            ..mock_starknet_config(&mock_server.uri())
+        };
+        let client = KakarotClient::new(config).unwrap();
+        let request = BroadcastedInvokeTransactionV1 {
+            max_fee: *MAX_FEE,
+            signature: vec![],
+            nonce: FieldElement::ZERO,
+            sender_address: SENDER_STARKNET_ADDRESS,
+            calldata: raw_starknet_calldata(KAKAROT_ADDRESS, Bytes::from_str(RAW_TRANSACTION).unwrap()),
+        };
+        let expected_hash = compute_invoke_v1_transaction_hash(STARKNET_CHAIN_ID, &request);
+
+        let hash = client.submit_starknet_transaction(request).await.unwrap();
+
+        assert_eq!(hash, H256::from(expected_hash.to_bytes_be()));
+        let transaction = client.transaction_by_hash(hash).await.unwrap();
+        assert_eq!(transaction.hash, hash);
+        assert_eq!(transaction.block_hash, None);
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_submission_returns_the_original_transaction_hash() {
+        let mock_server = setup_mock_server().await;
+        // The node already received the transaction, e.g. before a retry
+        mock_starknet_error("starknet_addInvokeTransaction", 59, "A transaction with the same hash already exists")
+            .with_priority(1)
+            .expect(1)
+            .mount(&mock_server)
+            .await;
+        let config =
+            StarknetConfig { starknet_chain_id: Some(STARKNET_CHAIN_ID), ..mock_starknet_config(&mock_server.uri()) };
+        let client = KakarotClient::new(config).unwrap();
+        let request = BroadcastedInvokeTransactionV1 {
+            max_fee: *MAX_FEE,
+            signature: vec![],
+            nonce: FieldElement::ZERO,
+            sender_address: SENDER_STARKNET_ADDRESS,
+            calldata: raw_starknet_calldata(KAKAROT_ADDRESS, Bytes::from_str(RAW_TRANSACTION).unwrap()),
+        };
+        let original_hash = compute_invoke_v1_transaction_hash(STARKNET_CHAIN_ID, &request);
+
+        let hash = client.submit_starknet_transaction(request).await.unwrap();
+
+        assert_eq!(hash, H256::from(original_hash.to_bytes_be()));
+    }
+
+    #[test]
+    fn test_submission_queue_requires_the_starknet_chain_id() {
+        let config = StarknetConfig { submission_queue_capacity: 1, ..mock_starknet_config("http://localhost:5050") };
+
+        assert!(KakarotClient::new(config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_and_receipt_read_the_sender_at_their_block() {
+        let mock_server = setup_mock_server().await;
+        // The sender of the fixture transaction is only known as 0xe0a at the block of its receipt
+        mock_evm_address_of_at(SENDER_STARKNET_ADDRESS, felt!("0xe0a"), BlockId::Hash(felt!("0xd")))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        let receipt = client.transaction_receipt(hash).await.unwrap().unwrap();
+        let transaction = client.transaction_by_hash(hash).await.unwrap();
+
+        assert_eq!(receipt.from, Address::from_low_u64_be(0xe0a));
+        assert_eq!(transaction.from, Address::from_low_u64_be(0xe0a));
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_skip_non_kakarot_transactions() {
+        let mock_server = setup_mock_server().await;
+        let block_hash = FieldElement::from(0xb10cu64);
+        let other_sender = FieldElement::from(0x123u64);
+        let mut other_transaction = fixture_transaction();
+        other_transaction["transaction_hash"] = serde_json::json!("0x1234");
+        other_transaction["sender_address"] = serde_json::json!(format!("{other_sender:#x}"));
+        for mock in
+            mock_block_with_transactions(BlockId::Hash(block_hash), vec![other_transaction, fixture_transaction()])
+        {
+            mock.mount(&mock_server).await;
+        }
+        mock_class_hash_at(other_sender, FieldElement::from(0xc1a55u64)).mount(&mock_server).await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        let receipts = client.block_receipts(BlockId::Hash(block_hash)).await.unwrap();
+
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(
+            receipts[0].transaction_hash,
+            Some(H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap())
+        );
+        assert_eq!(receipts[0].transaction_index, Some(U256::from(1)));
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_propagate_upstream_errors() {
+        let mock_server = setup_mock_server().await;
+        let block_hash = FieldElement::from(0xb10cu64);
+        let mut failing_transaction = fixture_transaction();
+        failing_transaction["transaction_hash"] = serde_json::json!("0xbad");
+        for mock in
+            mock_block_with_transactions(BlockId::Hash(block_hash), vec![fixture_transaction(), failing_transaction])
+        {
+            mock.mount(&mock_server).await;
+        }
+        Mock::given(method("POST"))
+            .and(body_partial_json(
+                serde_json::json!({ "method": "starknet_getTransactionReceipt", "params": ["0xbad"] }),
+            ))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&mock_server)
+            .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        assert!(client.block_receipts(BlockId::Hash(block_hash)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_block_receipts_use_the_gas_of_the_fees_paid_before_them() {
+        let mock_server = setup_mock_server().await;
+        let block_hash = felt!("0xb10c");
+        let (other_sender, other_hash) = (felt!("0x123"), felt!("0x1234"));
+        let fixture_hash = felt!("0x3204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c");
+        let mut other_transaction = fixture_transaction();
+        other_transaction["transaction_hash"] = serde_json::json!(format!("{other_hash:#x}"));
+        other_transaction["sender_address"] = serde_json::json!(format!("{other_sender:#x}"));
+        for mock in
+            mock_block_with_transactions(BlockId::Hash(block_hash), vec![other_transaction, fixture_transaction()])
+        {
+            mock.mount(&mock_server).await;
+        }
+        mock_class_hash_at(other_sender, FieldElement::from(0xc1a55u64)).mount(&mock_server).await;
+        let transfer = |transaction_hash: FieldElement, value: u64| {
+            serde_json::json!({
+                "from_address": STARKNET_NATIVE_TOKEN,
+                "keys": [format!("{:#x}", get_selector_from_name("Transfer").unwrap())],
+                "data": ["0x1", "0x2", format!("{value:#x}"), "0x0"],
+                "block_hash": format!("{block_hash:#x}"),
+                "block_number": 3,
+                "transaction_hash": format!("{transaction_hash:#x}")
+            })
+        };
+        // The other transaction transfers tokens before paying its fee, the last transfer
+        let block_id = serde_json::json!({ "block_hash": format!("{block_hash:#x}") });
+        mock_events(
+            serde_json::json!({ "from_block": block_id, "to_block": block_id, "address": STARKNET_NATIVE_TOKEN }),
+            serde_json::json!([
+                transfer(other_hash, 0x5),
+                transfer(other_hash, 0x100),
+                transfer(fixture_hash, 0x0451_4f14_cba8_00)
+            ]),
+        )
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        let receipts = client.block_receipts(BlockId::Hash(block_hash)).await.unwrap();
+
+        // The gas used times the gas price of the receipt is the fee paid
+        let fee = U256::from(0x0451_4f14_cba8_00u64);
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].gas_used, Some(fee / U256::from(BASE_FEE_PER_GAS)));
+        assert_eq!(receipts[0].cumulative_gas_used, (U256::from(0x100) + fee) / U256::from(BASE_FEE_PER_GAS));
+    }
+
+    #[tokio::test]
+    async fn test_transaction_not_wrapping_an_evm_transaction_is_served_with_its_calldata() {
+        let client = setup_mock_client_crate().await;
+        let mut transaction = fixture_transaction();
+        // A multicall without any call
+        transaction["calldata"] = serde_json::json!(["0x0"]);
+        let transaction: Transaction = serde_json::from_value(transaction).unwrap();
+
+        let eth_tx =
+            StarknetTransaction::from(transaction).to_eth_transaction(&client, None, None, None).await.unwrap();
+
+        assert_eq!(eth_tx.input, Bytes::from(vec![0u8; 32]));
+        assert_eq!(eth_tx.to, None);
+        assert_eq!(eth_tx.signature, None);
+    }
+
+    #[tokio::test]
+    async fn test_requests_about_pruned_blocks_are_served_by_the_fallback_node() {
+        // The Starknet node pruned the block of the transaction, which the fallback node serves,
+        // and is still catching up with the fallback node, at block 19640, so it does not know
+        // the transaction either
+        let pruned_node = MockServer::start().await;
+        mount_block_number(&pruned_node, 19_000, None).await;
+        for starknet_method in ["starknet_getTransactionReceipt", "starknet_getTransactionByHash"] {
+            mock_starknet_error(starknet_method, 25, "Transaction hash not found").mount(&pruned_node).await;
+        }
+        for starknet_method in ["starknet_getBlockWithTxs", "starknet_call", "starknet_getClassHashAt"] {
+            mock_starknet_error(starknet_method, 24, "Block not found").mount(&pruned_node).await;
+        }
+        let fallback_rpc = setup_wiremock().await;
+        let config =
+            StarknetConfig { fallback_starknet_rpc: Some(fallback_rpc), ..mock_starknet_config(&pruned_node.uri()) };
+        let client = KakarotClient::new(config).unwrap();
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        let receipt = client.transaction_receipt(hash).await.unwrap().unwrap();
+
+        assert_eq!(receipt.transaction_hash, Some(hash));
+        assert_eq!(receipt.from, Address::from_str("0x54b288676b749def5fc10eb17244fe2c87375de1").unwrap());
+        assert!(!pruned_node.received_requests().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unknown_transaction_is_not_looked_up_on_a_fallback_node_which_is_not_ahead() {
+        let primary_node = MockServer::start().await;
+        mount_block_number(&primary_node, 19_640, None).await;
+        mock_starknet_error("starknet_getTransactionReceipt", 25, "Transaction hash not found")
+            .mount(&primary_node)
+            .await;
+        // The fallback node is at the same block 19640, so it is not asked for the transaction
+        let fallback_node = setup_wiremock().await;
+        let config =
+            StarknetConfig { fallback_starknet_rpc: Some(fallback_node), ..mock_starknet_config(&primary_node.uri()) };
+        let client = KakarotClient::new(config).unwrap();
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        let err = client.transaction_receipt(hash).await.unwrap_err();
+
+        assert!(is_transaction_not_found_error(&err), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_log_filter_changes_are_bounded_by_the_filter_blocks() {
+        let mock_server = setup_mock_server().await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+        // Both filters are installed at the fixture block 19640
+        let bounded = client.new_filter(Filter::new().to_block(BlockNumberOrTag::Number(19_642))).await.unwrap();
+        let elapsed = client.new_filter(Filter::new().to_block(BlockNumberOrTag::Number(19_640))).await.unwrap();
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "starknet_blockNumber" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": 19_645
+            })))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        mock_events(
+            serde_json::json!({ "from_block": { "block_number": 19_641 }, "to_block": { "block_number": 19_642 } }),
+            serde_json::json!([]),
+        )
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        // No other block is queried
+        mock_events(serde_json::json!({}), serde_json::json!([])).with_priority(2).expect(0).mount(&mock_server).await;
+
+        assert_eq!(client.filter_changes(bounded).await.unwrap(), FilterChanges::Logs(vec![]));
+        assert_eq!(client.filter_changes(elapsed).await.unwrap(), FilterChanges::Logs(vec![]));
+    }
+
+    #[tokio::test]
+    async fn test_event_pages_are_all_fetched_from_the_node_serving_the_first_one() {
+        // The Starknet node pruned the blocks, and does not know the continuation tokens of the
+        // fallback node
+        let pruned_node = MockServer::start().await;
+        mock_starknet_error("starknet_getEvents", 24, "Block not found").expect(1).mount(&pruned_node).await;
+        let fallback_node = MockServer::start().await;
+        let event = |transaction_hash: &str| {
+            serde_json::json!({
+                "from_address": format!("{KAKAROT_ADDRESS:#x}"),
+                "keys": ["0x1"],
+                "data": [],
+                "block_hash": "0xd",
+                "block_number": 3,
+                "transaction_hash": transaction_hash
+            })
+        };
+        for (continuation_token, events, next_token, priority) in [
+            (serde_json::json!({}), vec![event("0x1")], serde_json::json!("1"), 2),
+            (serde_json::json!({ "continuation_token": "1" }), vec![event("0x2")], serde_json::Value::Null, 1),
+        ] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(
+                    serde_json::json!({ "method": "starknet_getEvents", "params": [continuation_token] }),
+                ))
+                .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": { "events": events, "continuation_token": next_token },
+                })))
+                .with_priority(priority)
+                .expect(1)
+                .mount(&fallback_node)
+                .await;
+        }
+        let config = StarknetConfig {
+            fallback_starknet_rpc: Some(fallback_node.uri()),
+            ..mock_starknet_config(&pruned_node.uri())
+        };
+        let client = KakarotClient::new(config).unwrap();
+
+        let events = client.get_kakarot_events(3, 3, None).await.unwrap();
+
+        let hashes = events.iter().map(|event| event.transaction_hash).collect::<Vec<_>>();
+        assert_eq!(hashes, vec![felt!("0x1"), felt!("0x2")]);
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_from_earliest_block_starts_at_genesis() {
+        let mock_server = setup_mock_server().await;
+        let event = serde_json::json!({
+            "from_address": format!("{KAKAROT_ADDRESS:#x}"),
+            "keys": ["0x1", "0x0", "0xabc"],
+            "data": [],
+            "block_hash": "0xd",
+            "block_number": 3,
+            "transaction_hash": "0x3204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c"
+        });
+        mock_events(
+            serde_json::json!({ "from_block": { "block_number": 0 }, "to_block": { "block_number": 5 } }),
+            serde_json::json!([event]),
+        )
+        .with_priority(1)
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+        let filter = Filter::new().from_block(BlockNumberOrTag::Earliest).to_block(BlockNumberOrTag::Number(5));
+
+        let logs = client.get_logs(filter).await.unwrap();
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].address, Address::from_low_u64_be(0xabc));
+        assert_eq!(logs[0].topics, vec![H256::from_low_u64_be(1)]);
+        assert_eq!(logs[0].block_number, Some(U256::from(3)));
+        assert_eq!(logs[0].transaction_index, Some(U256::ZERO));
+        assert_eq!(logs[0].log_index, Some(U256::ZERO));
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_are_indexed_in_their_block() {
+        let mock_server = setup_mock_server().await;
+        let block_hash = felt!("0xb10c");
+        let (other_hash, fixture_hash) =
+            (felt!("0x1234"), felt!("0x3204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c"));
+        let mut other_transaction = fixture_transaction();
+        other_transaction["transaction_hash"] = serde_json::json!(format!("{other_hash:#x}"));
+        for mock in
+            mock_block_with_transactions(BlockId::Hash(block_hash), vec![other_transaction, fixture_transaction()])
+        {
+            mock.mount(&mock_server).await;
+        }
+        let kakarot_event = |topic: u64| {
+            serde_json::json!({
+                "from_address": format!("{KAKAROT_ADDRESS:#x}"),
+                "keys": [format!("{topic:#x}"), "0x0", "0xabc"],
+                "data": []
+            })
+        };
+        // The other transaction emits one log, the fixture transaction two
+        let emitted_events = [(other_hash, 1), (fixture_hash, 2), (fixture_hash, 3)]
+            .into_iter()
+            .map(|(hash, topic)| {
+                let mut event = kakarot_event(topic);
+                event["block_hash"] = serde_json::json!(format!("{block_hash:#x}"));
+                event["block_number"] = serde_json::json!(3);
+                event["transaction_hash"] = serde_json::json!(format!("{hash:#x}"));
+                event
+            })
+            .collect::<Vec<_>>();
+        let block_id = serde_json::json!({ "block_hash": format!("{block_hash:#x}") });
+        mock_events(
+            serde_json::json!({ "from_block": block_id, "to_block": block_id }),
+            serde_json::json!(emitted_events),
+        )
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+        let filter = Filter::new().at_block_hash(H256::from_low_u64_be(0xb10c));
+
+        let logs = client.get_logs(filter).await.unwrap();
+
+        let indexes = logs.iter().map(|log| (log.transaction_index, log.log_index)).collect::<Vec<_>>();
+        assert_eq!(
+            indexes,
+            vec![
+                (Some(U256::ZERO), Some(U256::ZERO)),
+                (Some(U256::from(1)), Some(U256::from(2))),
+                (Some(U256::from(1)), Some(U256::from(3))),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_propagate_conversion_errors() {
+        let mock_server = setup_mock_server().await;
+        // A Kakarot event without the key holding the EVM address of its emitter
+        let event = serde_json::json!({
+            "from_address": format!("{KAKAROT_ADDRESS:#x}"),
+            "keys": [],
+            "data": [],
+            "block_hash": "0xd",
+            "block_number": 3,
+            "transaction_hash": "0x3204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c"
+        });
+        mock_events(serde_json::json!({}), serde_json::json!([event])).with_priority(1).mount(&mock_server).await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+        let filter = Filter::new().from_block(BlockNumberOrTag::Number(0)).to_block(BlockNumberOrTag::Number(5));
+
+        assert!(client.get_logs(filter).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_over_max_range_is_rejected() {
+        let client = setup_mock_client_crate().await;
+        let filter = Filter::new()
+            .from_block(BlockNumberOrTag::Number(0))
+            .to_block(BlockNumberOrTag::Number(MAX_LOGS_BLOCK_RANGE));
+
+        let err = client.get_logs(filter).await.unwrap_err();
+
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+    }
+
+    #[tokio::test]
+    async fn test_get_logs_with_inverted_range_is_rejected() {
+        let client = setup_mock_client_crate().await;
+        let filter = Filter::new().from_block(BlockNumberOrTag::Number(5)).to_block(BlockNumberOrTag::Number(4));
+
+        let err = client.get_logs(filter).await.unwrap_err();
+
+        assert!(matches!(err, EthApiError::InvalidParams(_)));
+    }
+
+    #[tokio::test]
+    async fn test_block_logs_bloom_covers_the_logs_of_the_block() {
+        let mock_server = setup_mock_server().await;
+        let block_hash = felt!("0x449aa33ad836b65b10fa60082de99e24ac876ee2fd93e723a99190a530af0a9");
+        let block_id = serde_json::json!({ "block_hash": format!("{block_hash:#x}") });
+        let event = serde_json::json!({
+            "from_address": format!("{KAKAROT_ADDRESS:#x}"),
+            "keys": ["0x1", "0x0", "0xabc"],
+            "data": [],
+            "block_hash": format!("{block_hash:#x}"),
+            "block_number": 19612,
+            "transaction_hash": "0x3204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c"
+        });
+        mock_events(serde_json::json!({ "from_block": block_id, "to_block": block_id }), serde_json::json!([event]))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        let block = client.get_eth_block_from_starknet_block(BlockId::Hash(block_hash), true).await.unwrap();
+
+        assert_ne!(block.header.logs_bloom, Bloom::default());
+    }
+
+    #[tokio::test]
+    async fn test_block_logs_bloom_propagates_conversion_errors() {
+        let mock_server = setup_mock_server().await;
+        let block_hash = felt!("0x449aa33ad836b65b10fa60082de99e24ac876ee2fd93e723a99190a530af0a9");
+        // A Kakarot event without the key holding the EVM address of its emitter
+        let event = serde_json::json!({
+            "from_address": format!("{KAKAROT_ADDRESS:#x}"),
+            "keys": [],
+            "data": [],
+            "block_hash": format!("{block_hash:#x}"),
+            "block_number": 19612,
+            "transaction_hash": "0x3204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c"
+        });
+        mock_events(serde_json::json!({}), serde_json::json!([event])).with_priority(1).mount(&mock_server).await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        assert!(client.get_eth_block_from_starknet_block(BlockId::Hash(block_hash), true).await.is_err());
+    }
+
+    /// Mounts `starknet_blockNumber` returning `number`, `times` times or for every call if `None`.
+    async fn mount_block_number(mock_server: &MockServer, number: u64, times: Option<u64>) {
+        let mock = Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "starknet_blockNumber" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": number
+            })));
+        match times {
+            Some(times) => mock.up_to_n_times(times).with_priority(1).mount(mock_server).await,
+            None => mock.mount(mock_server).await,
+        }
+    }
+
+    /// Returns the next block or reorg followed, as its number and hash or as the first block
+    /// reverted.
+    async fn next_followed(events: &mut broadcast::Receiver<ClientEvent>) -> Result<(u64, H256), u64> {
+        loop {
+            match tokio::time::timeout(Duration::from_secs(5), events.recv()).await.unwrap().unwrap() {
+                ClientEvent::NewBlock { block, .. } => {
+                    return Ok((block.header.number.unwrap().to::<u64>(), block.header.hash.unwrap()));
+                }
+                ClientEvent::ReorgDetected { from_block } => return Err(from_block),
+                _ => {}
+            }
+        }
+    }
+
+    /// Starts a client following an empty chain, returning it with the mock node it follows.
+    async fn setup_followed_chain() -> (MockServer, Arc<dyn KakarotProvider>) {
+        let mock_server = MockServer::start().await;
+        mock_events(serde_json::json!({}), serde_json::json!([])).mount(&mock_server).await;
+        let client: Arc<dyn KakarotProvider> =
+            Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap());
+        (mock_server, client)
+    }
+
+    #[tokio::test]
+    async fn test_follower_follows_every_block() {
+        let (mock_server, client) = setup_followed_chain().await;
+        mount_block_number(&mock_server, 1, Some(1)).await;
+        mount_block_number(&mock_server, 3, None).await;
+        for (number, hash, parent_hash) in
+            [(1, felt!("0x1"), felt!("0x0")), (2, felt!("0x2"), felt!("0x1")), (3, felt!("0x3"), felt!("0x2"))]
+        {
+            for mock in mock_chain_block(number, hash, parent_hash) {
+                mock.mount(&mock_server).await;
+            }
+        }
+        let mut events = client.events().subscribe();
+
+        client.follower().start(client.clone(), Duration::from_millis(10), None);
+
+        for number in 1..=3 {
+            assert_eq!(next_followed(&mut events).await, Ok((number, H256::from_low_u64_be(number))));
+        }
+        client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_follower_reports_head_regression_as_reorg() {
+        let (mock_server, client) = setup_followed_chain().await;
+        mount_block_number(&mock_server, 3, Some(1)).await;
+        mount_block_number(&mock_server, 2, None).await;
+        for (number, hash, parent_hash) in [(2, felt!("0x2"), felt!("0x1")), (3, felt!("0x3"), felt!("0x2"))] {
+            for mock in mock_chain_block(number, hash, parent_hash) {
+                mock.mount(&mock_server).await;
+            }
+        }
+        let mut events = client.events().subscribe();
+
+        client.follower().start(client.clone(), Duration::from_millis(10), None);
+
+        assert_eq!(next_followed(&mut events).await, Ok((3, H256::from_low_u64_be(3))));
+        // The node went back to block 2, so block 3 was dropped and block 2 is followed again
+        assert_eq!(next_followed(&mut events).await, Err(2));
+        assert_eq!(next_followed(&mut events).await, Ok((2, H256::from_low_u64_be(2))));
+        client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_follower_follows_a_replaced_parent_again() {
+        let (mock_server, client) = setup_followed_chain().await;
+        mount_block_number(&mock_server, 1, Some(1)).await;
+        mount_block_number(&mock_server, 2, None).await;
+        // Block 1 is replaced by block 0xb1 once followed
+        for mock in mock_chain_block(1, felt!("0xa1"), felt!("0x0")) {
+            mock.up_to_n_times(1).with_priority(1).mount(&mock_server).await;
+        }
+        for (number, hash, parent_hash) in [(1, felt!("0xb1"), felt!("0x0")), (2, felt!("0x2"), felt!("0xb1"))] {
+            for mock in mock_chain_block(number, hash, parent_hash) {
+                mock.mount(&mock_server).await;
+            }
+        }
+        let mut events = client.events().subscribe();
+
+        client.follower().start(client.clone(), Duration::from_millis(10), None);
+
+        assert_eq!(next_followed(&mut events).await, Ok((1, H256::from_low_u64_be(0xa1))));
+        assert_eq!(next_followed(&mut events).await, Err(1));
+        assert_eq!(next_followed(&mut events).await, Ok((1, H256::from_low_u64_be(0xb1))));
+        assert_eq!(next_followed(&mut events).await, Ok((2, H256::from_low_u64_be(2))));
+        client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_follower_tracks_the_last_block_accepted_on_l1() {
+        let (mock_server, client) = setup_followed_chain().await;
+        mount_block_number(&mock_server, 3, None).await;
+        for mock in mock_chain_block(3, felt!("0x3"), felt!("0x2")) {
+            mock.mount(&mock_server).await;
+        }
+        for number in [1, 2] {
+            let mut block: serde_json::Value = serde_json::from_str(include_str!(
+                "../src/mock/fixtures/responses/blocks/starknet_getBlockWithTxHashes.json"
+            ))
+            .unwrap();
+            block["result"]["block_number"] = serde_json::json!(number);
+            block["result"]["status"] = serde_json::json!("ACCEPTED_ON_L1");
+            Mock::given(method("POST"))
+                .and(body_partial_json(serde_json::json!({
+                    "method": "starknet_getBlockWithTxHashes",
+                    "params": [{ "block_number": number }]
+                })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(block))
+                .with_priority(1)
+                .mount(&mock_server)
+                .await;
+        }
+        let mut events = client.events().subscribe();
+
+        // The block is not tracked until the chain is followed
+        assert_eq!(client.follower().last_l1_accepted_block(), None);
+        client.follower().start(client.clone(), Duration::from_millis(10), None);
+        assert_eq!(next_followed(&mut events).await, Ok((3, H256::from_low_u64_be(3))));
+
+        let tracked = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Some(block_number) = client.follower().last_l1_accepted_block() {
+                    return block_number;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(tracked, 2);
+        client.follower().stop();
+        assert_eq!(client.follower().last_l1_accepted_block(), None);
+    }
+
+    #[tokio::test]
+    async fn test_follower_reports_its_lag_behind_the_node() {
+        let (mock_server, client) = setup_followed_chain().await;
+        mount_block_number(&mock_server, 1, Some(1)).await;
+        mount_block_number(&mock_server, 3, None).await;
+        // Block 2 is not served, so the follower stays behind at block 1
+        for (number, hash, parent_hash) in [(1, felt!("0x1"), felt!("0x0")), (3, felt!("0x3"), felt!("0x2"))] {
+            for mock in mock_chain_block(number, hash, parent_hash) {
+                mock.mount(&mock_server).await;
+            }
+        }
+        let mut events = client.events().subscribe();
+
+        assert_eq!(client.follower().lag(), None);
+        client.follower().start(client.clone(), Duration::from_millis(10), None);
+        assert_eq!(next_followed(&mut events).await, Ok((1, H256::from_low_u64_be(1))));
+
+        // Blocks 2 and 3 are left to follow once the node moved to block 3
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while client.follower().lag() != Some(2) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        client.follower().stop();
+        assert_eq!(client.follower().lag(), None);
+    }
+
+    #[tokio::test]
+    async fn test_chain_metadata() {
+        let starknet_rpc = setup_wiremock().await;
+        let public_rpc_url = format!("{starknet_rpc}/public");
+        let config = StarknetConfig { rpc_urls: vec![public_rpc_url.clone()], ..mock_starknet_config(&starknet_rpc) };
+        let client = KakarotClient::new(config).unwrap();
+
+        // Without block explorer, `blockExplorerUrls` is omitted
+        assert_eq!(
+            serde_json::to_value(client.chain_metadata()).unwrap(),
+            serde_json::json!({
+                "chainId": format!("{CHAIN_ID:#x}"),
+                "chainName": DEFAULT_CHAIN_NAME,
+                "rpcUrls": [public_rpc_url],
+                "nativeCurrency": {
+                    "name": DEFAULT_NATIVE_CURRENCY_NAME,
+                    "symbol": DEFAULT_NATIVE_CURRENCY_SYMBOL,
+                    "decimals": NATIVE_CURRENCY_DECIMALS,
+                },
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_chain_metadata_falls_back_to_the_adapter_url() {
+        let starknet_rpc = setup_wiremock().await;
+        let adapter_url = format!("{starknet_rpc}/adapter");
+        let public_rpc_url = format!("{starknet_rpc}/public");
+
+        // EIP-3085 rejects empty `rpcUrls`, the adapter URL is served instead
+        let config = mock_starknet_config(&starknet_rpc).with_fallback_rpc_url(adapter_url.clone());
+        let client = KakarotClient::new(config).unwrap();
+        assert_eq!(client.chain_metadata().rpc_urls, vec![adapter_url.clone()]);
+
+        // The configured URLs are kept
+        let config = StarknetConfig { rpc_urls: vec![public_rpc_url.clone()], ..mock_starknet_config(&starknet_rpc) }
+            .with_fallback_rpc_url(adapter_url);
+        let client = KakarotClient::new(config).unwrap();
+        assert_eq!(client.chain_metadata().rpc_urls, vec![public_rpc_url]);
+    }
+
+    #[tokio::test]
+    async fn test_starknet_block_info() {
+        let client = setup_mock_client_crate().await;
+        let block_hash =
+            FieldElement::from_hex_be("0x0197be2810df6b5eedd5d9e468b200d0b845b642b81a44755e19047f08cc8c6e").unwrap();
+
+        let block_info = client.starknet_block_info(BlockId::Hash(block_hash)).await.unwrap();
+
+        assert_eq!(block_info.block_hash, Some(block_hash));
+        assert_eq!(block_info.block_number, Some(19639));
+        assert_eq!(
+            block_info.sequencer_address,
+            FieldElement::from_hex_be("0x5dcd266a80b8a5f29f04d779c6b166b80150c24f2180a75e82427242dab20a9").unwrap()
+        );
+        assert_eq!(block_info.timestamp, 1_675_496_282);
+    }
+
+    #[tokio::test]
+    async fn test_zero_request_timeout_is_rejected() {
+        let starknet_rpc = setup_wiremock().await;
+        let config = StarknetConfig { request_timeout_ms: 0, ..mock_starknet_config(&starknet_rpc) };
+
+        assert!(KakarotClient::new(config).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sdk_wait_for_receipt_of_included_transaction() {
+        let provider = KakarotEthProvider::new(Arc::new(setup_mock_client_crate().await));
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        let receipt = provider.wait_for_receipt(hash, Duration::from_millis(10), Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(receipt.transaction_hash, Some(hash));
+    }
+
+    #[tokio::test]
+    async fn test_sdk_wait_for_receipt_of_transaction_not_yet_received() {
+        let mock_server = setup_mock_server().await;
+        // The node does not know the transaction for the first two lookups
+        mock_starknet_error("starknet_getTransactionReceipt", 25, "Transaction hash not found")
+            .up_to_n_times(2)
+            .with_priority(1)
+            .expect(2)
+            .mount(&mock_server)
+            .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+        let provider = KakarotEthProvider::new(Arc::new(client));
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        let receipt = provider.wait_for_receipt(hash, Duration::from_millis(10), Duration::from_secs(5)).await.unwrap();
+
+        assert_eq!(receipt.transaction_hash, Some(hash));
+    }
+
+    #[tokio::test]
+    async fn test_recorded_exchanges_are_replayed() {
+        let starknet_rpc = setup_wiremock().await;
+        let transport = RecordingTransport::new(HttpTransport::new(Url::parse(&starknet_rpc).unwrap()));
+        let recording = transport.recording();
+        let block_number = JsonRpcClient::new(transport).block_number().await.unwrap();
+
+        let fixture = std::env::temp_dir().join("kakarot_recorded_exchanges.json");
+        recording.save(&fixture).unwrap();
+        let exchanges = load_exchanges(&fixture).unwrap();
+        assert_eq!(exchanges, recording.exchanges());
+
+        let mock_server = MockServer::start().await;
+        mount_exchanges(&mock_server, &exchanges).await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        assert_eq!(client.block_number().await.unwrap().as_u64(), block_number);
+    }
+
+    #[tokio::test]
+    async fn test_watchlist_events_are_posted_to_the_webhooks() {
+        let webhook = MockServer::start().await;
+        Mock::given(method("POST")).respond_with(ResponseTemplate::new(200)).expect(2).mount(&webhook).await;
+        let activity = AddressActivity {
+            address: Address::from_low_u64_be(1),
+            block_hash: H256::from_low_u64_be(2),
+            block_number: U64::from(3),
+            transaction_hash: H256::from_low_u64_be(4),
+        };
+
+        let (sender, events) = broadcast::channel(16);
+        let delivery = tokio::spawn(deliver_webhooks(events, vec![Url::parse(&webhook.uri()).unwrap()]));
+        sender.send(ClientEvent::AddressActivity(activity.clone())).unwrap();
+        sender.send(ClientEvent::ReorgDetected { from_block: 3 }).unwrap();
+        sender.send(ClientEvent::AddressActivityReverted(activity.clone())).unwrap();
+        // Closing the event bus stops the delivery once every event is posted
+        drop(sender);
+        delivery.await.unwrap();
+
+        let posted: Vec<WatchlistEvent> = webhook
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|request| serde_json::from_slice(&request.body).unwrap())
+            .collect();
+        assert_eq!(posted, vec![WatchlistEvent::Added(activity.clone()), WatchlistEvent::Removed(activity)]);
+    }
+
+    #[tokio::test]
+    async fn test_pending_transactions_are_pushed_once_converted() {
+        let mock_server = setup_mock_server().await;
+        let (other_sender, flaky_sender) = (felt!("0x123"), felt!("0xf1a"));
+        let mut other_transaction = fixture_transaction();
+        other_transaction["transaction_hash"] = serde_json::json!("0x1234");
+        other_transaction["sender_address"] = serde_json::json!(format!("{other_sender:#x}"));
+        let mut flaky_transaction = fixture_transaction();
+        flaky_transaction["transaction_hash"] = serde_json::json!("0xf1a");
+        flaky_transaction["sender_address"] = serde_json::json!(format!("{flaky_sender:#x}"));
+        mock_pending_block(vec![other_transaction, flaky_transaction, fixture_transaction()]).mount(&mock_server).await;
+        mock_class_hash_at(other_sender, felt!("0xc1a55")).mount(&mock_server).await;
+        // The class of the sender of the flaky transaction fails to be fetched on first poll
+        Mock::given(method("POST"))
+            .and(StarknetRpcAtAnyBlock::new("starknet_getClassHashAt", vec![serde_json::json!("0xf1a")], 0))
+            .respond_with(ResponseTemplate::new(500))
+            .up_to_n_times(1)
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        mock_class_hash_at(flaky_sender, PROXY_ACCOUNT_CLASS_HASH).mount(&mock_server).await;
+        mock_evm_address_of(flaky_sender, felt!("0xe0a")).mount(&mock_server).await;
+        let client: Arc<dyn KakarotProvider> =
+            Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap());
+        let subscriptions = SubscriptionManager::new(client, Duration::from_millis(10), None);
+
+        let mut pending_transactions = subscriptions.subscribe_pending_transactions();
+
+        let timeout = Duration::from_secs(5);
+        assert_eq!(
+            tokio::time::timeout(timeout, pending_transactions.recv()).await.unwrap().unwrap(),
+            H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap()
+        );
+        // The flaky transaction is pushed on next poll, and the others are not pushed again
+        assert_eq!(
+            tokio::time::timeout(timeout, pending_transactions.recv()).await.unwrap().unwrap(),
+            H256::from_low_u64_be(0xf1a)
+        );
+        assert!(tokio::time::timeout(Duration::from_millis(100), pending_transactions.recv()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_without_rewards_fetches_no_block() {
+        let (mock_server, client) = setup_followed_chain().await;
+        mount_block_number(&mock_server, 3, None).await;
+        let transfer = |block_number: u64, transaction_hash: u64, value: u64| {
+            serde_json::json!({
+                "from_address": STARKNET_NATIVE_TOKEN,
+                "keys": [format!("{:#x}", get_selector_from_name("Transfer").unwrap())],
+                "data": ["0x1", "0x2", format!("{value:#x}"), "0x0"],
+                "block_hash": format!("{block_number:#x}"),
+                "block_number": block_number,
+                "transaction_hash": format!("{transaction_hash:#x}")
+            })
+        };
+        // The block 1 is empty, and the second transaction of the block 2 transfers tokens before
+        // paying its fee
+        mock_events(
+            serde_json::json!({ "address": STARKNET_NATIVE_TOKEN }),
+            serde_json::json!([
+                transfer(0, 0xa, 250_000),
+                transfer(2, 0xb, 100_000),
+                transfer(2, 0xc, 5),
+                transfer(2, 0xc, 400_000)
+            ]),
+        )
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+
+        let fee_history = client.fee_history(U256::from(10), BlockNumberOrTag::Number(2), None).await.unwrap();
+
+        // The range is clamped to the blocks up to the newest block
+        assert_eq!(fee_history.oldest_block, U256::ZERO);
+        assert_eq!(fee_history.base_fee_per_gas, vec![U256::from(BASE_FEE_PER_GAS); 4]);
+        // The gas used is the fee paid at a gas price of 1 wei, over the gas limit of 1_000_000
+        assert_eq!(fee_history.gas_used_ratio, vec![0.25, 0.0, 0.5]);
+        assert_eq!(fee_history.reward, None);
+        let requests = mock_server.received_requests().await.unwrap();
+        assert!(requests.iter().all(|request| {
+            let method = &serde_json::from_slice::<serde_json::Value>(&request.body).unwrap()["method"];
+            method == "starknet_blockNumber" || method == "starknet_getEvents"
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_samples_the_rewards_of_the_requested_blocks() {
+        let (mock_server, client) = setup_followed_chain().await;
+        mount_block_number(&mock_server, 3, None).await;
+        for (number, hash, parent_hash) in [(2, felt!("0x2"), felt!("0x1")), (3, felt!("0x3"), felt!("0x2"))] {
+            for mock in mock_chain_block(number, hash, parent_hash) {
+                mock.mount(&mock_server).await;
+            }
+        }
+
+        let fee_history =
+            client.fee_history(U256::from(2), BlockNumberOrTag::Latest, Some(vec![25.0, 75.0])).await.unwrap();
+
+        assert_eq!(fee_history.oldest_block, U256::from(2));
+        assert_eq!(fee_history.gas_used_ratio.len(), 2);
+        // The blocks are empty
+        assert_eq!(fee_history.reward, Some(vec![vec![U256::ZERO; 2]; 2]));
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_rewards_are_the_priority_fees_paid() {
+        let mock_server = setup_mock_server().await;
+        mount_block_number(&mock_server, 3, Some(u64::MAX)).await;
+        mock_events(serde_json::json!({}), serde_json::json!([])).mount(&mock_server).await;
+        for mock in mock_block_with_transactions(BlockId::Number(3), vec![fixture_transaction()]) {
+            mock.mount(&mock_server).await;
+        }
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        let fee_history =
+            client.fee_history(U256::from(1), BlockNumberOrTag::Latest, Some(vec![0.0, 50.0, 100.0])).await.unwrap();
+
+        // The fixture transaction pays a max fee and a max priority fee of 0xdead, above a base fee of 1
+        assert_eq!(fee_history.reward, Some(vec![vec![U256::from(0xdead - 1); 3]]));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_of_a_reverted_execution_has_a_failure_status_and_a_revert_reason() {
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+        let mock_server = setup_mock_server().await;
+        let mut receipt: serde_json::Value = serde_json::from_str(include_str!(
+            "../src/mock/fixtures/responses/transactions/starknet_getTransactionReceipt.json"
+        ))
+        .unwrap();
+        // The account of the sender reports an execution which reverted with 0xdead
+        receipt["result"]["events"].as_array_mut().unwrap().push(serde_json::json!({
+            "from_address": format!("{SENDER_STARKNET_ADDRESS:#x}"),
+            "keys": [format!("{TRANSACTION_EXECUTED:#x}")],
+            "data": ["0x2", "0xde", "0xad", "0x0", "0x5208"]
+        }));
+        Mock::given(method("POST"))
+            .and(body_partial_json(serde_json::json!({ "method": "starknet_getTransactionReceipt" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(receipt))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+        let receipt = client.transaction_receipt(hash).await.unwrap().unwrap();
+
+        assert_eq!(receipt.status_code, Some(U64::from(0)));
+        // The execution result is not a log
+        assert!(receipt.logs.is_empty());
+        assert_eq!(client.revert_reason(hash).await.unwrap(), Some("0xdead".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_receipt_status_is_failure_for_rejected_transactions_and_null_for_pending_ones() {
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+        for (status, status_code) in
+            [("REJECTED", Some(0_u64)), ("ACCEPTED_ON_L2", Some(1)), ("ACCEPTED_ON_L1", Some(1)), ("PENDING", None)]
+        {
+            let mock_server = setup_mock_server().await;
+            let mut receipt: serde_json::Value = serde_json::from_str(include_str!(
+                "../src/mock/fixtures/responses/transactions/starknet_getTransactionReceipt.json"
+            ))
+            .unwrap();
+            receipt["result"]["status"] = serde_json::json!(status);
+            Mock::given(method("POST"))
+                .and(body_partial_json(serde_json::json!({ "method": "starknet_getTransactionReceipt" })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(receipt))
+                .with_priority(1)
+                .mount(&mock_server)
+                .await;
+            let client = KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap();
+
+            let receipt = client.transaction_receipt(hash).await.unwrap().unwrap();
+
+            assert_eq!(receipt.status_code, status_code.map(U64::from), "status {status}");
+        }
+    }
 }
@@ -0,0 +1,58 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Environment variables overriding the commit read from git, for builds outside of a checkout,
+/// e.g. Docker images, or by CIs checking out a detached commit.
+const COMMIT_ENV_VARS: [&str; 2] = ["KAKAROT_RPC_COMMIT", "GITHUB_SHA"];
+
+/// Embeds the commit the adapter is built from, reported by `web3_clientVersion`.
+fn main() {
+    for name in COMMIT_ENV_VARS {
+        println!("cargo:rerun-if-env-changed={name}");
+    }
+    let commit = COMMIT_ENV_VARS
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|commit| !commit.trim().is_empty()))
+        .map(|commit| commit.trim().chars().take(7).collect::<String>())
+        .or_else(|| git(&["rev-parse", "--short", "HEAD"]));
+    if let Some(commit) = commit {
+        println!("cargo:rustc-env=KAKAROT_RPC_COMMIT={commit}");
+    }
+
+    if let Some(git_dir) = git(&["rev-parse", "--absolute-git-dir"]).map(PathBuf::from) {
+        rerun_if_head_moves(&git_dir);
+    }
+}
+
+/// Reruns the build script when `HEAD` is checked out to another commit or branch, or when the
+/// branch it points to moves, whether its ref is loose or packed.
+fn rerun_if_head_moves(git_dir: &Path) {
+    let head = git_dir.join("HEAD");
+    let mut watched = vec![git_dir.join("packed-refs")];
+    if let Some(reference) = std::fs::read_to_string(&head)
+        .ok()
+        .and_then(|head| head.strip_prefix("ref:").map(|reference| reference.trim().to_string()))
+    {
+        let reference = git_dir.join(reference);
+        // A packed ref is only written as a loose ref once the branch moves, in its directory
+        match reference.parent().filter(|_| !reference.exists()) {
+            Some(directory) => watched.push(directory.to_path_buf()),
+            None => watched.push(reference),
+        }
+    }
+    watched.push(head);
+    // A missing file would rerun the build script on every build
+    for path in watched.into_iter().filter(|path| path.exists()) {
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    Command::new("git")
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|output| output.trim().to_string())
+}
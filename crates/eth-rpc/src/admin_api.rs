@@ -0,0 +1,33 @@
+use jsonrpsee::core::{RpcResult as Result, SubscriptionResult};
+use jsonrpsee::proc_macros::rpc;
+use kakarot_rpc_core::models::activity::{AddressActivity, WatchlistEvent};
+use reth_primitives::Address;
+
+/// Admin API, for operators only, served on its own authenticated listener
+#[rpc(server, namespace = "admin")]
+pub trait AdminApi {
+    /// Watches an address, returning whether it was not watched yet.
+    #[method(name = "watchAddress")]
+    fn watch_address(&self, address: Address) -> Result<bool>;
+
+    /// Stops watching an address and drops its history, returning whether it was watched.
+    #[method(name = "unwatchAddress")]
+    fn unwatch_address(&self, address: Address) -> Result<bool>;
+
+    /// Returns the watched addresses.
+    #[method(name = "getWatchedAddresses")]
+    fn watched_addresses(&self) -> Result<Vec<Address>>;
+
+    /// Returns the recent activity of a watched address, the oldest first.
+    #[method(name = "getAddressActivity")]
+    fn address_activity(&self, address: Address) -> Result<Option<Vec<AddressActivity>>>;
+
+    /// Pushes the activities of the watched addresses in new blocks, and the activities removed
+    /// by reorgs.
+    #[subscription(
+        name = "subscribeAddressActivity" => "addressActivity",
+        unsubscribe = "unsubscribeAddressActivity",
+        item = WatchlistEvent
+    )]
+    async fn subscribe_address_activity(&self) -> SubscriptionResult;
+}
@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use jsonrpsee::core::{async_trait, RpcResult as Result, SubscriptionResult};
+use jsonrpsee::server::SubscriptionMessage;
+use jsonrpsee::PendingSubscriptionSink;
+use kakarot_rpc_core::client::client_api::KakarotProvider;
+use kakarot_rpc_core::client::errors::{rpc_err, EthRpcErrorCode};
+use kakarot_rpc_core::models::activity::{AddressActivity, WatchlistEvent};
+use reth_primitives::Address;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::admin_api::AdminApiServer;
+
+/// The RPC module for the `admin` namespace.
+pub struct KakarotAdminRpc {
+    pub kakarot_client: Arc<dyn KakarotProvider>,
+}
+
+impl KakarotAdminRpc {
+    #[must_use]
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>) -> Self {
+        Self { kakarot_client }
+    }
+}
+
+#[async_trait]
+impl AdminApiServer for KakarotAdminRpc {
+    fn watch_address(&self, address: Address) -> Result<bool> {
+        self.kakarot_client
+            .watchlist()
+            .watch(address)
+            .map_err(|err| rpc_err(EthRpcErrorCode::LimitExceeded as i32, err.to_string()).into())
+    }
+
+    fn unwatch_address(&self, address: Address) -> Result<bool> {
+        Ok(self.kakarot_client.watchlist().unwatch(address))
+    }
+
+    fn watched_addresses(&self) -> Result<Vec<Address>> {
+        Ok(self.kakarot_client.watchlist().addresses())
+    }
+
+    fn address_activity(&self, address: Address) -> Result<Option<Vec<AddressActivity>>> {
+        Ok(self.kakarot_client.watchlist().history(address))
+    }
+
+    async fn subscribe_address_activity(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let mut events = self.kakarot_client.events().subscribe();
+        let sink = pending.accept().await?;
+
+        loop {
+            let event = tokio::select! {
+                event = events.recv() => event,
+                // The subscriber disconnected
+                _ = sink.closed() => return Ok(()),
+            };
+            let event = match event {
+                Ok(event) => match WatchlistEvent::from_client_event(event) {
+                    Some(event) => event,
+                    None => continue,
+                },
+                // A subscriber missing activities is closed rather than served a partial history
+                Err(RecvError::Lagged(missed)) => {
+                    return Err(format!("the subscription missed {missed} events and was closed").into());
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            };
+            if sink.send(SubscriptionMessage::from_json(&event)?).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
@@ -1,17 +1,301 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
 use eyre::{eyre, Result};
+use kakarot_rpc_core::client::config::{
+    config_json_schema, ConfigField, ConfigValue, StarknetConfig, POSITIVE_PATTERN, UNSIGNED_PATTERN,
+};
+use serde_json::Value;
+use url::Url;
+
+use crate::rpc::KakarotRpcNamespace;
+
+const DEFAULT_SUBSCRIPTION_WORKER_THREADS: usize = 1;
+pub const DEFAULT_MAX_REQUEST_SIZE: u32 = 10 * 1024 * 1024;
+pub const DEFAULT_MAX_RESPONSE_SIZE: u32 = 10 * 1024 * 1024;
+pub const DEFAULT_MAX_CONNECTIONS: u32 = 100;
+pub const DEFAULT_MAX_SUBSCRIPTIONS: u32 = 1024;
+pub const DEFAULT_MAX_REQUESTS_PER_SECOND: u32 = 100;
+pub const DEFAULT_MAX_BUFFERED_MESSAGES: u32 = 1024;
 
 pub struct RPCConfig {
     pub socket_addr: String,
+    /// The RPC namespaces served, all of them by default.
+    pub namespaces: Vec<KakarotRpcNamespace>,
+    /// Number of threads of the runtime dedicated to the subscriptions, 0 to poll for them on the
+    /// runtime serving the requests.
+    pub subscription_worker_threads: usize,
+    /// Maximum size, in bytes, of an HTTP request body or a WebSocket message.
+    pub max_request_size: u32,
+    /// Maximum size, in bytes, of a response.
+    pub max_response_size: u32,
+    /// Maximum number of concurrent HTTP and WebSocket connections.
+    pub max_connections: u32,
+    /// Maximum number of subscriptions of a WebSocket connection.
+    pub max_subscriptions: u32,
+    /// Maximum number of HTTP requests per second of a connection.
+    pub max_requests_per_second: u32,
+    /// Maximum number of messages of a WebSocket connection waiting to be sent.
+    pub max_buffered_messages: u32,
+    /// Methods also served under another name, as `(alias, method)` pairs.
+    pub method_aliases: Vec<(String, String)>,
+    /// Maximum age, in milliseconds, of the latest block, block number and priority fee served
+    /// while they are refreshed in the background, 0 to always serve them fresh.
+    pub staleness_budget_ms: u64,
+    /// Maximum number of blocks the chain follower may be behind the Starknet node before the
+    /// adapter reports itself unhealthy, no maximum if `None`.
+    pub max_follower_lag: Option<u64>,
+    /// The address of the listener serving the `admin` namespace, and the bearer token its
+    /// callers must present. The namespace is not served if `None`.
+    pub admin: Option<(String, String)>,
+    /// The webhooks the activity of the watched addresses is posted to.
+    pub webhook_urls: Vec<Url>,
 }
 
 impl RPCConfig {
     pub fn new(socket_addr: String) -> RPCConfig {
-        RPCConfig { socket_addr }
+        RPCConfig {
+            socket_addr,
+            namespaces: KakarotRpcNamespace::DEFAULT.to_vec(),
+            subscription_worker_threads: DEFAULT_SUBSCRIPTION_WORKER_THREADS,
+            max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            max_subscriptions: DEFAULT_MAX_SUBSCRIPTIONS,
+            max_requests_per_second: DEFAULT_MAX_REQUESTS_PER_SECOND,
+            max_buffered_messages: DEFAULT_MAX_BUFFERED_MESSAGES,
+            method_aliases: Vec::new(),
+            staleness_budget_ms: 0,
+            max_follower_lag: None,
+            admin: None,
+            webhook_urls: Vec::new(),
+        }
+    }
+
+    /// Documents the environment variables read by [`RPCConfig::from_env`].
+    #[must_use]
+    /// The HTTP URL of the adapter, on the loopback interface when it listens on all of them.
+    #[must_use]
+    pub fn url(&self) -> String {
+        match self.socket_addr.parse::<SocketAddr>() {
+            Ok(mut socket_addr) if socket_addr.ip().is_unspecified() => {
+                let loopback = if socket_addr.is_ipv4() {
+                    IpAddr::V4(Ipv4Addr::LOCALHOST)
+                } else {
+                    IpAddr::V6(Ipv6Addr::LOCALHOST)
+                };
+                socket_addr.set_ip(loopback);
+                format!("http://{socket_addr}")
+            }
+            _ => format!("http://{}", self.socket_addr),
+        }
+    }
+
+    pub fn document() -> Vec<ConfigField> {
+        use ConfigValue::{Pattern, Text};
+        vec![
+            ConfigField::required(
+                "KAKAROT_HTTP_RPC_ADDRESS",
+                "Kakarot RPC address, serving both HTTP and WebSocket",
+                Text,
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_NAMESPACES",
+                "Comma separated RPC namespaces served, among eth, kakarot, debug, txpool, net, web3",
+                "eth,kakarot,debug,txpool,net,web3",
+                Pattern("^\\s*(eth|kakarot|debug|txpool|net|web3)\\s*(,\\s*(eth|kakarot|debug|txpool|net|web3)\\s*)*$"),
+            ),
+            ConfigField::optional(
+                "KAKAROT_SUBSCRIPTION_WORKER_THREADS",
+                "Threads of the runtime polling for subscriptions, 0 to share the request handling runtime",
+                DEFAULT_SUBSCRIPTION_WORKER_THREADS,
+                Pattern(UNSIGNED_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_MAX_REQUEST_SIZE",
+                "Maximum size, in bytes, of an HTTP request body or a WebSocket message",
+                DEFAULT_MAX_REQUEST_SIZE,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_MAX_RESPONSE_SIZE",
+                "Maximum size, in bytes, of a response",
+                DEFAULT_MAX_RESPONSE_SIZE,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_MAX_CONNECTIONS",
+                "Maximum number of concurrent HTTP and WebSocket connections",
+                DEFAULT_MAX_CONNECTIONS,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_MAX_SUBSCRIPTIONS",
+                "Maximum number of subscriptions per WebSocket connection",
+                DEFAULT_MAX_SUBSCRIPTIONS,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_MAX_REQUESTS_PER_SECOND",
+                "Maximum number of HTTP requests per second of a connection",
+                DEFAULT_MAX_REQUESTS_PER_SECOND,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_MAX_BUFFERED_MESSAGES",
+                "Maximum number of messages of a WebSocket connection waiting to be sent",
+                DEFAULT_MAX_BUFFERED_MESSAGES,
+                Pattern(POSITIVE_PATTERN),
+            ),
+            ConfigField::unset(
+                "KAKAROT_RPC_METHOD_ALIASES",
+                "Comma separated `alias=method` pairs serving methods under other names",
+                Pattern("^\\s*([^=,\\s]+\\s*=\\s*[^=,\\s]+\\s*(,\\s*[^=,\\s]+\\s*=\\s*[^=,\\s]+\\s*)*)?$"),
+            ),
+            ConfigField::optional(
+                "KAKAROT_RPC_STALENESS_BUDGET_MS",
+                "Max age, in ms, of the latest block and fee suggestion served while refreshed, 0 disables",
+                0,
+                Pattern(UNSIGNED_PATTERN),
+            ),
+            ConfigField::unset(
+                "KAKAROT_RPC_MAX_FOLLOWER_LAG",
+                "Blocks the chain follower may be behind the Starknet node before net_listening is false",
+                Pattern(UNSIGNED_PATTERN),
+            ),
+            ConfigField::unset(
+                "KAKAROT_ADMIN_RPC_ADDRESS",
+                "Address of the listener serving the admin namespace, which is not served if unset",
+                Text,
+            ),
+            ConfigField::unset(
+                "KAKAROT_ADMIN_RPC_TOKEN",
+                "Bearer token of the admin namespace, required with KAKAROT_ADMIN_RPC_ADDRESS",
+                Text,
+            ),
+            ConfigField::unset(
+                "KAKAROT_WATCHLIST_WEBHOOK_URLS",
+                "Comma separated URLs the activity of the watched addresses is posted to",
+                Text,
+            ),
+        ]
     }
 
     pub fn from_env() -> Result<Self> {
         let socket_addr = std::env::var("KAKAROT_HTTP_RPC_ADDRESS")
             .map_err(|_| eyre!("Missing mandatory environment variable: KAKAROT_HTTP_RPC_ADDRESS"))?;
-        Ok(RPCConfig::new(socket_addr))
+
+        let namespaces = match std::env::var("KAKAROT_RPC_NAMESPACES") {
+            Ok(namespaces) => namespaces
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<Vec<KakarotRpcNamespace>, _>>()
+                .map_err(|err| eyre!("KAKAROT_RPC_NAMESPACES is set wrong: {err}"))?,
+            Err(_) => KakarotRpcNamespace::DEFAULT.to_vec(),
+        };
+
+        let subscription_worker_threads = match std::env::var("KAKAROT_SUBSCRIPTION_WORKER_THREADS") {
+            Ok(threads) => threads
+                .parse()
+                .map_err(|_| eyre!("KAKAROT_SUBSCRIPTION_WORKER_THREADS should be a number, got {threads}"))?,
+            Err(_) => DEFAULT_SUBSCRIPTION_WORKER_THREADS,
+        };
+
+        let max_request_size = get_optional_limit("KAKAROT_RPC_MAX_REQUEST_SIZE", DEFAULT_MAX_REQUEST_SIZE)?;
+        let max_response_size = get_optional_limit("KAKAROT_RPC_MAX_RESPONSE_SIZE", DEFAULT_MAX_RESPONSE_SIZE)?;
+        let max_connections = get_optional_limit("KAKAROT_RPC_MAX_CONNECTIONS", DEFAULT_MAX_CONNECTIONS)?;
+        let max_subscriptions = get_optional_limit("KAKAROT_RPC_MAX_SUBSCRIPTIONS", DEFAULT_MAX_SUBSCRIPTIONS)?;
+        let max_requests_per_second =
+            get_optional_limit("KAKAROT_RPC_MAX_REQUESTS_PER_SECOND", DEFAULT_MAX_REQUESTS_PER_SECOND)?;
+        let max_buffered_messages =
+            get_optional_limit("KAKAROT_RPC_MAX_BUFFERED_MESSAGES", DEFAULT_MAX_BUFFERED_MESSAGES)?;
+
+        let method_aliases = match std::env::var("KAKAROT_RPC_METHOD_ALIASES") {
+            Ok(method_aliases) => parse_method_aliases(&method_aliases)
+                .map_err(|err| eyre!("KAKAROT_RPC_METHOD_ALIASES is set wrong: {err}"))?,
+            Err(_) => Vec::new(),
+        };
+
+        let staleness_budget_ms = match std::env::var("KAKAROT_RPC_STALENESS_BUDGET_MS") {
+            Ok(budget) => {
+                budget.parse().map_err(|_| eyre!("KAKAROT_RPC_STALENESS_BUDGET_MS should be a number, got {budget}"))?
+            }
+            Err(_) => 0,
+        };
+
+        let max_follower_lag = match std::env::var("KAKAROT_RPC_MAX_FOLLOWER_LAG") {
+            Ok(lag) => {
+                Some(lag.parse().map_err(|_| eyre!("KAKAROT_RPC_MAX_FOLLOWER_LAG should be a number, got {lag}"))?)
+            }
+            Err(_) => None,
+        };
+
+        let admin = match (std::env::var("KAKAROT_ADMIN_RPC_ADDRESS"), std::env::var("KAKAROT_ADMIN_RPC_TOKEN")) {
+            (Ok(address), Ok(token)) if !token.trim().is_empty() => Some((address, token.trim().to_string())),
+            (Ok(_), _) => return Err(eyre!("KAKAROT_ADMIN_RPC_TOKEN is required with KAKAROT_ADMIN_RPC_ADDRESS")),
+            (Err(_), _) => None,
+        };
+
+        let webhook_urls = match std::env::var("KAKAROT_WATCHLIST_WEBHOOK_URLS") {
+            Ok(webhook_urls) => webhook_urls
+                .split(',')
+                .filter(|webhook_url| !webhook_url.trim().is_empty())
+                .map(|webhook_url| {
+                    Url::parse(webhook_url.trim())
+                        .map_err(|err| eyre!("KAKAROT_WATCHLIST_WEBHOOK_URLS is set wrong: {webhook_url}: {err}"))
+                })
+                .collect::<Result<Vec<_>>>()?,
+            Err(_) => Vec::new(),
+        };
+
+        Ok(RPCConfig {
+            namespaces,
+            subscription_worker_threads,
+            max_request_size,
+            max_response_size,
+            max_connections,
+            max_subscriptions,
+            max_requests_per_second,
+            max_buffered_messages,
+            method_aliases,
+            staleness_budget_ms,
+            max_follower_lag,
+            admin,
+            webhook_urls,
+            ..RPCConfig::new(socket_addr)
+        })
     }
 }
+
+fn get_optional_limit(name: &str, default: u32) -> Result<u32> {
+    match std::env::var(name) {
+        Ok(limit) => match limit.parse::<u32>() {
+            Ok(0) => Err(eyre!("{name} should be greater than 0")),
+            Ok(limit) => Ok(limit),
+            Err(_) => Err(eyre!("{name} should be a number, got {limit}")),
+        },
+        Err(_) => Ok(default),
+    }
+}
+
+/// Parses a comma separated list of `alias=method` pairs, e.g.
+/// `parity_chainId=eth_chainId,web3_blockNumber=eth_blockNumber`.
+fn parse_method_aliases(method_aliases: &str) -> Result<Vec<(String, String)>, String> {
+    method_aliases
+        .split(',')
+        .filter(|method_alias| !method_alias.trim().is_empty())
+        .map(|method_alias| match method_alias.split_once('=') {
+            Some((alias, method)) if !alias.trim().is_empty() && !method.trim().is_empty() => {
+                Ok((alias.trim().to_string(), method.trim().to_string()))
+            }
+            _ => Err(format!("expected alias=method, got {method_alias}")),
+        })
+        .collect()
+}
+
+/// Returns the JSON Schema of the whole configuration of the server, for deployment tooling to
+/// validate env files before rollout.
+#[must_use]
+pub fn config_schema() -> Value {
+    let fields: Vec<ConfigField> = RPCConfig::document().into_iter().chain(StarknetConfig::document()).collect();
+    config_json_schema(&fields)
+}
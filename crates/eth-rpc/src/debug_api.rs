@@ -0,0 +1,12 @@
+use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::proc_macros::rpc;
+use reth_primitives::{Bytes, H256};
+
+/// Debug API
+#[rpc(server, namespace = "debug")]
+#[async_trait]
+pub trait DebugApi {
+    /// Returns an EIP-2718 binary-encoded transaction.
+    #[method(name = "getRawTransaction")]
+    async fn raw_transaction(&self, hash: H256) -> Result<Bytes>;
+}
@@ -0,0 +1,28 @@
+use std::sync::Arc;
+
+use jsonrpsee::core::{async_trait, RpcResult as Result};
+use kakarot_rpc_core::client::client_api::KakarotProvider;
+use reth_primitives::{Bytes, H256};
+
+use crate::debug_api::DebugApiServer;
+
+/// The RPC module for the `debug` namespace.
+pub struct KakarotDebugRpc {
+    pub kakarot_client: Arc<dyn KakarotProvider>,
+}
+
+impl KakarotDebugRpc {
+    #[must_use]
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>) -> Self {
+        Self { kakarot_client }
+    }
+}
+
+#[async_trait]
+impl DebugApiServer for KakarotDebugRpc {
+    /// Returns the raw signed EVM transaction wrapped by a Kakarot transaction.
+    async fn raw_transaction(&self, hash: H256) -> Result<Bytes> {
+        let raw_transaction = self.kakarot_client.raw_transaction_by_hash(hash).await?;
+        Ok(raw_transaction)
+    }
+}
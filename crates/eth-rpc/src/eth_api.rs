@@ -1,10 +1,12 @@
 use jsonrpsee::core::RpcResult as Result;
 use jsonrpsee::proc_macros::rpc;
+use kakarot_rpc_core::client::filters::FilterChanges;
+use kakarot_rpc_core::models::metadata::AdapterSyncStatus;
 use reth_primitives::rpc::transaction::eip2930::AccessListWithGasUsed;
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, H256, H64, U128, U256, U64};
 use reth_rpc_types::{
-    CallRequest, EIP1186AccountProofResponse, FeeHistory, Index, RichBlock, SyncStatus, Transaction as EthTransaction,
-    TransactionReceipt, TransactionRequest, Work,
+    CallRequest, EIP1186AccountProofResponse, FeeHistory, Filter, Index, Log, Rich, RichBlock,
+    Transaction as EthTransaction, TransactionReceipt, TransactionRequest, Work,
 };
 
 #[rpc(server)]
@@ -13,13 +15,10 @@ pub trait EthApi {
     #[method(name = "eth_blockNumber")]
     async fn block_number(&self) -> Result<U64>;
 
-    /// Returns the protocol version encoded as a string.
-    #[method(name = "net_version")]
-    fn protocol_version(&self) -> Result<U64>;
-
-    /// Returns an object with data about the sync status or false.
+    /// Returns an object with data about the sync status, along with the state of the adapter, or
+    /// false.
     #[method(name = "eth_syncing")]
-    async fn syncing(&self) -> Result<SyncStatus>;
+    async fn syncing(&self) -> Result<AdapterSyncStatus>;
 
     /// Returns the client coinbase address.
     #[method(name = "eth_coinbase")]
@@ -71,11 +70,15 @@ pub trait EthApi {
 
     /// Returns the information about a transaction requested by transaction hash.
     #[method(name = "eth_getTransactionByHash")]
-    async fn transaction_by_hash(&self, hash: H256) -> Result<Option<EthTransaction>>;
+    async fn transaction_by_hash(&self, hash: H256) -> Result<Option<Rich<EthTransaction>>>;
 
     /// Returns information about a transaction by block hash and transaction index position.
     #[method(name = "eth_getTransactionByBlockHashAndIndex")]
-    async fn transaction_by_block_hash_and_index(&self, hash: H256, index: Index) -> Result<Option<EthTransaction>>;
+    async fn transaction_by_block_hash_and_index(
+        &self,
+        hash: H256,
+        index: Index,
+    ) -> Result<Option<Rich<EthTransaction>>>;
 
     /// Returns information about a transaction by block number and transaction index position.
     #[method(name = "eth_getTransactionByBlockNumberAndIndex")]
@@ -83,11 +86,15 @@ pub trait EthApi {
         &self,
         number: BlockNumberOrTag,
         index: Index,
-    ) -> Result<Option<EthTransaction>>;
+    ) -> Result<Option<Rich<EthTransaction>>>;
 
     /// Returns the receipt of a transaction by transaction hash.
     #[method(name = "eth_getTransactionReceipt")]
-    async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>>;
+    async fn transaction_receipt(&self, hash: H256) -> Result<Option<Rich<TransactionReceipt>>>;
+
+    /// Returns the receipts of the transactions of a block.
+    #[method(name = "eth_getBlockReceipts")]
+    async fn block_receipts(&self, block_id: BlockId) -> Result<Option<Vec<Rich<TransactionReceipt>>>>;
 
     /// Returns the balance of the account of given address.
     #[method(name = "eth_getBalance")]
@@ -188,6 +195,10 @@ pub trait EthApi {
     #[method(name = "eth_sendRawTransaction")]
     async fn send_raw_transaction(&self, bytes: Bytes) -> Result<H256>;
 
+    /// Returns the transactions of the pending block, which are not included in a block yet.
+    #[method(name = "eth_pendingTransactions")]
+    async fn pending_transactions(&self) -> Result<Vec<EthTransaction>>;
+
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
     /// + len(message) + message))).
     #[method(name = "eth_sign")]
@@ -202,6 +213,31 @@ pub trait EthApi {
     #[method(name = "eth_signTypedData")]
     async fn sign_typed_data(&self, address: Address, data: serde_json::Value) -> Result<Bytes>;
 
+    /// Returns logs matching given filter object.
+    #[method(name = "eth_getLogs")]
+    async fn get_logs(&self, filter: Filter) -> Result<Vec<Log>>;
+
+    /// Creates a filter reporting the logs matching the filter object in new blocks, and returns
+    /// its id.
+    #[method(name = "eth_newFilter")]
+    async fn new_filter(&self, filter: Filter) -> Result<U64>;
+
+    /// Creates a filter reporting the hashes of new blocks, and returns its id.
+    #[method(name = "eth_newBlockFilter")]
+    async fn new_block_filter(&self) -> Result<U64>;
+
+    /// Returns the changes of a filter since its last poll.
+    #[method(name = "eth_getFilterChanges")]
+    async fn get_filter_changes(&self, id: U64) -> Result<FilterChanges>;
+
+    /// Returns all the logs matching a log filter.
+    #[method(name = "eth_getFilterLogs")]
+    async fn get_filter_logs(&self, id: U64) -> Result<Vec<Log>>;
+
+    /// Uninstalls a filter.
+    #[method(name = "eth_uninstallFilter")]
+    async fn uninstall_filter(&self, id: U64) -> Result<bool>;
+
     /// Returns the account and storage values of the specified account including the Merkle-proof.
     /// This call can be used to verify that the data you are pulling from is not tampered with.
     #[method(name = "eth_getProof")]
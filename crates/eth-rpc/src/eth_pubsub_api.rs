@@ -0,0 +1,39 @@
+use jsonrpsee::core::SubscriptionResult;
+use jsonrpsee::proc_macros::rpc;
+use reth_primitives::U64;
+use reth_rpc_types::Filter;
+use serde::{Deserialize, Serialize};
+
+/// The kind of an `eth_subscribe` subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SubscriptionKind {
+    /// Headers of the new blocks.
+    NewHeads,
+    /// Logs of the new blocks matching a filter.
+    Logs,
+    /// Hashes of the Kakarot transactions entering the pending block.
+    NewPendingTransactions,
+}
+
+/// Ethereum publish-subscribe API, served over WebSocket.
+#[rpc(server, namespace = "eth")]
+pub trait EthPubSubApi {
+    /// Creates a subscription to new heads, logs or pending transactions.
+    #[subscription(name = "subscribe" => "subscription", unsubscribe = "unsubscribe", item = serde_json::Value)]
+    async fn subscribe(&self, kind: SubscriptionKind, filter: Option<Filter>) -> SubscriptionResult;
+}
+
+/// Kakarot extensions of the publish-subscribe API, served over WebSocket.
+#[rpc(server, namespace = "kakarot")]
+pub trait KakarotPubSubApi {
+    /// Creates a subscription to new heads or logs which first replays the blocks from
+    /// `from_block` on, for the subscribers resyncing after a reorg or a disconnection.
+    #[subscription(name = "resubscribeFrom" => "subscription", unsubscribe = "unsubscribe", item = serde_json::Value)]
+    async fn resubscribe_from(
+        &self,
+        from_block: U64,
+        kind: SubscriptionKind,
+        filter: Option<Filter>,
+    ) -> SubscriptionResult;
+}
@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use jsonrpsee::core::{async_trait, SubscriptionResult};
+use jsonrpsee::server::SubscriptionMessage;
+use jsonrpsee::PendingSubscriptionSink;
+use kakarot_rpc_core::client::constants::requests::MAX_BLOCK_RANGE;
+use kakarot_rpc_core::client::errors::EthApiError;
+use kakarot_rpc_core::client::events::{ClientEvent, REORG_DEPTH};
+use kakarot_rpc_core::client::subscriptions::SubscriptionManager;
+use reth_primitives::U64;
+use reth_rpc_types::{Filter, FilteredParams, Header, Log};
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::eth_pubsub_api::{EthPubSubApiServer, KakarotPubSubApiServer, SubscriptionKind};
+
+/// The RPC module for the Ethereum publish-subscribe API.
+pub struct KakarotEthPubSubRpc {
+    pub subscriptions: SubscriptionManager,
+}
+
+impl KakarotEthPubSubRpc {
+    #[must_use]
+    pub fn new(subscriptions: SubscriptionManager) -> Self {
+        Self { subscriptions }
+    }
+
+    /// Pushes the header of each new block, or its logs matching `logs_filter` if set, after the
+    /// blocks from `from_block` on if set.
+    ///
+    /// The blocks and their receipts are fetched once by the chain follower of the client, for
+    /// every subscriber, and the logs are filtered here. A subscriber missing blocks is closed
+    /// rather than served a partial chain. On a reorg, the logs pushed from the blocks replaced are
+    /// pushed again as removed, the most recent first, and the headers and logs of the replacing
+    /// blocks follow as they are followed.
+    async fn serve_new_blocks(
+        &self,
+        pending: PendingSubscriptionSink,
+        logs_filter: Option<Filter>,
+        from_block: Option<u64>,
+    ) -> SubscriptionResult {
+        // Subscribing before accepting, the subscriber receives every block from its acceptance on
+        let mut events = self.subscriptions.subscribe_new_blocks();
+        let (replayed, mut replayed_to) = match from_block {
+            Some(from_block) => match self.replay(from_block, logs_filter.as_ref()).await {
+                Ok((replayed, replayed_to)) => (replayed, Some(replayed_to)),
+                Err(err) => {
+                    pending.reject(err).await;
+                    return Ok(());
+                }
+            },
+            None => (vec![], None),
+        };
+        let sink = pending.accept().await?;
+        let logs_params = logs_filter.map(|filter| FilteredParams::new(Some(filter)));
+        // The logs pushed from the last blocks, by block number, removed if the blocks are replaced
+        let mut pushed_logs: BTreeMap<u64, Vec<Log>> = BTreeMap::new();
+        let mut replayed = replayed.into_iter();
+
+        loop {
+            let update = match replayed.next() {
+                Some(update) => update,
+                None => {
+                    let event = tokio::select! {
+                        event = events.recv() => event,
+                        // The subscriber disconnected
+                        _ = sink.closed() => return Ok(()),
+                    };
+                    match event {
+                        Ok(ClientEvent::NewBlock { block, receipts }) => {
+                            // The blocks replayed were already pushed
+                            let block_number = block.header.number.map(|number| number.to::<u64>());
+                            if matches!((block_number, replayed_to), (Some(number), Some(to)) if number <= to) {
+                                continue;
+                            }
+                            match &logs_params {
+                                None => Update::Header(block.header.clone()),
+                                Some(params) => Update::Logs(
+                                    receipts
+                                        .into_iter()
+                                        .flat_map(|receipt| receipt.logs)
+                                        .filter(|log| params.filter_address(log) && params.filter_topics(log))
+                                        .collect(),
+                                ),
+                            }
+                        }
+                        Ok(ClientEvent::ReorgDetected { from_block }) => Update::Reorg { from_block },
+                        Ok(_) => continue,
+                        Err(RecvError::Lagged(missed)) => {
+                            return Err(format!("the subscription missed {missed} blocks and was closed").into());
+                        }
+                        Err(RecvError::Closed) => return Ok(()),
+                    }
+                }
+            };
+            let messages = match update {
+                Update::Header(header) => vec![SubscriptionMessage::from_json(&header)?],
+                Update::Logs(logs) => {
+                    let mut messages = Vec::with_capacity(logs.len());
+                    for log in logs {
+                        messages.push(SubscriptionMessage::from_json(&log)?);
+                        if let Some(block_number) = log.block_number.map(|number| number.to::<u64>()) {
+                            pushed_logs.entry(block_number).or_default().push(log);
+                            pushed_logs = pushed_logs.split_off(&(block_number + 1).saturating_sub(REORG_DEPTH));
+                        }
+                    }
+                    messages
+                }
+                Update::Reorg { from_block } => {
+                    // The replacing blocks are pushed even if their numbers were replayed
+                    replayed_to = replayed_to.and_then(|to| from_block.checked_sub(1).map(|last| to.min(last)));
+                    pushed_logs
+                        .split_off(&from_block)
+                        .into_values()
+                        .rev()
+                        .flat_map(|logs| logs.into_iter().rev())
+                        .map(|log| SubscriptionMessage::from_json(&Log { removed: true, ..log }))
+                        .collect::<Result<_, _>>()?
+                }
+            };
+
+            for message in messages {
+                if sink.send(message).await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Returns the updates of the blocks from `from_block` to the latest block, as pushed for the
+    /// blocks followed: their headers, or their logs matching `logs_filter` if set, along with the
+    /// last block replayed.
+    ///
+    /// At most `MAX_BLOCK_RANGE` blocks are replayed.
+    async fn replay(&self, from_block: u64, logs_filter: Option<&Filter>) -> Result<(Vec<Update>, u64), EthApiError> {
+        let client = self.subscriptions.kakarot_client();
+        let latest_block = client.block_number().await?.as_u64();
+        if from_block > latest_block {
+            // Nothing to replay, the blocks up to `from_block` are not pushed either
+            return Ok((vec![], from_block - 1));
+        }
+        if latest_block - from_block >= MAX_BLOCK_RANGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "cannot replay more than {MAX_BLOCK_RANGE} blocks, from block {from_block} is too old"
+            )));
+        }
+
+        let replayed = match logs_filter {
+            Some(filter) => {
+                let filter = filter.clone().from_block(from_block).to_block(latest_block);
+                vec![Update::Logs(client.get_logs(filter).await?)]
+            }
+            None => client
+                .get_eth_blocks_in_range(from_block, latest_block, false)
+                .await?
+                .into_iter()
+                .map(|block| Update::Header(block.header.clone()))
+                .collect(),
+        };
+        Ok((replayed, latest_block))
+    }
+
+    /// Pushes the hash of each Kakarot transaction entering the pending block.
+    async fn serve_pending_transactions(&self, pending: PendingSubscriptionSink) -> SubscriptionResult {
+        let mut pending_transactions = self.subscriptions.subscribe_pending_transactions();
+        let sink = pending.accept().await?;
+
+        loop {
+            let hash = tokio::select! {
+                hash = pending_transactions.recv() => hash,
+                // The subscriber disconnected
+                _ = sink.closed() => return Ok(()),
+            };
+            let hash = match hash {
+                Ok(hash) => hash,
+                Err(RecvError::Lagged(missed)) => {
+                    return Err(format!("the subscription missed {missed} transactions and was closed").into());
+                }
+                Err(RecvError::Closed) => return Ok(()),
+            };
+
+            if sink.send(SubscriptionMessage::from_json(&hash)?).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl EthPubSubApiServer for KakarotEthPubSubRpc {
+    async fn subscribe(
+        &self,
+        pending: PendingSubscriptionSink,
+        kind: SubscriptionKind,
+        filter: Option<Filter>,
+    ) -> SubscriptionResult {
+        match kind {
+            SubscriptionKind::NewHeads => self.serve_new_blocks(pending, None, None).await,
+            SubscriptionKind::Logs => self.serve_new_blocks(pending, Some(filter.unwrap_or_default()), None).await,
+            SubscriptionKind::NewPendingTransactions => self.serve_pending_transactions(pending).await,
+        }
+    }
+}
+
+#[async_trait]
+impl KakarotPubSubApiServer for KakarotEthPubSubRpc {
+    async fn resubscribe_from(
+        &self,
+        pending: PendingSubscriptionSink,
+        from_block: U64,
+        kind: SubscriptionKind,
+        filter: Option<Filter>,
+    ) -> SubscriptionResult {
+        let from_block = Some(from_block.as_u64());
+        match kind {
+            SubscriptionKind::NewHeads => self.serve_new_blocks(pending, None, from_block).await,
+            SubscriptionKind::Logs => {
+                self.serve_new_blocks(pending, Some(filter.unwrap_or_default()), from_block).await
+            }
+            SubscriptionKind::NewPendingTransactions => {
+                let err = EthApiError::InvalidParams("pending transactions cannot be replayed".to_string());
+                pending.reject(err).await;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// What a subscription to new blocks pushes next.
+enum Update {
+    /// The header of a new block.
+    Header(Header),
+    /// The logs matching the filter of the subscription, emitted in new blocks.
+    Logs(Vec<Log>),
+    /// The blocks from `from_block` on were replaced or dropped.
+    Reorg { from_block: u64 },
+}
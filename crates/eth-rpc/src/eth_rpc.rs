@@ -1,53 +1,216 @@
+use std::future::Future;
+use std::result::Result as StdResult;
+use std::sync::Arc;
+use std::time::Duration;
+
 use jsonrpsee::core::{async_trait, RpcResult as Result};
 use jsonrpsee::proc_macros::rpc;
 use jsonrpsee::types::error::{INTERNAL_ERROR_CODE, METHOD_NOT_FOUND_CODE};
 use kakarot_rpc_core::client::client_api::KakarotProvider;
-use kakarot_rpc_core::client::constants::{CHAIN_ID, ESTIMATE_GAS};
-use kakarot_rpc_core::client::errors::rpc_err;
-use kakarot_rpc_core::client::helpers::ethers_block_id_to_starknet_block_id;
-use kakarot_rpc_core::models::balance::TokenBalances;
+use kakarot_rpc_core::client::errors::{rpc_err, EthApiError};
+use kakarot_rpc_core::client::filters::FilterChanges;
+use kakarot_rpc_core::client::helpers::is_transaction_not_found_error;
+use kakarot_rpc_core::client::stale_cache::StaleCache;
+use kakarot_rpc_core::models::balance::{BalanceChange, TokenBalances};
+use kakarot_rpc_core::models::block::StarknetBlockInfo;
+use kakarot_rpc_core::models::chain_metadata::ChainMetadata;
+use kakarot_rpc_core::models::confirmation::L1Confirmation;
+use kakarot_rpc_core::models::event::RawKakarotEvent;
+use kakarot_rpc_core::models::metadata::{
+    kakarot_metadata_requested, AdapterState, AdapterSyncStatus, GasEstimate, KAKAROT_METADATA_KEY, REVERT_REASON_KEY,
+};
 use reth_primitives::rpc::transaction::eip2930::AccessListWithGasUsed;
 use reth_primitives::{Address, BlockId, BlockNumberOrTag, Bytes, H256, H64, U128, U256, U64};
 use reth_rpc_types::{
-    CallRequest, EIP1186AccountProofResponse, FeeHistory, Index, RichBlock, SyncStatus,
+    CallRequest, EIP1186AccountProofResponse, FeeHistory, Filter, Index, Log, Rich, RichBlock, SyncInfo, SyncStatus,
     Transaction as EtherTransaction, TransactionReceipt, TransactionRequest, Work,
 };
 use serde_json::Value;
-use starknet::core::types::{BlockId as StarknetBlockId, BlockTag};
+use starknet::core::types::FieldElement;
 
 use crate::eth_api::EthApiServer;
 
+/// The methods of the standard `eth` namespace which Kakarot does not implement, answered with a
+/// method not found error.
+pub const STUB_METHODS: &[&str] = &[
+    "eth_coinbase",
+    "eth_getUncleCountByBlockHash",
+    "eth_getUncleCountByBlockNumber",
+    "eth_getUncleByBlockHashAndIndex",
+    "eth_getUncleByBlockNumberAndIndex",
+    "eth_createAccessList",
+    "eth_mining",
+    "eth_hashrate",
+    "eth_getWork",
+    "eth_submitHashrate",
+    "eth_submitWork",
+    "eth_sendTransaction",
+    "eth_sign",
+    "eth_signTransaction",
+    "eth_signTypedData",
+    "eth_getProof",
+];
+
+/// The status of the methods served by Kakarot.
+const RPC_API_STATUS: &str = "https://github.com/sayajin-labs/kakarot-rpc/blob/main/docs/rpc_api_status.md";
+
+/// Answers a method of [`STUB_METHODS`], which all stubs go through so that the list cannot drift
+/// from them.
+fn unsupported<T>(method: &'static str) -> Result<T> {
+    debug_assert!(STUB_METHODS.contains(&method), "{method} is answered as a stub but missing from STUB_METHODS");
+    Err(rpc_err(
+        METHOD_NOT_FOUND_CODE,
+        format!("Unsupported method: {method}. See available methods at {RPC_API_STATUS}"),
+    ))
+}
+
 /// The RPC module for the Ethereum protocol required by Kakarot.
 pub struct KakarotEthRpc {
-    pub kakarot_client: Box<dyn KakarotProvider>,
+    pub kakarot_client: Arc<dyn KakarotProvider>,
+    head_cache: Option<HeadCache>,
 }
 
-#[async_trait]
-impl EthApiServer for KakarotEthRpc {
-    async fn block_number(&self) -> Result<U64> {
-        let block_number = self.kakarot_client.block_number().await?;
-        Ok(block_number)
+/// The caches of the queries following the head of the chain, served stale while revalidated.
+struct HeadCache {
+    block_number: Arc<StaleCache<U64>>,
+    latest_block: Arc<StaleCache<RichBlock>>,
+    latest_full_block: Arc<StaleCache<RichBlock>>,
+    max_priority_fee_per_gas: Arc<StaleCache<U128>>,
+}
+
+impl HeadCache {
+    fn new(staleness_budget: Duration) -> Self {
+        Self {
+            block_number: Arc::new(StaleCache::new(staleness_budget)),
+            latest_block: Arc::new(StaleCache::new(staleness_budget)),
+            latest_full_block: Arc::new(StaleCache::new(staleness_budget)),
+            max_priority_fee_per_gas: Arc::new(StaleCache::new(staleness_budget)),
+        }
+    }
+
+    /// Returns the share of the caches holding a value within the staleness budget.
+    fn coverage(&self) -> f64 {
+        let warm = [
+            self.block_number.is_warm(),
+            self.latest_block.is_warm(),
+            self.latest_full_block.is_warm(),
+            self.max_priority_fee_per_gas.is_warm(),
+        ];
+        warm.iter().filter(|&&warm| warm).count() as f64 / warm.len() as f64
     }
+}
 
-    /// Get the protocol version of the Kakarot Starknet RPC.
-    ///
-    /// # Returns
-    /// * `protocol_version(u64)` - The protocol version.
+impl KakarotEthRpc {
+    /// Handles a request within the request timeout of the client.
     ///
-    /// `Ok(protocol_version)` if the operation was successful.
-    /// `Err(EthApiError)` if the operation failed.
-    fn protocol_version(&self) -> Result<U64> {
-        let protocol_version = 1_u64;
-        Ok(protocol_version.into())
+    /// When the timeout elapses, `request` is dropped, which cancels all of its outstanding
+    /// Starknet calls instead of letting them run after the client gave up.
+    async fn with_deadline<T>(&self, request: impl Future<Output = StdResult<T, EthApiError>>) -> Result<T> {
+        let request_timeout = self.kakarot_client.request_timeout();
+        let response = tokio::time::timeout(request_timeout, request)
+            .await
+            .map_err(|_| EthApiError::RequestTimeout(request_timeout))??;
+        Ok(response)
+    }
+
+    /// Fetches the latest block, at most the staleness budget old if stale-while-revalidate is
+    /// enabled.
+    async fn latest_block(&self, full: bool) -> StdResult<RichBlock, EthApiError> {
+        let fetch = || {
+            let kakarot_client = self.kakarot_client.clone();
+            async move {
+                let starknet_block_id =
+                    kakarot_client.resolve_block_id(BlockId::Number(BlockNumberOrTag::Latest)).await?;
+                kakarot_client.get_eth_block_from_starknet_block(starknet_block_id, full).await
+            }
+        };
+        match &self.head_cache {
+            Some(head_cache) if full => head_cache.latest_full_block.get(fetch).await,
+            Some(head_cache) => head_cache.latest_block.get(fetch).await,
+            None => fetch().await,
+        }
+    }
+
+    /// Adds the Kakarot metadata of a transaction to a transaction or receipt response, if the
+    /// request asked for it.
+    async fn with_transaction_metadata<T: Send>(&self, response: T, hash: H256) -> StdResult<Rich<T>, EthApiError> {
+        let mut response: Rich<T> = response.into();
+        if kakarot_metadata_requested() {
+            if let Some(metadata) = self.kakarot_client.transaction_metadata(hash).await? {
+                if let Ok(metadata) = serde_json::to_value(metadata) {
+                    response.extra_info.insert(KAKAROT_METADATA_KEY.to_string(), metadata);
+                }
+            }
+        }
+        Ok(response)
+    }
+
+    /// Returns the response of a receipt, with the Kakarot metadata if requested and with the
+    /// revert reason of a failed transaction.
+    async fn receipt_response(&self, receipt: TransactionReceipt) -> StdResult<Rich<TransactionReceipt>, EthApiError> {
+        let hash = receipt.transaction_hash.unwrap_or_default();
+        let failed = receipt.status_code == Some(U64::from(0));
+        let mut response = self.with_transaction_metadata(receipt, hash).await?;
+        if failed {
+            if let Some(reason) = self.kakarot_client.revert_reason(hash).await? {
+                response.extra_info.insert(REVERT_REASON_KEY.to_string(), Value::String(reason));
+            }
+        }
+        Ok(response)
     }
+}
 
-    async fn syncing(&self) -> Result<SyncStatus> {
-        let status = self.kakarot_client.syncing().await?;
-        Ok(status)
+#[async_trait]
+impl EthApiServer for KakarotEthRpc {
+    async fn block_number(&self) -> Result<U64> {
+        let fetch = || {
+            let kakarot_client = self.kakarot_client.clone();
+            async move {
+                let block_number = kakarot_client.block_number().await?;
+                // Only the latest block served to users may not go backwards
+                kakarot_client.block_guard().check_head(block_number.as_u64())?;
+                Ok::<_, EthApiError>(block_number)
+            }
+        };
+        self.with_deadline(async {
+            match &self.head_cache {
+                Some(head_cache) => head_cache.block_number.get(fetch).await,
+                None => fetch().await,
+            }
+        })
+        .await
+    }
+
+    async fn syncing(&self) -> Result<AdapterSyncStatus> {
+        let state = AdapterState {
+            follower_lag: self.kakarot_client.follower().lag(),
+            submission_queue_depth: self.kakarot_client.submission_queue_depth(),
+            head_cache_coverage: self.head_cache.as_ref().map(HeadCache::coverage),
+        };
+        self.with_deadline(async {
+            let progress = match self.kakarot_client.syncing().await? {
+                SyncStatus::Info(progress) => progress,
+                SyncStatus::None if state.is_catching_up() => {
+                    // The node is synced, so the progress is the one of the follower up to its head
+                    let highest_block = self.kakarot_client.block_number().await?.as_u64();
+                    let current_block = U256::from(highest_block.saturating_sub(state.follower_lag.unwrap_or(0)));
+                    SyncInfo {
+                        starting_block: current_block,
+                        current_block,
+                        highest_block: U256::from(highest_block),
+                        warp_chunks_amount: None,
+                        warp_chunks_processed: None,
+                    }
+                }
+                SyncStatus::None => return Ok(AdapterSyncStatus::Synced),
+            };
+            Ok::<_, EthApiError>(AdapterSyncStatus::Syncing { progress, state })
+        })
+        .await
     }
 
     async fn author(&self) -> Result<Address> {
-        todo!()
+        unsupported("eth_coinbase")
     }
 
     async fn accounts(&self) -> Result<Vec<Address>> {
@@ -55,44 +218,47 @@ impl EthApiServer for KakarotEthRpc {
     }
 
     async fn chain_id(&self) -> Result<Option<U64>> {
-        // CHAIN_ID = KKRT (0x4b4b5254) in ASCII
-        Ok(Some(CHAIN_ID.into()))
+        Ok(Some(self.kakarot_client.chain_id().into()))
     }
 
     async fn block_by_hash(&self, hash: H256, full: bool) -> Result<Option<RichBlock>> {
         let block_id = BlockId::Hash(hash.into());
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(block_id)?;
-        let block = self.kakarot_client.get_eth_block_from_starknet_block(starknet_block_id, full).await?;
-        Ok(Some(block))
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.get_eth_block_from_starknet_block(starknet_block_id, full).await.map(Some)
+        })
+        .await
     }
 
     async fn block_by_number(&self, number: BlockNumberOrTag, full: bool) -> Result<Option<RichBlock>> {
-        let block_id = BlockId::Number(number);
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(block_id)?;
-        let block = self.kakarot_client.get_eth_block_from_starknet_block(starknet_block_id, full).await?;
-        Ok(Some(block))
+        self.with_deadline(async {
+            if matches!(number, BlockNumberOrTag::Latest) {
+                return self.latest_block(full).await.map(Some);
+            }
+            let starknet_block_id = self.kakarot_client.resolve_block_id(BlockId::Number(number)).await?;
+            self.kakarot_client.get_eth_block_from_starknet_block(starknet_block_id, full).await.map(Some)
+        })
+        .await
     }
 
     async fn block_transaction_count_by_hash(&self, hash: H256) -> Result<U64> {
-        let transaction_count = self.kakarot_client.block_transaction_count_by_hash(hash).await?;
-        Ok(transaction_count)
+        self.with_deadline(self.kakarot_client.block_transaction_count_by_hash(hash)).await
     }
 
     async fn block_transaction_count_by_number(&self, number: BlockNumberOrTag) -> Result<U64> {
-        let transaction_count = self.kakarot_client.block_transaction_count_by_number(number).await?;
-        Ok(transaction_count)
+        self.with_deadline(self.kakarot_client.block_transaction_count_by_number(number)).await
     }
 
     async fn block_uncles_count_by_hash(&self, _hash: H256) -> Result<U256> {
-        todo!()
+        unsupported("eth_getUncleCountByBlockHash")
     }
 
     async fn block_uncles_count_by_number(&self, _number: BlockNumberOrTag) -> Result<U256> {
-        todo!()
+        unsupported("eth_getUncleCountByBlockNumber")
     }
 
     async fn uncle_by_block_hash_and_index(&self, _hash: H256, _index: Index) -> Result<Option<RichBlock>> {
-        todo!()
+        unsupported("eth_getUncleByBlockHashAndIndex")
     }
 
     async fn uncle_by_block_number_and_index(
@@ -100,67 +266,110 @@ impl EthApiServer for KakarotEthRpc {
         _number: BlockNumberOrTag,
         _index: Index,
     ) -> Result<Option<RichBlock>> {
-        todo!()
+        unsupported("eth_getUncleByBlockNumberAndIndex")
     }
 
-    async fn transaction_by_hash(&self, _hash: H256) -> Result<Option<EtherTransaction>> {
-        let ether_tx = self.kakarot_client.transaction_by_hash(_hash).await?;
-        Ok(Some(ether_tx))
+    async fn transaction_by_hash(&self, hash: H256) -> Result<Option<Rich<EtherTransaction>>> {
+        self.with_deadline(async {
+            let ether_tx = match self.kakarot_client.transaction_by_hash(hash).await {
+                Ok(ether_tx) => ether_tx,
+                // Unknown transactions are null, as in geth
+                Err(err) if is_transaction_not_found_error(&err) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+            self.with_transaction_metadata(ether_tx, hash).await.map(Some)
+        })
+        .await
     }
 
-    async fn transaction_by_block_hash_and_index(&self, hash: H256, index: Index) -> Result<Option<EtherTransaction>> {
+    async fn transaction_by_block_hash_and_index(
+        &self,
+        hash: H256,
+        index: Index,
+    ) -> Result<Option<Rich<EtherTransaction>>> {
         let block_id = BlockId::Hash(hash.into());
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(block_id)?;
-        let tx = self.kakarot_client.transaction_by_block_id_and_index(starknet_block_id, index).await?;
-        Ok(Some(tx))
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            let tx = self.kakarot_client.transaction_by_block_id_and_index(starknet_block_id, index).await?;
+            let tx_hash = tx.hash;
+            self.with_transaction_metadata(tx, tx_hash).await.map(Some)
+        })
+        .await
     }
 
     async fn transaction_by_block_number_and_index(
         &self,
         number: BlockNumberOrTag,
         index: Index,
-    ) -> Result<Option<EtherTransaction>> {
+    ) -> Result<Option<Rich<EtherTransaction>>> {
         let block_id = BlockId::Number(number);
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(block_id)?;
-        let tx = self.kakarot_client.transaction_by_block_id_and_index(starknet_block_id, index).await?;
-        Ok(Some(tx))
-    }
-
-    async fn transaction_receipt(&self, hash: H256) -> Result<Option<TransactionReceipt>> {
-        let receipt = self.kakarot_client.transaction_receipt(hash).await?;
-        Ok(receipt)
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            let tx = self.kakarot_client.transaction_by_block_id_and_index(starknet_block_id, index).await?;
+            let tx_hash = tx.hash;
+            self.with_transaction_metadata(tx, tx_hash).await.map(Some)
+        })
+        .await
+    }
+
+    async fn transaction_receipt(&self, hash: H256) -> Result<Option<Rich<TransactionReceipt>>> {
+        self.with_deadline(async {
+            match self.kakarot_client.transaction_receipt(hash).await? {
+                Some(receipt) => self.receipt_response(receipt).await.map(Some),
+                None => Ok(None),
+            }
+        })
+        .await
+    }
+
+    async fn block_receipts(&self, block_id: BlockId) -> Result<Option<Vec<Rich<TransactionReceipt>>>> {
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            let receipts = self.kakarot_client.block_receipts(starknet_block_id).await?;
+
+            let mut responses = Vec::with_capacity(receipts.len());
+            for receipt in receipts {
+                responses.push(self.receipt_response(receipt).await?);
+            }
+            Ok(Some(responses))
+        })
+        .await
     }
 
     async fn balance(&self, address: Address, block_number: Option<BlockId>) -> Result<U256> {
-        let starknet_block_id =
-            ethers_block_id_to_starknet_block_id(block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest)))?;
-
-        let balance = self.kakarot_client.balance(address, starknet_block_id).await?;
-        Ok(balance)
+        let block_id = block_number.unwrap_or_else(|| self.kakarot_client.default_block_id());
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.balance(address, starknet_block_id).await
+        })
+        .await
     }
 
-    async fn storage_at(&self, _address: Address, _index: U256, _block_number: Option<BlockId>) -> Result<H256> {
-        todo!()
+    async fn storage_at(&self, address: Address, index: U256, block_number: Option<BlockId>) -> Result<H256> {
+        let block_id = block_number.unwrap_or_else(|| self.kakarot_client.default_block_id());
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.storage_at(address, index, starknet_block_id).await
+        })
+        .await
     }
 
-    async fn transaction_count(&self, _address: Address, _block_number: Option<BlockId>) -> Result<U256> {
-        let starknet_block_id = _block_number.map(ethers_block_id_to_starknet_block_id);
-        let starknet_block_id = match starknet_block_id {
-            Some(Ok(b)) => b,
-            Some(Err(e)) => return Err(e.into()),
-            None => StarknetBlockId::Tag(BlockTag::Latest),
-        };
-
-        let transaction_count = self.kakarot_client.nonce(_address, starknet_block_id).await?;
-
-        Ok(transaction_count)
+    async fn transaction_count(&self, address: Address, block_number: Option<BlockId>) -> Result<U256> {
+        let block_id = block_number.unwrap_or_else(|| self.kakarot_client.default_block_id());
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.nonce(address, starknet_block_id).await
+        })
+        .await
     }
 
     async fn get_code(&self, address: Address, block_number: Option<BlockId>) -> Result<Bytes> {
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(block_number.unwrap())?;
-
-        let code = self.kakarot_client.get_code(address, starknet_block_id).await?;
-        Ok(code)
+        let block_id = block_number.unwrap_or_else(|| self.kakarot_client.default_block_id());
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.get_code(address, starknet_block_id).await
+        })
+        .await
     }
 
     async fn call(&self, request: CallRequest, block_number: Option<BlockId>) -> Result<Bytes> {
@@ -173,11 +382,12 @@ impl EthApiServer for KakarotEthRpc {
             rpc_err(INTERNAL_ERROR_CODE, "CallRequest `data` field is None. Cannot process a Kakarot call")
         })?;
 
-        let block_id = block_number.unwrap_or(BlockId::Number(BlockNumberOrTag::Latest));
-        let starknet_block_id = ethers_block_id_to_starknet_block_id(block_id)?;
-        let result = self.kakarot_client.call_view(to, Bytes::from(calldata.0), starknet_block_id).await?;
-
-        Ok(result)
+        let block_id = block_number.unwrap_or_else(|| self.kakarot_client.default_block_id());
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.call_view(to, Bytes::from(calldata.0), starknet_block_id).await
+        })
+        .await
     }
 
     async fn create_access_list(
@@ -185,11 +395,12 @@ impl EthApiServer for KakarotEthRpc {
         _request: CallRequest,
         _block_number: Option<BlockId>,
     ) -> Result<AccessListWithGasUsed> {
-        todo!()
+        unsupported("eth_createAccessList")
     }
 
-    async fn estimate_gas(&self, _request: CallRequest, _block_number: Option<BlockId>) -> Result<U256> {
-        Ok(*ESTIMATE_GAS)
+    async fn estimate_gas(&self, request: CallRequest, block_number: Option<BlockId>) -> Result<U256> {
+        let estimate = self.with_deadline(self.kakarot_client.estimate_gas(request, block_number)).await?;
+        Ok(estimate.gas)
     }
 
     async fn gas_price(&self) -> Result<U256> {
@@ -203,55 +414,90 @@ impl EthApiServer for KakarotEthRpc {
         newest_block: BlockNumberOrTag,
         reward_percentiles: Option<Vec<f64>>,
     ) -> Result<FeeHistory> {
-        let fee_history = self.kakarot_client.fee_history(block_count, newest_block, reward_percentiles).await?;
-
-        Ok(fee_history)
+        self.with_deadline(self.kakarot_client.fee_history(block_count, newest_block, reward_percentiles)).await
     }
 
     async fn max_priority_fee_per_gas(&self) -> Result<U128> {
-        let max_priority_fee = self.kakarot_client.max_priority_fee_per_gas();
-        Ok(max_priority_fee)
+        let fetch = || {
+            let kakarot_client = self.kakarot_client.clone();
+            async move { kakarot_client.suggest_max_priority_fee_per_gas().await }
+        };
+        self.with_deadline(async {
+            match &self.head_cache {
+                Some(head_cache) => head_cache.max_priority_fee_per_gas.get(fetch).await,
+                None => fetch().await,
+            }
+        })
+        .await
     }
 
     async fn is_mining(&self) -> Result<bool> {
-        Err(rpc_err(METHOD_NOT_FOUND_CODE, "Unsupported method: eth_mining. See available methods at https://github.com/sayajin-labs/kakarot-rpc/blob/main/docs/rpc_api_status.md".to_string()))
+        unsupported("eth_mining")
     }
 
     async fn hashrate(&self) -> Result<U256> {
-        Err(rpc_err(METHOD_NOT_FOUND_CODE, "Unsupported method: eth_hashrate. See available methods at https://github.com/sayajin-labs/kakarot-rpc/blob/main/docs/rpc_api_status.md".to_string()))
+        unsupported("eth_hashrate")
     }
 
     async fn get_work(&self) -> Result<Work> {
-        Err(rpc_err(METHOD_NOT_FOUND_CODE, "Unsupported method: eth_getWork. See available methods at https://github.com/sayajin-labs/kakarot-rpc/blob/main/docs/rpc_api_status.md".to_string()))
+        unsupported("eth_getWork")
     }
 
     async fn submit_hashrate(&self, _hashrate: U256, _id: H256) -> Result<bool> {
-        todo!()
+        unsupported("eth_submitHashrate")
     }
 
     async fn submit_work(&self, _nonce: H64, _pow_hash: H256, _mix_digest: H256) -> Result<bool> {
-        todo!()
+        unsupported("eth_submitWork")
     }
 
     async fn send_transaction(&self, _request: TransactionRequest) -> Result<H256> {
-        todo!()
+        unsupported("eth_sendTransaction")
     }
 
     async fn send_raw_transaction(&self, _bytes: Bytes) -> Result<H256> {
-        let transaction_hash = self.kakarot_client.send_transaction(_bytes).await?;
-        Ok(transaction_hash)
+        self.with_deadline(self.kakarot_client.send_transaction(_bytes)).await
+    }
+
+    async fn pending_transactions(&self) -> Result<Vec<EtherTransaction>> {
+        self.with_deadline(self.kakarot_client.pending_transactions()).await
     }
 
     async fn sign(&self, _address: Address, _message: Bytes) -> Result<Bytes> {
-        todo!()
+        unsupported("eth_sign")
     }
 
     async fn sign_transaction(&self, _transaction: CallRequest) -> Result<Bytes> {
-        todo!()
+        unsupported("eth_signTransaction")
     }
 
     async fn sign_typed_data(&self, _address: Address, _data: Value) -> Result<Bytes> {
-        todo!()
+        unsupported("eth_signTypedData")
+    }
+
+    async fn get_logs(&self, filter: Filter) -> Result<Vec<Log>> {
+        self.with_deadline(self.kakarot_client.get_logs(filter)).await
+    }
+
+    async fn new_filter(&self, filter: Filter) -> Result<U64> {
+        self.with_deadline(self.kakarot_client.new_filter(filter)).await
+    }
+
+    async fn new_block_filter(&self) -> Result<U64> {
+        self.with_deadline(self.kakarot_client.new_block_filter()).await
+    }
+
+    async fn get_filter_changes(&self, id: U64) -> Result<FilterChanges> {
+        self.with_deadline(self.kakarot_client.filter_changes(id)).await
+    }
+
+    async fn get_filter_logs(&self, id: U64) -> Result<Vec<Log>> {
+        self.with_deadline(self.kakarot_client.filter_logs(id)).await
+    }
+
+    async fn uninstall_filter(&self, id: U64) -> Result<bool> {
+        let uninstalled = self.kakarot_client.uninstall_filter(id).await?;
+        Ok(uninstalled)
     }
 
     async fn get_proof(
@@ -260,31 +506,152 @@ impl EthApiServer for KakarotEthRpc {
         _keys: Vec<H256>,
         _block_number: Option<BlockId>,
     ) -> Result<EIP1186AccountProofResponse> {
-        todo!()
+        unsupported("eth_getProof")
     }
 }
 
 #[rpc(server, client)]
-trait KakarotCustomApi {
+pub trait KakarotCustomApi {
     #[method(name = "kakarot_getTokenBalances")]
     async fn token_balances(
         &self,
         address: Address,
         contract_addresses: Vec<Address>,
+        block_id: Option<BlockId>,
     ) -> jsonrpsee::core::RpcResult<TokenBalances>;
+
+    /// Returns the Starknet block underlying an EVM block.
+    #[method(name = "kakarot_getStarknetBlock")]
+    async fn starknet_block(&self, block_id: BlockId) -> jsonrpsee::core::RpcResult<StarknetBlockInfo>;
+
+    /// Returns the blocks numbered `from_block` to `to_block` (inclusive), for indexers catching
+    /// up.
+    #[method(name = "kakarot_getBlockRange")]
+    async fn block_range(
+        &self,
+        from_block: U64,
+        to_block: U64,
+        full: bool,
+    ) -> jsonrpsee::core::RpcResult<Vec<RichBlock>>;
+
+    /// Returns the Kakarot events emitted between `from_block` and `to_block` (inclusive), with
+    /// their raw Starknet keys and data next to their EVM log conversion.
+    #[method(name = "kakarot_getEventsRaw")]
+    async fn raw_events(
+        &self,
+        from_block: U64,
+        to_block: U64,
+        keys: Option<Vec<Vec<FieldElement>>>,
+    ) -> jsonrpsee::core::RpcResult<Vec<RawKakarotEvent>>;
+
+    /// Sends a raw transaction without simulating it first, even if pre-validation is enabled.
+    #[method(name = "kakarot_sendRawTransactionUnchecked")]
+    async fn send_raw_transaction_unchecked(&self, bytes: Bytes) -> jsonrpsee::core::RpcResult<H256>;
+
+    /// Returns the L1 settlement status of a transaction.
+    #[method(name = "kakarot_getL1Confirmation")]
+    async fn l1_confirmation(&self, hash: H256) -> jsonrpsee::core::RpcResult<Option<L1Confirmation>>;
+
+    /// Returns the native token balance changes of the accounts touched by a transaction,
+    /// including the value sent by its internal calls and the fee paid.
+    #[method(name = "kakarot_getBalanceChanges")]
+    async fn balance_changes(&self, hash: H256) -> jsonrpsee::core::RpcResult<Option<Vec<BalanceChange>>>;
+
+    /// Returns the `wallet_addEthereumChain` (EIP-3085) parameters of the chain, for dapps to add
+    /// it to wallets.
+    #[method(name = "kakarot_getChainMetadata")]
+    fn chain_metadata(&self) -> jsonrpsee::core::RpcResult<ChainMetadata>;
+
+    /// Returns the number of upstream blocks found inconsistent with the blocks served before
+    /// them, which were not served.
+    #[method(name = "kakarot_getBlockInconsistencies")]
+    fn block_inconsistencies(&self) -> jsonrpsee::core::RpcResult<U64>;
+
+    /// Returns the gas estimate of `eth_estimateGas`, flagging whether it includes the deployment
+    /// of the sender account by its first transaction.
+    #[method(name = "kakarot_estimateGas")]
+    async fn gas_estimate(
+        &self,
+        request: CallRequest,
+        block_number: Option<BlockId>,
+    ) -> jsonrpsee::core::RpcResult<GasEstimate>;
 }
 
 #[async_trait]
 impl KakarotCustomApiServer for KakarotEthRpc {
-    async fn token_balances(&self, address: Address, contract_addresses: Vec<Address>) -> Result<TokenBalances> {
-        let token_balances = self.kakarot_client.token_balances(address, contract_addresses).await?;
-        Ok(token_balances)
+    async fn token_balances(
+        &self,
+        address: Address,
+        contract_addresses: Vec<Address>,
+        block_id: Option<BlockId>,
+    ) -> Result<TokenBalances> {
+        let block_id = block_id.unwrap_or_else(|| self.kakarot_client.default_block_id());
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.token_balances(address, contract_addresses, starknet_block_id).await
+        })
+        .await
+    }
+
+    async fn starknet_block(&self, block_id: BlockId) -> Result<StarknetBlockInfo> {
+        self.with_deadline(async {
+            let starknet_block_id = self.kakarot_client.resolve_block_id(block_id).await?;
+            self.kakarot_client.starknet_block_info(starknet_block_id).await
+        })
+        .await
+    }
+
+    async fn block_range(&self, from_block: U64, to_block: U64, full: bool) -> Result<Vec<RichBlock>> {
+        self.with_deadline(self.kakarot_client.get_eth_blocks_in_range(from_block.as_u64(), to_block.as_u64(), full))
+            .await
+    }
+
+    async fn raw_events(
+        &self,
+        from_block: U64,
+        to_block: U64,
+        keys: Option<Vec<Vec<FieldElement>>>,
+    ) -> Result<Vec<RawKakarotEvent>> {
+        self.with_deadline(self.kakarot_client.raw_events(from_block.as_u64(), to_block.as_u64(), keys)).await
+    }
+
+    async fn send_raw_transaction_unchecked(&self, bytes: Bytes) -> Result<H256> {
+        self.with_deadline(self.kakarot_client.send_unchecked_transaction(bytes)).await
+    }
+
+    async fn l1_confirmation(&self, hash: H256) -> Result<Option<L1Confirmation>> {
+        self.with_deadline(self.kakarot_client.l1_confirmation(hash)).await
+    }
+
+    async fn balance_changes(&self, hash: H256) -> Result<Option<Vec<BalanceChange>>> {
+        self.with_deadline(self.kakarot_client.balance_changes(hash)).await
+    }
+
+    fn chain_metadata(&self) -> Result<ChainMetadata> {
+        Ok(self.kakarot_client.chain_metadata())
+    }
+
+    fn block_inconsistencies(&self) -> Result<U64> {
+        Ok(U64::from(self.kakarot_client.block_guard().inconsistencies()))
+    }
+
+    async fn gas_estimate(&self, request: CallRequest, block_number: Option<BlockId>) -> Result<GasEstimate> {
+        self.with_deadline(self.kakarot_client.estimate_gas(request, block_number)).await
     }
 }
 
 impl KakarotEthRpc {
     #[must_use]
-    pub fn new(kakarot_client: Box<dyn KakarotProvider>) -> Self {
-        Self { kakarot_client }
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>) -> Self {
+        Self { kakarot_client, head_cache: None }
+    }
+
+    /// Serves the latest block, block number and priority fee suggestion up to `staleness_budget`
+    /// old, while refreshing them in the background, which bounds their latency under load. A
+    /// zero budget disables the caching.
+    #[must_use]
+    pub fn with_staleness_budget(mut self, staleness_budget: Duration) -> Self {
+        self.head_cache = (!staleness_budget.is_zero()).then(|| HeadCache::new(staleness_budget));
+        self
     }
 }
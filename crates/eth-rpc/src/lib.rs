@@ -1,15 +1,28 @@
 // //! Kakarot RPC module for Ethereum.
 // //! It is an adapter layer to interact with Kakarot ZK-EVM.
-use std::net::{AddrParseError, SocketAddr};
+use std::net::AddrParseError;
+use std::sync::Arc;
 pub mod eth_rpc;
 use config::RPCConfig;
-use eth_api::EthApiServer;
-use eth_rpc::KakarotEthRpc;
+pub mod admin_api;
+pub mod admin_rpc;
 pub mod config;
+pub mod debug_api;
+pub mod debug_rpc;
 pub mod eth_api;
+pub mod eth_pubsub_api;
+pub mod eth_pubsub_rpc;
+pub mod net_api;
+pub mod net_rpc;
+pub mod rpc;
+pub mod server;
+pub mod txpool_api;
+pub mod txpool_rpc;
+pub mod web3_api;
+pub mod web3_rpc;
 use eyre::Result;
-use jsonrpsee::server::{ServerBuilder, ServerHandle};
 use kakarot_rpc_core::client::client_api::KakarotProvider;
+use server::{KakarotRpcServerBuilder, KakarotRpcServerHandle};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -18,6 +31,10 @@ pub enum RpcError {
     JsonRpcServerError(#[from] jsonrpsee::core::Error),
     #[error(transparent)]
     ParseError(#[from] AddrParseError),
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
+    #[error("{0} should be greater than 0")]
+    ZeroLimit(&'static str),
 }
 
 /// # Errors
@@ -26,15 +43,7 @@ pub enum RpcError {
 pub async fn run_server(
     starknet_client: Box<dyn KakarotProvider>,
     rpc_config: RPCConfig,
-) -> Result<(SocketAddr, ServerHandle), RpcError> {
-    let RPCConfig { socket_addr } = rpc_config;
-
-    let server = ServerBuilder::default().build(socket_addr.parse::<SocketAddr>()?).await?;
-
-    let addr = server.local_addr()?;
-
-    let rpc_calls = KakarotEthRpc::new(starknet_client);
-    let handle = server.start(rpc_calls.into_rpc())?;
-
-    Ok((addr, handle))
+) -> Result<KakarotRpcServerHandle, RpcError> {
+    let kakarot_client: Arc<dyn KakarotProvider> = Arc::from(starknet_client);
+    KakarotRpcServerBuilder::from_config(kakarot_client, rpc_config).build()?.start()
 }
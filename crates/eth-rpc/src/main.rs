@@ -1,6 +1,6 @@
 use dotenv::dotenv;
 use eyre::Result;
-use kakarot_rpc::config::RPCConfig;
+use kakarot_rpc::config::{config_schema, RPCConfig};
 use kakarot_rpc::run_server;
 use kakarot_rpc_core::client::config::StarknetConfig;
 use kakarot_rpc_core::client::KakarotClient;
@@ -8,6 +8,13 @@ use tracing_subscriber::util::SubscriberInitExt;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // `kakarot-rpc config schema` prints the JSON Schema of the configuration instead of serving
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args == ["config", "schema"] {
+        println!("{}", serde_json::to_string_pretty(&config_schema())?);
+        return Ok(());
+    }
+
     dotenv().ok();
     // Environment variables are safe to use after this
 
@@ -15,12 +22,12 @@ async fn main() -> Result<()> {
         .add_directive("jsonrpsee[method_call{name = \"eth_chainId\"}]=trace".parse()?);
     tracing_subscriber::FmtSubscriber::builder().with_env_filter(filter).finish().try_init()?;
 
-    let starknet_config = StarknetConfig::from_env()?;
     let rpc_config = RPCConfig::from_env()?;
+    let starknet_config = StarknetConfig::from_env()?.with_fallback_rpc_url(rpc_config.url());
     let kakarot_client = KakarotClient::new(starknet_config)?;
 
-    let (server_addr, server_handle) = run_server(Box::new(kakarot_client), rpc_config).await?;
-    let url = format!("http://{server_addr}");
+    let server_handle = run_server(Box::new(kakarot_client), rpc_config).await?;
+    let url = format!("http://{}", server_handle.local_addr());
 
     println!("RPC Server running on {url}...");
 
@@ -0,0 +1,20 @@
+use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::proc_macros::rpc;
+use reth_primitives::U64;
+
+/// Net API
+#[rpc(server, namespace = "net")]
+#[async_trait]
+pub trait NetApi {
+    /// Returns the network id, which is the chain id encoded as a decimal string.
+    #[method(name = "version")]
+    fn version(&self) -> Result<String>;
+
+    /// Returns whether the client is listening for network connections.
+    #[method(name = "listening")]
+    async fn listening(&self) -> Result<bool>;
+
+    /// Returns the number of peers connected to the client.
+    #[method(name = "peerCount")]
+    async fn peer_count(&self) -> Result<U64>;
+}
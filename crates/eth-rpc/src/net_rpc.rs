@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use jsonrpsee::core::{async_trait, RpcResult as Result};
+use kakarot_rpc_core::client::client_api::KakarotProvider;
+use reth_primitives::U64;
+
+use crate::net_api::NetApiServer;
+
+/// The RPC module for the `net` namespace.
+///
+/// The adapter has no peer to peer network: its only peer is the Starknet node it queries, so it
+/// listens as long as this node answers with a latest block the block guard accepts, and, if a
+/// maximum follower lag is set, as long as the chain follower keeps up with this node.
+pub struct KakarotNetRpc {
+    pub kakarot_client: Arc<dyn KakarotProvider>,
+    max_follower_lag: Option<u64>,
+}
+
+impl KakarotNetRpc {
+    #[must_use]
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>) -> Self {
+        Self { kakarot_client, max_follower_lag: None }
+    }
+
+    /// Reports the adapter unhealthy while the chain follower is more than `max_follower_lag`
+    /// blocks behind the Starknet node, so that load balancers stop sending traffic to it.
+    #[must_use]
+    pub fn with_max_follower_lag(mut self, max_follower_lag: u64) -> Self {
+        self.max_follower_lag = Some(max_follower_lag);
+        self
+    }
+
+    async fn is_upstream_healthy(&self) -> bool {
+        let head_accepted = match self.kakarot_client.block_number().await {
+            Ok(block_number) => self.kakarot_client.block_guard().check_head(block_number.as_u64()).is_ok(),
+            Err(_) => false,
+        };
+        head_accepted && self.is_follower_keeping_up()
+    }
+
+    /// Whether the chain follower is at most the maximum lag behind the Starknet node, which is
+    /// the case when the chain is not followed.
+    fn is_follower_keeping_up(&self) -> bool {
+        match (self.max_follower_lag, self.kakarot_client.follower().lag()) {
+            (Some(max_follower_lag), Some(lag)) if lag > max_follower_lag => {
+                log::warn!(
+                    "The chain follower is {lag} blocks behind the Starknet node, more than the maximum of \
+                     {max_follower_lag}"
+                );
+                false
+            }
+            _ => true,
+        }
+    }
+}
+
+#[async_trait]
+impl NetApiServer for KakarotNetRpc {
+    fn version(&self) -> Result<String> {
+        Ok(self.kakarot_client.chain_id().to_string())
+    }
+
+    async fn listening(&self) -> Result<bool> {
+        Ok(self.is_upstream_healthy().await)
+    }
+
+    async fn peer_count(&self) -> Result<U64> {
+        let peer_count = u64::from(self.is_upstream_healthy().await);
+        Ok(U64::from(peer_count))
+    }
+}
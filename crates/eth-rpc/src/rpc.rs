@@ -0,0 +1,247 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use jsonrpsee::RpcModule;
+use kakarot_rpc_core::client::client_api::KakarotProvider;
+use kakarot_rpc_core::client::constants::requests::NEW_HEADS_POLL_INTERVAL_MS;
+use kakarot_rpc_core::client::subscriptions::SubscriptionManager;
+use serde::{Deserialize, Serialize};
+use tokio::runtime::Handle;
+
+use crate::debug_api::DebugApiServer;
+use crate::debug_rpc::KakarotDebugRpc;
+use crate::eth_api::EthApiServer;
+use crate::eth_pubsub_api::{EthPubSubApiServer, KakarotPubSubApiServer};
+use crate::eth_pubsub_rpc::KakarotEthPubSubRpc;
+use crate::eth_rpc::{KakarotCustomApiServer, KakarotEthRpc, STUB_METHODS};
+use crate::net_api::NetApiServer;
+use crate::net_rpc::KakarotNetRpc;
+use crate::txpool_api::TxpoolApiServer;
+use crate::txpool_rpc::KakarotTxpoolRpc;
+use crate::web3_api::Web3ApiServer;
+use crate::web3_rpc::KakarotWeb3Rpc;
+use crate::RpcError;
+
+/// The RPC namespaces served by Kakarot.
+///
+/// The `admin` namespace is not one of them, as it is only served on its own authenticated
+/// listener, see
+/// [`KakarotRpcServerBuilder::with_admin`](crate::server::KakarotRpcServerBuilder::with_admin).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KakarotRpcNamespace {
+    Eth,
+    Kakarot,
+    Debug,
+    Txpool,
+    Net,
+    Web3,
+}
+
+impl KakarotRpcNamespace {
+    pub const ALL: [KakarotRpcNamespace; 6] = [
+        KakarotRpcNamespace::Eth,
+        KakarotRpcNamespace::Kakarot,
+        KakarotRpcNamespace::Debug,
+        KakarotRpcNamespace::Txpool,
+        KakarotRpcNamespace::Net,
+        KakarotRpcNamespace::Web3,
+    ];
+
+    /// The namespaces served by default, all of them as they are safe to expose publicly.
+    pub const DEFAULT: [KakarotRpcNamespace; 6] = Self::ALL;
+}
+
+impl fmt::Display for KakarotRpcNamespace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KakarotRpcNamespace::Eth => write!(f, "eth"),
+            KakarotRpcNamespace::Kakarot => write!(f, "kakarot"),
+            KakarotRpcNamespace::Debug => write!(f, "debug"),
+            KakarotRpcNamespace::Txpool => write!(f, "txpool"),
+            KakarotRpcNamespace::Net => write!(f, "net"),
+            KakarotRpcNamespace::Web3 => write!(f, "web3"),
+        }
+    }
+}
+
+impl FromStr for KakarotRpcNamespace {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "eth" => Ok(KakarotRpcNamespace::Eth),
+            "kakarot" => Ok(KakarotRpcNamespace::Kakarot),
+            "debug" => Ok(KakarotRpcNamespace::Debug),
+            "txpool" => Ok(KakarotRpcNamespace::Txpool),
+            "net" => Ok(KakarotRpcNamespace::Net),
+            "web3" => Ok(KakarotRpcNamespace::Web3),
+            other => Err(format!("unknown RPC namespace {other}")),
+        }
+    }
+}
+
+/// A method served by the module, as listed by `kakarot_supportedMethods`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SupportedMethod {
+    pub name: String,
+    /// Whether the method is registered but not implemented yet, see [`STUB_METHODS`].
+    pub stub: bool,
+}
+
+/// Builds the RPC module served by Kakarot, from the set of enabled namespaces.
+///
+/// All namespaces are enabled by default.
+pub struct KakarotRpcModuleBuilder {
+    kakarot_client: Arc<dyn KakarotProvider>,
+    namespaces: HashSet<KakarotRpcNamespace>,
+    subscriptions_runtime: Option<Handle>,
+    method_aliases: Vec<(String, String)>,
+    staleness_budget: Duration,
+    max_follower_lag: Option<u64>,
+}
+
+impl KakarotRpcModuleBuilder {
+    #[must_use]
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>) -> Self {
+        Self {
+            kakarot_client,
+            namespaces: KakarotRpcNamespace::DEFAULT.into_iter().collect(),
+            subscriptions_runtime: None,
+            method_aliases: Vec::new(),
+            staleness_budget: Duration::ZERO,
+            max_follower_lag: None,
+        }
+    }
+
+    /// Only serves the given namespaces.
+    #[must_use]
+    pub fn with_namespaces(mut self, namespaces: impl IntoIterator<Item = KakarotRpcNamespace>) -> Self {
+        self.namespaces = namespaces.into_iter().collect();
+        self
+    }
+
+    /// Runs the polling of the subscriptions on a dedicated runtime, so that heavy request
+    /// handling cannot delay the tracking of new blocks.
+    #[must_use]
+    pub fn with_subscriptions_runtime(mut self, runtime: Handle) -> Self {
+        self.subscriptions_runtime = Some(runtime);
+        self
+    }
+
+    /// Also serves methods under other names, given as `(alias, method)` pairs, for the clients
+    /// calling them by a name of another node implementation.
+    #[must_use]
+    pub fn with_method_aliases(mut self, method_aliases: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.method_aliases = method_aliases.into_iter().collect();
+        self
+    }
+
+    /// Serves the head-adjacent queries of the `eth` namespace up to `staleness_budget` old while
+    /// refreshing them in the background, 0 to always serve them fresh.
+    #[must_use]
+    pub fn with_staleness_budget(mut self, staleness_budget: Duration) -> Self {
+        self.staleness_budget = staleness_budget;
+        self
+    }
+
+    /// Reports the adapter unhealthy on the `net` namespace while the chain follower is more than
+    /// `max_follower_lag` blocks behind the Starknet node.
+    #[must_use]
+    pub fn with_max_follower_lag(mut self, max_follower_lag: u64) -> Self {
+        self.max_follower_lag = Some(max_follower_lag);
+        self
+    }
+
+    #[must_use]
+    pub fn enable(mut self, namespace: KakarotRpcNamespace) -> Self {
+        self.namespaces.insert(namespace);
+        self
+    }
+
+    #[must_use]
+    pub fn disable(mut self, namespace: KakarotRpcNamespace) -> Self {
+        self.namespaces.remove(&namespace);
+        self
+    }
+
+    /// # Errors
+    ///
+    /// Will return `Err` if two namespaces register the same method, or if an alias is already
+    /// registered or targets a method which is not served.
+    ///
+    /// Also serves `rpc_modules` and, with the `kakarot` namespace, `kakarot_supportedMethods`,
+    /// which list the served namespaces and methods.
+    pub fn build(self) -> Result<RpcModule<()>, RpcError> {
+        let mut module = RpcModule::new(());
+        // The subscriptions of the `eth` and `kakarot` namespaces share their polling
+        let subscriptions = SubscriptionManager::new(
+            self.kakarot_client.clone(),
+            Duration::from_millis(NEW_HEADS_POLL_INTERVAL_MS),
+            self.subscriptions_runtime.clone(),
+        );
+
+        for namespace in KakarotRpcNamespace::ALL.into_iter().filter(|namespace| self.namespaces.contains(namespace)) {
+            let kakarot_client = self.kakarot_client.clone();
+            match namespace {
+                KakarotRpcNamespace::Eth => {
+                    module.merge(EthApiServer::into_rpc(
+                        KakarotEthRpc::new(kakarot_client).with_staleness_budget(self.staleness_budget),
+                    ))?;
+                    module.merge(EthPubSubApiServer::into_rpc(KakarotEthPubSubRpc::new(subscriptions.clone())))?
+                }
+                KakarotRpcNamespace::Kakarot => {
+                    module.merge(KakarotCustomApiServer::into_rpc(KakarotEthRpc::new(kakarot_client)))?;
+                    module.merge(KakarotPubSubApiServer::into_rpc(KakarotEthPubSubRpc::new(subscriptions.clone())))?
+                }
+                KakarotRpcNamespace::Debug => module.merge(KakarotDebugRpc::new(kakarot_client).into_rpc())?,
+                KakarotRpcNamespace::Txpool => module.merge(KakarotTxpoolRpc::new(kakarot_client).into_rpc())?,
+                KakarotRpcNamespace::Net => {
+                    let net = KakarotNetRpc::new(kakarot_client);
+                    let net = match self.max_follower_lag {
+                        Some(max_follower_lag) => net.with_max_follower_lag(max_follower_lag),
+                        None => net,
+                    };
+                    module.merge(net.into_rpc())?
+                }
+                KakarotRpcNamespace::Web3 => module.merge(KakarotWeb3Rpc::new().into_rpc())?,
+            }
+        }
+
+        // Method names live as long as the module, which is served until the process exits
+        let mut stub_methods: HashSet<String> = STUB_METHODS.iter().map(ToString::to_string).collect();
+        for (alias, method) in self.method_aliases {
+            if stub_methods.contains(&method) {
+                stub_methods.insert(alias.clone());
+            }
+            module.register_alias(Box::leak(alias.into_boxed_str()), Box::leak(method.into_boxed_str()))?;
+        }
+
+        // The capabilities are listed from the registered methods, so they cannot drift from them
+        let mut method_names: Vec<String> = module.method_names().map(ToString::to_string).collect();
+        method_names.push("rpc_modules".to_string());
+        if self.namespaces.contains(&KakarotRpcNamespace::Kakarot) {
+            method_names.push("kakarot_supportedMethods".to_string());
+        }
+        method_names.sort();
+
+        let modules: BTreeMap<String, String> = method_names
+            .iter()
+            .filter_map(|name| name.split_once('_'))
+            .map(|(namespace, _)| (namespace.to_string(), "1.0".to_string()))
+            .collect();
+        module.register_method("rpc_modules", move |_, _| Ok(modules.clone()))?;
+
+        if self.namespaces.contains(&KakarotRpcNamespace::Kakarot) {
+            let supported_methods: Vec<SupportedMethod> = method_names
+                .into_iter()
+                .map(|name| SupportedMethod { stub: stub_methods.contains(&name), name })
+                .collect();
+            module.register_method("kakarot_supportedMethods", move |_, _| Ok(supported_methods.clone()))?;
+        }
+
+        Ok(module)
+    }
+}
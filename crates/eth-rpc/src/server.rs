@@ -0,0 +1,715 @@
+use std::future::Future;
+use std::net::{SocketAddr, TcpListener};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use jsonrpsee::server::logger::Logger;
+use jsonrpsee::server::{ServerBuilder, ServerHandle};
+use jsonrpsee::RpcModule;
+use kakarot_rpc_core::client::client_api::KakarotProvider;
+use kakarot_rpc_core::client::constants::requests::{NEW_HEADS_POLL_INTERVAL_MS, SUBMISSION_QUEUE_FLUSH_INTERVAL_MS};
+use kakarot_rpc_core::client::webhooks::deliver_webhooks;
+use kakarot_rpc_core::models::metadata::with_kakarot_metadata;
+use tokio::runtime::Handle;
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+use tower_http::auth::require_authorization::Bearer;
+use tower_http::validate_request::ValidateRequestHeaderLayer;
+use url::Url;
+
+use crate::admin_api::AdminApiServer;
+use crate::admin_rpc::KakarotAdminRpc;
+use crate::config::{
+    RPCConfig, DEFAULT_MAX_BUFFERED_MESSAGES, DEFAULT_MAX_CONNECTIONS, DEFAULT_MAX_REQUESTS_PER_SECOND,
+    DEFAULT_MAX_REQUEST_SIZE, DEFAULT_MAX_RESPONSE_SIZE, DEFAULT_MAX_SUBSCRIPTIONS,
+};
+use crate::rpc::{KakarotRpcModuleBuilder, KakarotRpcNamespace};
+use crate::RpcError;
+
+/// Builds a Kakarot RPC server, for binaries embedding it, e.g. test frameworks or node
+/// products.
+///
+/// The server binds `127.0.0.1:0` and serves all the namespaces by default. The `admin`
+/// namespace is only served on its own listener, to callers presenting its bearer token:
+///
+/// ```ignore
+/// let server = KakarotRpcServerBuilder::new(kakarot_client).with_socket_addr("0.0.0.0:3030").build()?;
+/// let handle = server.start()?;
+/// // ... serve until done
+/// handle.stop();
+/// handle.stopped().await;
+/// ```
+pub struct KakarotRpcServerBuilder<L = ()> {
+    kakarot_client: Arc<dyn KakarotProvider>,
+    socket_addr: String,
+    namespaces: Vec<KakarotRpcNamespace>,
+    subscription_worker_threads: usize,
+    method_aliases: Vec<(String, String)>,
+    staleness_budget: Duration,
+    max_follower_lag: Option<u64>,
+    limits: ServerLimits,
+    admin: Option<AdminConfig>,
+    webhook_urls: Vec<Url>,
+    logger: L,
+}
+
+/// The listener of the `admin` namespace, and the bearer token its callers must present.
+struct AdminConfig {
+    socket_addr: String,
+    token: String,
+}
+
+/// The limits protecting the server from abusive clients, enforced with JSON-RPC errors.
+#[derive(Debug, Clone, Copy)]
+struct ServerLimits {
+    max_request_size: u32,
+    max_response_size: u32,
+    max_connections: u32,
+    max_subscriptions: u32,
+    max_requests_per_second: u32,
+    max_buffered_messages: u32,
+}
+
+impl ServerLimits {
+    /// Rejects the limits of 0, which would refuse every request rather than none.
+    fn validate(&self) -> Result<(), RpcError> {
+        let limits = [
+            ("max_request_size", self.max_request_size),
+            ("max_response_size", self.max_response_size),
+            ("max_connections", self.max_connections),
+            ("max_subscriptions", self.max_subscriptions),
+            ("max_requests_per_second", self.max_requests_per_second),
+            ("max_buffered_messages", self.max_buffered_messages),
+        ];
+        match limits.into_iter().find(|(_, limit)| *limit == 0) {
+            Some((name, _)) => Err(RpcError::ZeroLimit(name)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl KakarotRpcServerBuilder {
+    #[must_use]
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>) -> Self {
+        Self {
+            kakarot_client,
+            socket_addr: "127.0.0.1:0".to_string(),
+            namespaces: KakarotRpcNamespace::DEFAULT.to_vec(),
+            subscription_worker_threads: 0,
+            method_aliases: Vec::new(),
+            staleness_budget: Duration::ZERO,
+            max_follower_lag: None,
+            limits: ServerLimits {
+                max_request_size: DEFAULT_MAX_REQUEST_SIZE,
+                max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+                max_connections: DEFAULT_MAX_CONNECTIONS,
+                max_subscriptions: DEFAULT_MAX_SUBSCRIPTIONS,
+                max_requests_per_second: DEFAULT_MAX_REQUESTS_PER_SECOND,
+                max_buffered_messages: DEFAULT_MAX_BUFFERED_MESSAGES,
+            },
+            admin: None,
+            webhook_urls: Vec::new(),
+            logger: (),
+        }
+    }
+
+    /// Creates a builder serving the address, namespaces, subscription runtime, limits, method
+    /// aliases, staleness budget, maximum follower lag, admin listener and webhooks of the config.
+    #[must_use]
+    pub fn from_config(kakarot_client: Arc<dyn KakarotProvider>, rpc_config: RPCConfig) -> Self {
+        let RPCConfig {
+            socket_addr,
+            namespaces,
+            subscription_worker_threads,
+            max_request_size,
+            max_response_size,
+            max_connections,
+            max_subscriptions,
+            max_requests_per_second,
+            max_buffered_messages,
+            method_aliases,
+            staleness_budget_ms,
+            max_follower_lag,
+            admin,
+            webhook_urls,
+        } = rpc_config;
+        let builder = match max_follower_lag {
+            Some(max_follower_lag) => Self::new(kakarot_client).with_max_follower_lag(max_follower_lag),
+            None => Self::new(kakarot_client),
+        };
+        let builder = match admin {
+            Some((admin_socket_addr, admin_token)) => builder.with_admin(admin_socket_addr, admin_token),
+            None => builder,
+        };
+        builder
+            .with_socket_addr(socket_addr)
+            .with_namespaces(namespaces)
+            .with_subscription_worker_threads(subscription_worker_threads)
+            .with_max_request_size(max_request_size)
+            .with_max_response_size(max_response_size)
+            .with_max_connections(max_connections)
+            .with_max_subscriptions(max_subscriptions)
+            .with_max_requests_per_second(max_requests_per_second)
+            .with_max_buffered_messages(max_buffered_messages)
+            .with_method_aliases(method_aliases)
+            .with_staleness_budget(Duration::from_millis(staleness_budget_ms))
+            .with_webhooks(webhook_urls)
+    }
+}
+
+impl<L: Logger> KakarotRpcServerBuilder<L> {
+    /// Binds the server to the address, port 0 picking a free port.
+    #[must_use]
+    pub fn with_socket_addr(mut self, socket_addr: impl Into<String>) -> Self {
+        self.socket_addr = socket_addr.into();
+        self
+    }
+
+    /// Only serves the given namespaces.
+    #[must_use]
+    pub fn with_namespaces(mut self, namespaces: impl IntoIterator<Item = KakarotRpcNamespace>) -> Self {
+        self.namespaces = namespaces.into_iter().collect();
+        self
+    }
+
+    /// Polls for the subscriptions on a runtime with this number of threads, 0 to poll on the
+    /// runtime serving the requests.
+    #[must_use]
+    pub fn with_subscription_worker_threads(mut self, worker_threads: usize) -> Self {
+        self.subscription_worker_threads = worker_threads;
+        self
+    }
+
+    /// Also serves methods under other names, given as `(alias, method)` pairs.
+    #[must_use]
+    pub fn with_method_aliases(mut self, method_aliases: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.method_aliases = method_aliases.into_iter().collect();
+        self
+    }
+
+    /// Serves the latest block, block number and priority fee suggestion up to `staleness_budget`
+    /// old while refreshing them in the background, 0 to always serve them fresh.
+    #[must_use]
+    pub fn with_staleness_budget(mut self, staleness_budget: Duration) -> Self {
+        self.staleness_budget = staleness_budget;
+        self
+    }
+
+    /// Reports the adapter unhealthy on `net_listening` and `net_peerCount` while the chain
+    /// follower is more than `max_follower_lag` blocks behind the Starknet node.
+    #[must_use]
+    pub fn with_max_follower_lag(mut self, max_follower_lag: u64) -> Self {
+        self.max_follower_lag = Some(max_follower_lag);
+        self
+    }
+
+    /// Rejects the HTTP request bodies and WebSocket messages larger than `max_request_size`
+    /// bytes.
+    #[must_use]
+    pub fn with_max_request_size(mut self, max_request_size: u32) -> Self {
+        self.limits.max_request_size = max_request_size;
+        self
+    }
+
+    /// Replaces the responses larger than `max_response_size` bytes with an error.
+    #[must_use]
+    pub fn with_max_response_size(mut self, max_response_size: u32) -> Self {
+        self.limits.max_response_size = max_response_size;
+        self
+    }
+
+    /// Refuses the connections beyond `max_connections` concurrent ones.
+    #[must_use]
+    pub fn with_max_connections(mut self, max_connections: u32) -> Self {
+        self.limits.max_connections = max_connections;
+        self
+    }
+
+    /// Rejects the subscriptions of a WebSocket connection beyond `max_subscriptions`.
+    #[must_use]
+    pub fn with_max_subscriptions(mut self, max_subscriptions: u32) -> Self {
+        self.limits.max_subscriptions = max_subscriptions;
+        self
+    }
+
+    /// Answers the HTTP requests of a connection beyond `max_requests_per_second` per second
+    /// with a `429 Too Many Requests`.
+    #[must_use]
+    pub fn with_max_requests_per_second(mut self, max_requests_per_second: u32) -> Self {
+        self.limits.max_requests_per_second = max_requests_per_second;
+        self
+    }
+
+    /// Stops reading the messages of a WebSocket connection while `max_buffered_messages` of its
+    /// messages wait to be sent.
+    #[must_use]
+    pub fn with_max_buffered_messages(mut self, max_buffered_messages: u32) -> Self {
+        self.limits.max_buffered_messages = max_buffered_messages;
+        self
+    }
+
+    /// Serves the `admin` namespace on its own listener bound to `socket_addr`, only to the
+    /// requests authorized by the bearer `token`.
+    #[must_use]
+    pub fn with_admin(mut self, socket_addr: impl Into<String>, token: impl Into<String>) -> Self {
+        self.admin = Some(AdminConfig { socket_addr: socket_addr.into(), token: token.into() });
+        self
+    }
+
+    /// Posts the activity of the watched addresses to the webhooks, see [`deliver_webhooks`].
+    #[must_use]
+    pub fn with_webhooks(mut self, webhook_urls: impl IntoIterator<Item = Url>) -> Self {
+        self.webhook_urls = webhook_urls.into_iter().collect();
+        self
+    }
+
+    /// Wraps the handling of the requests in a middleware, called on every connection, request
+    /// and response.
+    #[must_use]
+    pub fn with_logger<T: Logger>(self, logger: T) -> KakarotRpcServerBuilder<T> {
+        KakarotRpcServerBuilder {
+            kakarot_client: self.kakarot_client,
+            socket_addr: self.socket_addr,
+            namespaces: self.namespaces,
+            subscription_worker_threads: self.subscription_worker_threads,
+            method_aliases: self.method_aliases,
+            staleness_budget: self.staleness_budget,
+            max_follower_lag: self.max_follower_lag,
+            limits: self.limits,
+            admin: self.admin,
+            webhook_urls: self.webhook_urls,
+            logger,
+        }
+    }
+
+    /// Binds the socket of the server and builds the RPC module it serves.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if a limit is 0, if an address is invalid or cannot be bound, if two
+    /// namespaces register the same method, or if a method alias is invalid.
+    pub fn build(self) -> Result<KakarotRpcServer<L>, RpcError> {
+        self.limits.validate()?;
+        let (listener, local_addr) = bind(&self.socket_addr)?;
+        let admin = match self.admin {
+            Some(AdminConfig { socket_addr, token }) => {
+                let (listener, local_addr) = bind(&socket_addr)?;
+                Some(AdminListener { listener, local_addr, token })
+            }
+            None => None,
+        };
+        // The activity of the watched addresses is recorded by following the chain
+        let follow_chain = admin.is_some() || !self.kakarot_client.watchlist().is_empty();
+
+        let mut module = KakarotRpcModuleBuilder::new(self.kakarot_client.clone())
+            .with_namespaces(self.namespaces)
+            .with_method_aliases(self.method_aliases)
+            .with_staleness_budget(self.staleness_budget);
+        if let Some(max_follower_lag) = self.max_follower_lag {
+            module = module.with_max_follower_lag(max_follower_lag);
+        }
+        let subscriptions_runtime = match self.subscription_worker_threads {
+            0 => None,
+            worker_threads => Some(SubscriptionsRuntime::spawn(worker_threads)?),
+        };
+        if let Some(runtime) = &subscriptions_runtime {
+            module = module.with_subscriptions_runtime(runtime.handle());
+        }
+
+        Ok(KakarotRpcServer {
+            kakarot_client: self.kakarot_client,
+            listener,
+            local_addr,
+            module: module.build()?,
+            limits: self.limits,
+            admin,
+            webhook_urls: self.webhook_urls,
+            logger: self.logger,
+            follow_chain,
+            subscriptions_runtime,
+        })
+    }
+}
+
+fn bind(socket_addr: &str) -> Result<(TcpListener, SocketAddr), RpcError> {
+    let listener = TcpListener::bind(socket_addr.parse::<SocketAddr>()?)?;
+    listener.set_nonblocking(true)?;
+    let local_addr = listener.local_addr()?;
+    Ok((listener, local_addr))
+}
+
+/// A Kakarot RPC server bound to its socket, which serves requests once started.
+pub struct KakarotRpcServer<L = ()> {
+    kakarot_client: Arc<dyn KakarotProvider>,
+    listener: TcpListener,
+    local_addr: SocketAddr,
+    module: RpcModule<()>,
+    limits: ServerLimits,
+    admin: Option<AdminListener>,
+    webhook_urls: Vec<Url>,
+    logger: L,
+    follow_chain: bool,
+    subscriptions_runtime: Option<SubscriptionsRuntime>,
+}
+
+/// The bound listener of the `admin` namespace.
+struct AdminListener {
+    listener: TcpListener,
+    local_addr: SocketAddr,
+    token: String,
+}
+
+impl<L: Logger> KakarotRpcServer<L> {
+    #[must_use]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the address of the listener of the `admin` namespace, if served.
+    #[must_use]
+    pub fn admin_local_addr(&self) -> Option<SocketAddr> {
+        self.admin.as_ref().map(|admin| admin.local_addr)
+    }
+
+    /// Starts serving the requests, submitting the transactions queued while the Starknet node is
+    /// unavailable, and following the chain for the watched addresses if any may be watched.
+    ///
+    /// Must be called from within a Tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the server fails to start.
+    pub fn start(self) -> Result<KakarotRpcServerHandle, RpcError> {
+        let ServerLimits {
+            max_request_size,
+            max_response_size,
+            max_connections,
+            max_subscriptions,
+            max_requests_per_second,
+            max_buffered_messages,
+        } = self.limits;
+        let server = ServerBuilder::default()
+            .max_request_body_size(max_request_size)
+            .max_response_body_size(max_response_size)
+            .max_connections(max_connections)
+            .max_subscriptions_per_connection(max_subscriptions)
+            .set_message_buffer_capacity(max_buffered_messages)
+            .set_middleware(
+                tower::ServiceBuilder::new()
+                    .layer(RequestRateLimitLayer::new(max_requests_per_second))
+                    .layer(KakarotMetadataLayer),
+            )
+            .set_logger(self.logger.clone())
+            .build_from_tcp(self.listener)?;
+        let handle = server.start(self.module)?;
+
+        let admin_handle = match self.admin {
+            Some(AdminListener { listener, local_addr, token }) => {
+                let server = ServerBuilder::default()
+                    .max_request_body_size(max_request_size)
+                    .max_response_body_size(max_response_size)
+                    .max_connections(max_connections)
+                    .max_subscriptions_per_connection(max_subscriptions)
+                    .set_message_buffer_capacity(max_buffered_messages)
+                    .set_middleware(
+                        tower::ServiceBuilder::new()
+                            .layer(RequestRateLimitLayer::new(max_requests_per_second))
+                            .layer(ValidateRequestHeaderLayer::<Bearer<hyper::Body>>::bearer(&token)),
+                    )
+                    .set_logger(self.logger)
+                    .build_from_tcp(listener)?;
+                Some((local_addr, server.start(KakarotAdminRpc::new(self.kakarot_client.clone()).into_rpc())?))
+            }
+            None => None,
+        };
+
+        // The follower is shared with the subscriptions, so it runs on their runtime too
+        if self.follow_chain {
+            self.kakarot_client.follower().start(
+                self.kakarot_client.clone(),
+                Duration::from_millis(NEW_HEADS_POLL_INTERVAL_MS),
+                self.subscriptions_runtime.as_ref().map(SubscriptionsRuntime::handle).as_ref(),
+            );
+        }
+
+        let flush_task = self.kakarot_client.is_submission_queue_enabled().then(|| {
+            let kakarot_client = self.kakarot_client.clone();
+            let server_handle = handle.clone();
+            tokio::spawn(async move {
+                let mut interval = tokio::time::interval(Duration::from_millis(SUBMISSION_QUEUE_FLUSH_INTERVAL_MS));
+                // The queue is flushed until the server stops, however it is stopped
+                let stopped = server_handle.stopped();
+                tokio::pin!(stopped);
+                loop {
+                    tokio::select! {
+                        _ = &mut stopped => break,
+                        _ = interval.tick() => {
+                            if let Err(err) = kakarot_client.flush_submission_queue().await {
+                                log::warn!("Failed to flush the submission queue: {err}");
+                            }
+                        }
+                    }
+                }
+            })
+        });
+
+        let webhooks_task = (!self.webhook_urls.is_empty())
+            .then(|| tokio::spawn(deliver_webhooks(self.kakarot_client.events().subscribe(), self.webhook_urls)));
+
+        Ok(KakarotRpcServerHandle {
+            kakarot_client: self.kakarot_client,
+            local_addr: self.local_addr,
+            handle,
+            admin_handle,
+            flush_task,
+            webhooks_task,
+            subscriptions_runtime: self.subscriptions_runtime,
+        })
+    }
+}
+
+/// A handle on a running Kakarot RPC server.
+pub struct KakarotRpcServerHandle {
+    kakarot_client: Arc<dyn KakarotProvider>,
+    local_addr: SocketAddr,
+    handle: ServerHandle,
+    admin_handle: Option<(SocketAddr, ServerHandle)>,
+    flush_task: Option<JoinHandle<()>>,
+    webhooks_task: Option<JoinHandle<()>>,
+    subscriptions_runtime: Option<SubscriptionsRuntime>,
+}
+
+impl KakarotRpcServerHandle {
+    #[must_use]
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Returns the address of the listener of the `admin` namespace, if served.
+    #[must_use]
+    pub fn admin_local_addr(&self) -> Option<SocketAddr> {
+        self.admin_handle.as_ref().map(|(local_addr, _)| *local_addr)
+    }
+
+    /// Stops the server, the chain follower and the subscriptions runtime, the requests being
+    /// handled are completed first.
+    ///
+    /// Stopping a stopped server does nothing.
+    pub fn stop(&self) {
+        if let Some(flush_task) = &self.flush_task {
+            flush_task.abort();
+        }
+        if let Some(webhooks_task) = &self.webhooks_task {
+            webhooks_task.abort();
+        }
+        self.kakarot_client.follower().stop();
+        if let Some(subscriptions_runtime) = &self.subscriptions_runtime {
+            subscriptions_runtime.shutdown();
+        }
+        if let Some((_, admin_handle)) = &self.admin_handle {
+            let _ = admin_handle.stop();
+        }
+        let _ = self.handle.stop();
+    }
+
+    /// Waits until the server, the `admin` listener if any, the flushing of the submission queue
+    /// and the subscriptions runtime are stopped.
+    pub async fn stopped(self) {
+        self.handle.stopped().await;
+        if let Some((_, admin_handle)) = self.admin_handle {
+            admin_handle.stopped().await;
+        }
+        if let Some(flush_task) = self.flush_task {
+            let _ = flush_task.await;
+        }
+        if let Some(subscriptions_runtime) = self.subscriptions_runtime {
+            subscriptions_runtime.stopped().await;
+        }
+    }
+}
+
+/// Query parameter with which HTTP requests opt in to the Kakarot metadata of the responses.
+pub const KAKAROT_METADATA_QUERY: &str = "kakarot_metadata=true";
+
+/// Serves the HTTP requests carrying [`KAKAROT_METADATA_QUERY`] with the Kakarot metadata.
+#[derive(Debug, Clone, Copy)]
+struct KakarotMetadataLayer;
+
+impl<S> tower::Layer<S> for KakarotMetadataLayer {
+    type Service = KakarotMetadataService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        KakarotMetadataService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct KakarotMetadataService<S> {
+    inner: S,
+}
+
+impl<S, B> tower::Service<hyper::Request<B>> for KakarotMetadataService<S>
+where
+    S: tower::Service<hyper::Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::Request<B>) -> Self::Future {
+        let requested =
+            request.uri().query().map_or(false, |query| query.split('&').any(|pair| pair == KAKAROT_METADATA_QUERY));
+        // HTTP calls are answered within the future of their request, WebSocket connections are not
+        Box::pin(with_kakarot_metadata(requested, self.inner.call(request)))
+    }
+}
+
+/// JSON-RPC error code of the requests beyond the rate limit of their connection.
+const RATE_LIMIT_EXCEEDED_CODE: i32 = -32005;
+
+/// Limits the HTTP requests of each connection to `max_requests_per_second` per second, with a
+/// bucket of as many tokens refilled continuously.
+///
+/// The layer is applied to the service of every accepted connection, so that each connection has
+/// its own bucket. A WebSocket connection only spends a token on its upgrade request.
+#[derive(Debug, Clone, Copy)]
+struct RequestRateLimitLayer {
+    max_requests_per_second: u32,
+}
+
+impl RequestRateLimitLayer {
+    fn new(max_requests_per_second: u32) -> Self {
+        Self { max_requests_per_second }
+    }
+}
+
+impl<S> tower::Layer<S> for RequestRateLimitLayer {
+    type Service = RequestRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        let bucket = RequestBucket {
+            max_requests_per_second: self.max_requests_per_second,
+            tokens: f64::from(self.max_requests_per_second),
+            refilled_at: Instant::now(),
+        };
+        RequestRateLimitService { inner, bucket: Arc::new(Mutex::new(bucket)) }
+    }
+}
+
+#[derive(Debug)]
+struct RequestBucket {
+    max_requests_per_second: u32,
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl RequestBucket {
+    /// Refills the bucket for the time elapsed since its last refill, and takes a token from it
+    /// if any is left.
+    fn try_acquire(&mut self) -> bool {
+        let now = Instant::now();
+        let capacity = f64::from(self.max_requests_per_second);
+        let refill = now.duration_since(self.refilled_at).as_secs_f64() * capacity;
+        self.tokens = (self.tokens + refill).min(capacity);
+        self.refilled_at = now;
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RequestRateLimitService<S> {
+    inner: S,
+    bucket: Arc<Mutex<RequestBucket>>,
+}
+
+impl<S, B> tower::Service<hyper::Request<B>> for RequestRateLimitService<S>
+where
+    S: tower::Service<hyper::Request<B>, Response = hyper::Response<hyper::Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::Request<B>) -> Self::Future {
+        if self.bucket.lock().expect("request bucket lock poisoned").try_acquire() {
+            return Box::pin(self.inner.call(request));
+        }
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": null,
+            "error": { "code": RATE_LIMIT_EXCEEDED_CODE, "message": "request rate limit exceeded" },
+        });
+        let response = hyper::Response::builder()
+            .status(hyper::StatusCode::TOO_MANY_REQUESTS)
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .body(hyper::Body::from(body.to_string()))
+            .expect("rate limit response is valid");
+        Box::pin(async move { Ok(response) })
+    }
+}
+
+/// The runtime dedicated to the subscriptions, parked on its own thread until it is shut down or
+/// dropped, which cancels the subscriptions it serves.
+pub struct SubscriptionsRuntime {
+    handle: Handle,
+    shutdown: Mutex<Option<oneshot::Sender<()>>>,
+    thread: std::thread::JoinHandle<()>,
+}
+
+impl SubscriptionsRuntime {
+    /// Starts a runtime with `worker_threads` worker threads.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the runtime cannot be built.
+    pub fn spawn(worker_threads: usize) -> Result<Self, std::io::Error> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(worker_threads)
+            .thread_name("kakarot-subscriptions")
+            .enable_all()
+            .build()?;
+        let handle = runtime.handle().clone();
+        let (shutdown, shutdown_requested) = oneshot::channel();
+        // A runtime cannot be dropped from an asynchronous context, so it is parked on its own
+        // thread, until the shutdown is requested or its sender dropped
+        let thread = std::thread::spawn(move || {
+            let _ = runtime.block_on(shutdown_requested);
+        });
+        Ok(Self { handle, shutdown: Mutex::new(Some(shutdown)), thread })
+    }
+
+    #[must_use]
+    pub fn handle(&self) -> Handle {
+        self.handle.clone()
+    }
+
+    /// Shuts the runtime down. Shutting down a shut down runtime does nothing.
+    pub fn shutdown(&self) {
+        if let Some(shutdown) = self.shutdown.lock().expect("subscriptions runtime lock poisoned").take() {
+            let _ = shutdown.send(());
+        }
+    }
+
+    /// Shuts the runtime down if it is not yet, and waits until its threads have exited.
+    pub async fn stopped(self) {
+        let Self { shutdown, thread, .. } = self;
+        drop(shutdown);
+        let _ = tokio::task::spawn_blocking(move || thread.join()).await;
+    }
+}
@@ -0,0 +1,16 @@
+use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::proc_macros::rpc;
+use kakarot_rpc_core::models::txpool::{TxpoolContent, TxpoolStatus};
+
+/// Txpool API
+#[rpc(server, namespace = "txpool")]
+#[async_trait]
+pub trait TxpoolApi {
+    /// Returns the pending and queued transactions, by sender and nonce.
+    #[method(name = "content")]
+    async fn content(&self) -> Result<TxpoolContent>;
+
+    /// Returns the number of pending and queued transactions.
+    #[method(name = "status")]
+    async fn status(&self) -> Result<TxpoolStatus>;
+}
@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use jsonrpsee::core::{async_trait, RpcResult as Result};
+use kakarot_rpc_core::client::client_api::KakarotProvider;
+use kakarot_rpc_core::models::txpool::{TxpoolContent, TxpoolStatus};
+use reth_primitives::U64;
+
+use crate::txpool_api::TxpoolApiServer;
+
+/// The RPC module for the `txpool` namespace, served from the Starknet pending block.
+pub struct KakarotTxpoolRpc {
+    pub kakarot_client: Arc<dyn KakarotProvider>,
+}
+
+impl KakarotTxpoolRpc {
+    #[must_use]
+    pub fn new(kakarot_client: Arc<dyn KakarotProvider>) -> Self {
+        Self { kakarot_client }
+    }
+}
+
+#[async_trait]
+impl TxpoolApiServer for KakarotTxpoolRpc {
+    async fn content(&self) -> Result<TxpoolContent> {
+        let pending_transactions = self.kakarot_client.pending_transactions().await?;
+        Ok(TxpoolContent::from(pending_transactions))
+    }
+
+    async fn status(&self) -> Result<TxpoolStatus> {
+        let pending_transactions = self.kakarot_client.pending_transactions().await?;
+        Ok(TxpoolStatus { pending: U64::from(pending_transactions.len()), queued: U64::ZERO })
+    }
+}
@@ -0,0 +1,15 @@
+use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::proc_macros::rpc;
+use reth_primitives::{Bytes, H256};
+
+/// Web3 API
+#[rpc(server, namespace = "web3")]
+pub trait Web3Api {
+    /// Returns the client version, e.g. `kakarot-rpc/v0.1.0-1a2b3c4`.
+    #[method(name = "clientVersion")]
+    fn client_version(&self) -> Result<String>;
+
+    /// Returns the Keccak-256 hash of the given data.
+    #[method(name = "sha3")]
+    fn sha3(&self, input: Bytes) -> Result<H256>;
+}
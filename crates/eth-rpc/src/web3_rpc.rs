@@ -0,0 +1,35 @@
+use jsonrpsee::core::RpcResult as Result;
+use reth_primitives::{keccak256, Bytes, H256};
+
+use crate::web3_api::Web3ApiServer;
+
+/// The RPC module for the `web3` namespace.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct KakarotWeb3Rpc;
+
+impl KakarotWeb3Rpc {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self
+    }
+}
+
+/// Returns the version of the adapter, suffixed with the commit it is built from when known.
+#[must_use]
+pub fn client_version() -> String {
+    let version = format!("kakarot-rpc/v{}", env!("CARGO_PKG_VERSION"));
+    match option_env!("KAKAROT_RPC_COMMIT") {
+        Some(commit) => format!("{version}-{commit}"),
+        None => version,
+    }
+}
+
+impl Web3ApiServer for KakarotWeb3Rpc {
+    fn client_version(&self) -> Result<String> {
+        Ok(client_version())
+    }
+
+    fn sha3(&self, input: Bytes) -> Result<H256> {
+        Ok(keccak256(input))
+    }
+}
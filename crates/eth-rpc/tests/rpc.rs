@@ -3,15 +3,53 @@ mod utils;
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeSet;
     use std::str::FromStr;
+    use std::sync::Arc;
+    use std::time::Duration;
 
+    use jsonrpsee::core::params::ArrayParams;
+    use jsonrpsee::rpc_params;
+    use jsonrpsee::types::error::METHOD_NOT_FOUND_CODE;
+    use kakarot_rpc::admin_api::AdminApiServer;
+    use kakarot_rpc::admin_rpc::KakarotAdminRpc;
+    use kakarot_rpc::config::{config_schema, RPCConfig};
     use kakarot_rpc::eth_api::EthApiServer;
-    use kakarot_rpc_core::mock::assert_helpers::{assert_block, assert_block_header, assert_transaction};
-    use reth_primitives::{BlockNumberOrTag, H160, H256, U256, U64};
-    use reth_rpc_types::Index;
+    use kakarot_rpc::eth_pubsub_api::{EthPubSubApiServer, KakarotPubSubApiServer};
+    use kakarot_rpc::eth_pubsub_rpc::KakarotEthPubSubRpc;
+    use kakarot_rpc::eth_rpc::{KakarotCustomApiServer, KakarotEthRpc, STUB_METHODS};
+    use kakarot_rpc::rpc::{KakarotRpcModuleBuilder, KakarotRpcNamespace, SupportedMethod};
+    use kakarot_rpc::server::{KakarotRpcServerBuilder, SubscriptionsRuntime, KAKAROT_METADATA_QUERY};
+    use kakarot_rpc_core::client::client_api::KakarotProvider;
+    use kakarot_rpc_core::client::config::StarknetConfig;
+    use kakarot_rpc_core::client::constants::requests::MAX_BLOCK_RANGE;
+    use kakarot_rpc_core::client::constants::{ACCOUNT_DEPLOYMENT_GAS, ESTIMATE_GAS};
+    use kakarot_rpc_core::client::events::{ClientEvent, EVENTS_CAPACITY};
+    use kakarot_rpc_core::client::helpers::create_default_transaction_receipt;
+    use kakarot_rpc_core::client::subscriptions::SubscriptionManager;
+    use kakarot_rpc_core::client::KakarotClient;
+    use kakarot_rpc_core::mock::assert_helpers::{
+        assert_block, assert_block_header, assert_transaction, fixture_eth_transaction,
+    };
+    use kakarot_rpc_core::mock::conformance::{assert_conforms, BLOCK, RECEIPT, TRANSACTION};
+    use kakarot_rpc_core::mock::wiremock_utils::{
+        fixture_transaction, mock_chain_block, mock_class_hash_at, mock_compute_starknet_address,
+        mock_compute_starknet_address_at, mock_events, mock_evm_address_of, mock_native_balance_at, mock_pending_block,
+        mock_starknet_config, setup_mock_server, PROXY_ACCOUNT_CLASS_HASH, SENDER_ADDRESS, SENDER_STARKNET_ADDRESS,
+    };
+    use kakarot_rpc_core::models::activity::{AddressActivity, WatchlistEvent};
+    use kakarot_rpc_core::models::balance::BalanceChange;
+    use kakarot_rpc_core::models::metadata::{GasEstimate, KAKAROT_METADATA_KEY};
+    use kakarot_rpc_core::models::txpool::{TxpoolContent, TxpoolStatus};
+    use reth_primitives::{BlockNumberOrTag, Bloom, Bytes, H160, H256, U128, U256, U64, U8};
+    use reth_rpc_types::{Block, BlockTransactions, Header, Index, Log, TransactionReceipt};
     use serde_json::json;
-    use starknet::core::types::{FieldElement, Transaction as StarknetTransaction};
+    use starknet::core::types::{
+        BlockId as StarknetBlockId, BlockTag, FieldElement, Transaction as StarknetTransaction,
+    };
     use starknet::macros::felt;
+    use wiremock::matchers::{body_partial_json, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
 
     use crate::utils::setup_kakarot_eth_rpc;
 
@@ -748,6 +786,217 @@ mod tests {
         assert_eq!(block_number.as_u64(), 19640);
     }
 
+    #[tokio::test]
+    async fn test_block_number_going_back_beyond_a_reorg_is_reported_once() {
+        let mock_server = setup_mock_server().await;
+        // The node serves 19640, goes back to 19630 by a reorg, then is reset to 100
+        for (priority, block_number) in [(1, 19640), (2, 19630), (3, 100)] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({ "method": "starknet_blockNumber" })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": block_number
+                })))
+                .up_to_n_times(1)
+                .with_priority(priority)
+                .mount(&mock_server)
+                .await;
+        }
+        let kakarot_rpc =
+            KakarotEthRpc::new(Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap()));
+
+        assert_eq!(kakarot_rpc.block_number().await.unwrap().as_u64(), 19640);
+        assert_eq!(kakarot_rpc.block_number().await.unwrap().as_u64(), 19630);
+        assert!(kakarot_rpc.block_number().await.is_err());
+        assert_eq!(kakarot_rpc.block_inconsistencies().unwrap(), U64::from(1));
+        // The reset is recorded, the next latest blocks are checked against it
+        assert_eq!(kakarot_rpc.block_number().await.unwrap().as_u64(), 19640);
+        assert_eq!(kakarot_rpc.block_inconsistencies().unwrap(), U64::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_head_queries_served_with_staleness_budget() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await.with_staleness_budget(Duration::from_secs(60));
+
+        // The first requests fetch the values, the next ones are served from the cache
+        let block = kakarot_rpc.block_by_number(BlockNumberOrTag::Latest, false).await.unwrap().unwrap();
+        let cached_block = kakarot_rpc.block_by_number(BlockNumberOrTag::Latest, false).await.unwrap().unwrap();
+        assert_eq!(cached_block.header.hash, block.header.hash);
+
+        assert_eq!(kakarot_rpc.block_number().await.unwrap().as_u64(), 19640);
+        assert_eq!(kakarot_rpc.block_number().await.unwrap().as_u64(), 19640);
+    }
+
+    async fn mount_syncing(mock_server: &MockServer, status: serde_json::Value) {
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "starknet_syncing" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": status
+            })))
+            .mount(mock_server)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_syncing_is_false_while_the_node_and_the_adapter_are_caught_up() {
+        let mock_server = setup_mock_server().await;
+        mount_syncing(&mock_server, json!(false)).await;
+        let kakarot_rpc =
+            KakarotEthRpc::new(Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap()));
+
+        let status = kakarot_rpc.syncing().await.unwrap();
+
+        assert_eq!(serde_json::to_value(status).unwrap(), json!(false));
+    }
+
+    #[tokio::test]
+    async fn test_syncing_reports_the_state_of_the_adapter_next_to_the_progress_of_the_node() {
+        let mock_server = setup_mock_server().await;
+        mount_syncing(
+            &mock_server,
+            json!({
+                "starting_block_hash": "0x1",
+                "starting_block_num": "0x1",
+                "current_block_hash": "0x2",
+                "current_block_num": "0x2",
+                "highest_block_hash": "0x3",
+                "highest_block_num": "0x3"
+            }),
+        )
+        .await;
+        let kakarot_rpc =
+            KakarotEthRpc::new(Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap()))
+                .with_staleness_budget(Duration::from_secs(60));
+        // Warms one of the four head caches
+        kakarot_rpc.block_number().await.unwrap();
+
+        let status = serde_json::to_value(kakarot_rpc.syncing().await.unwrap()).unwrap();
+
+        assert_eq!(status["startingBlock"], json!("0x1"));
+        assert_eq!(status["currentBlock"], json!("0x2"));
+        assert_eq!(status["highestBlock"], json!("0x3"));
+        assert_eq!(status["submissionQueueDepth"], json!(0));
+        assert_eq!(status["headCacheCoverage"], json!(0.25));
+        // The chain is not followed
+        assert!(status.get("followerLag").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_request_exceeding_deadline_is_cancelled() {
+        let mock_server = setup_mock_server().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "starknet_getBlockWithTxs" })))
+            .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(5)))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let config = StarknetConfig { request_timeout_ms: 10, ..mock_starknet_config(&mock_server.uri()) };
+        let kakarot_rpc = KakarotEthRpc::new(Arc::new(KakarotClient::new(config).unwrap()));
+
+        // The deadline covers the whole request, not each of its Starknet calls
+        let err = kakarot_rpc.block_by_number(BlockNumberOrTag::Latest, true).await.unwrap_err();
+
+        assert!(err.to_string().contains("deadline"));
+    }
+
+    #[tokio::test]
+    async fn test_balance_without_block_reads_the_configured_default_block() {
+        let mock_server = setup_mock_server().await;
+        let pending = StarknetBlockId::Tag(BlockTag::Pending);
+        mock_compute_starknet_address_at(pending).mount(&mock_server).await;
+        mock_native_balance_at(SENDER_STARKNET_ADDRESS, felt!("0x2a"), pending).expect(1).mount(&mock_server).await;
+        let config =
+            StarknetConfig { default_block: BlockNumberOrTag::Pending, ..mock_starknet_config(&mock_server.uri()) };
+        let kakarot_rpc = KakarotEthRpc::new(Arc::new(KakarotClient::new(config).unwrap()));
+
+        let balance = kakarot_rpc.balance(H160::from_str(SENDER_ADDRESS).unwrap(), None).await.unwrap();
+
+        assert_eq!(balance, U256::from(0x2a));
+    }
+
+    #[tokio::test]
+    async fn test_kakarot_estimate_gas_flags_the_deployment_of_the_sender_account() {
+        // The account of the sender is deployed once it has a nonce
+        for (nonce_response, includes_account_deployment) in [
+            (json!({ "result": "0x1" }), false),
+            (json!({ "error": { "code": 20, "message": "Contract not found" } }), true),
+        ] {
+            let mock_server = setup_mock_server().await;
+            mock_compute_starknet_address().mount(&mock_server).await;
+            let mut nonce_body = json!({ "jsonrpc": "2.0", "id": 1 });
+            nonce_body.as_object_mut().unwrap().extend(nonce_response.as_object().unwrap().clone());
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({ "method": "starknet_getNonce" })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(nonce_body))
+                // A single nonce read per estimate
+                .expect(2)
+                .with_priority(1)
+                .mount(&mock_server)
+                .await;
+            mock_native_balance_at(SENDER_STARKNET_ADDRESS, felt!("0x0"), StarknetBlockId::Tag(BlockTag::Latest))
+                .mount(&mock_server)
+                .await;
+            let kakarot_client: Arc<dyn KakarotProvider> =
+                Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap());
+            let module = KakarotRpcModuleBuilder::new(kakarot_client).build().unwrap();
+
+            let estimate: GasEstimate =
+                module.call("kakarot_estimateGas", rpc_params![json!({ "from": SENDER_ADDRESS })]).await.unwrap();
+
+            // Deploying the account costs an overhead
+            let expected_gas =
+                if includes_account_deployment { *ESTIMATE_GAS + *ACCOUNT_DEPLOYMENT_GAS } else { *ESTIMATE_GAS };
+            assert_eq!(estimate, GasEstimate { gas: expected_gas, includes_account_deployment });
+            let gas: U256 =
+                module.call("eth_estimateGas", rpc_params![json!({ "from": SENDER_ADDRESS })]).await.unwrap();
+            assert_eq!(gas, expected_gas);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_balance_changes_report_only_kakarot_accounts() {
+        let mock_server = setup_mock_server().await;
+        // The fee of the transaction is sent by a Kakarot account to the sequencer
+        let sender = felt!("0x050ae4e217f2763897dcf278267ce97e83b2564aa24fa92a1d337ceb8544b74b");
+        let sequencer = felt!("0x05ce143edbf39a5e450d0bdf68f4e6b515405d1e9ab89329efa75fd8a31d9487");
+        mock_class_hash_at(sender, PROXY_ACCOUNT_CLASS_HASH).mount(&mock_server).await;
+        mock_evm_address_of(sender, felt!("0xabde1")).mount(&mock_server).await;
+        mock_class_hash_at(sequencer, felt!("0x5e9")).mount(&mock_server).await;
+        let kakarot_rpc =
+            KakarotEthRpc::new(Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap()));
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        let changes = kakarot_rpc.balance_changes(hash).await.unwrap().unwrap();
+
+        assert_eq!(
+            changes,
+            vec![BalanceChange {
+                address: H160::from_low_u64_be(0xabde1),
+                received: U256::ZERO,
+                sent: U256::from(0x04514f14cba800u64),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_balance_changes_propagate_upstream_errors() {
+        let mock_server = setup_mock_server().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "starknet_getClassHashAt" })))
+            .respond_with(ResponseTemplate::new(500))
+            .with_priority(1)
+            .mount(&mock_server)
+            .await;
+        let kakarot_rpc =
+            KakarotEthRpc::new(Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap()));
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        assert!(kakarot_rpc.balance_changes(hash).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_get_block_by_hash_hydrated_is_ok() {
         let kakarot_rpc = setup_kakarot_eth_rpc().await;
@@ -863,6 +1112,7 @@ mod tests {
 
         assert_block(&block, starknet_res.to_string(), starknet_txs.to_string(), true);
         assert_block_header(&block, starknet_res.to_string(), true);
+        assert_conforms(&block, BLOCK);
     }
 
     #[tokio::test]
@@ -901,6 +1151,7 @@ mod tests {
 
         assert_block(&block, starknet_res.to_string(), starknet_txs.to_string(), false);
         assert_block_header(&block, starknet_res.to_string(), false);
+        assert_conforms(&block, BLOCK);
     }
 
     #[tokio::test]
@@ -951,18 +1202,26 @@ mod tests {
 
         assert_eq!(transaction_receipt.from, H160::from_str("0x54b288676b749def5fc10eb17244fe2c87375de1").unwrap());
 
-        // TODO
-        // assert_eq!(transaction_receipt.logs, None);
-        // assert_eq!(transaction_receipt.contract_address, Some(U64::from(1)));
+        // The transaction is the first of its block
+        assert_eq!(transaction_receipt.transaction_index, Some(U256::ZERO));
+
+        // The transaction deploys a contract, so it has no recipient
+        assert_eq!(transaction_receipt.to, None);
+        assert!(transaction_receipt.contract_address.is_some());
 
-        // assert_eq!(transaction_receipt.transaction_index, None);
-        // assert_eq!(transaction_receipt.to, None);
-        // assert_eq!(transaction_receipt.cumulative_gas_used, U256::from(1000000));
-        // assert_eq!(transaction_receipt.gas_used, None);
-        // assert_eq!(transaction_receipt.logs_bloom, Bloom::default());
-        // assert_eq!(transaction_receipt.state_root, None);
-        // assert_eq!(transaction_receipt.effective_gas_price, U128::from(1000000));
-        // assert_eq!(transaction_receipt.transaction_type, U256::from(0));
+        // The only event of the fixture receipt is the fee transfer, which Kakarot does not emit
+        assert!(transaction_receipt.logs.is_empty());
+        assert_eq!(transaction_receipt.logs_bloom, Bloom::default());
+        // The gas used is the fee paid at a gas price of 1 wei, and no transaction precedes it
+        // in its block
+        assert_eq!(transaction_receipt.gas_used, Some(U256::from(0x0451_4f14_cba8_00u64)));
+        assert_eq!(transaction_receipt.cumulative_gas_used, U256::from(0x0451_4f14_cba8_00u64));
+        assert_eq!(transaction_receipt.state_root, None);
+        assert_eq!(transaction_receipt.effective_gas_price, U128::from(1));
+        // The fixture transaction is an EIP-1559 transaction
+        assert_eq!(transaction_receipt.transaction_type, U8::from(2));
+
+        assert_conforms(&transaction_receipt, RECEIPT);
     }
 
     #[tokio::test]
@@ -977,10 +1236,8 @@ mod tests {
             kakarot_rpc.transaction_by_block_number_and_index(block_number, index).await.unwrap().unwrap();
 
         let starknet_tx = get_test_tx();
-        assert_transaction(
-            transaction.clone(),
-            serde_json::from_str::<StarknetTransaction>(&starknet_tx.to_string()).unwrap(),
-        );
+        let starknet_tx = serde_json::from_str::<StarknetTransaction>(&starknet_tx.to_string()).unwrap();
+        assert_transaction(transaction.inner.clone(), fixture_eth_transaction(&starknet_tx));
 
         assert_eq!(
             transaction.block_hash,
@@ -1001,11 +1258,9 @@ mod tests {
         let transaction = kakarot_rpc.transaction_by_block_hash_and_index(hash, index).await.unwrap().unwrap();
 
         let starknet_tx = get_test_tx();
-
-        assert_transaction(
-            transaction.clone(),
-            serde_json::from_str::<StarknetTransaction>(&starknet_tx.to_string()).unwrap(),
-        );
+        let starknet_tx = serde_json::from_str::<StarknetTransaction>(&starknet_tx.to_string()).unwrap();
+        assert_transaction(transaction.inner.clone(), fixture_eth_transaction(&starknet_tx));
+        assert_conforms(&transaction, TRANSACTION);
 
         assert_eq!(
             transaction.block_hash,
@@ -1013,4 +1268,789 @@ mod tests {
         );
         assert_eq!(U256::from(transaction.block_number.unwrap()), U256::from(13));
     }
+
+    #[tokio::test]
+    async fn test_rpc_module_builder_only_serves_enabled_namespaces() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client)
+            .disable(KakarotRpcNamespace::Debug)
+            .build()
+            .unwrap();
+        let methods: Vec<&str> = module.method_names().collect();
+
+        assert!(methods.contains(&"eth_blockNumber"));
+        assert!(methods.contains(&"eth_subscribe"));
+        assert!(methods.contains(&"kakarot_getTokenBalances"));
+        assert!(!methods.contains(&"debug_getRawTransaction"));
+    }
+
+    /// Posts a JSON-RPC request to `url`, with the bearer `token` if set.
+    async fn post_request(
+        url: String,
+        method: &str,
+        params: serde_json::Value,
+        token: Option<&str>,
+    ) -> reqwest::Response {
+        let request = reqwest::Client::new()
+            .post(url)
+            .body(json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string())
+            .header("content-type", "application/json");
+        match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+        .send()
+        .await
+        .unwrap()
+    }
+
+    /// Calls a JSON-RPC method at `url`, with the bearer `token` if set, returning the response.
+    async fn call(url: String, method: &str, params: serde_json::Value, token: Option<&str>) -> serde_json::Value {
+        serde_json::from_str(&post_request(url, method, params, token).await.text().await.unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_kakarot_metadata_is_only_served_to_the_requests_opting_in() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+        let handle = KakarotRpcServerBuilder::new(kakarot_rpc.kakarot_client).build().unwrap().start().unwrap();
+        let block_hash = "0x449aa33ad836b65b10fa60082de99e24ac876ee2fd93e723a99190a530af0a9";
+        let params = json!([format!("0x{:0>64}", &block_hash[2..]), true]);
+
+        let response =
+            call(format!("http://{}", handle.local_addr()), "eth_getBlockByHash", params.clone(), None).await;
+        assert!(response["result"]["hash"].is_string());
+        assert!(response["result"].get(KAKAROT_METADATA_KEY).is_none());
+
+        let url = format!("http://{}/?{KAKAROT_METADATA_QUERY}", handle.local_addr());
+        let response = call(url, "eth_getBlockByHash", params, None).await;
+        assert_eq!(response["result"][KAKAROT_METADATA_KEY]["block_hash"], json!(block_hash));
+
+        handle.stop();
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_runtime_shutdown_cancels_its_tasks() {
+        let runtime = SubscriptionsRuntime::spawn(1).unwrap();
+        let (_never_sent, never_received) = tokio::sync::oneshot::channel::<()>();
+        let (dropped_with_the_task, task_dropped) = tokio::sync::oneshot::channel::<()>();
+        runtime.handle().spawn(async move {
+            let _dropped_with_the_task = dropped_with_the_task;
+            let _ = never_received.await;
+        });
+
+        runtime.shutdown();
+
+        let dropped = tokio::time::timeout(Duration::from_secs(5), task_dropped).await.unwrap();
+        assert!(dropped.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stopped_server_waits_for_its_subscriptions_runtime() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+        let handle = KakarotRpcServerBuilder::new(kakarot_rpc.kakarot_client)
+            .with_subscription_worker_threads(1)
+            .build()
+            .unwrap()
+            .start()
+            .unwrap();
+
+        handle.stop();
+
+        // The runtime thread exits once shut down, or `stopped` would never complete
+        tokio::time::timeout(Duration::from_secs(5), handle.stopped()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_namespace_is_served_on_its_own_authenticated_listener() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+        let handle = KakarotRpcServerBuilder::new(kakarot_rpc.kakarot_client)
+            .with_admin("127.0.0.1:0", "secret")
+            .build()
+            .unwrap()
+            .start()
+            .unwrap();
+        let admin_url = format!("http://{}", handle.admin_local_addr().unwrap());
+        let address = H160::from_low_u64_be(1);
+
+        // The public listener does not serve the admin namespace
+        let response =
+            call(format!("http://{}", handle.local_addr()), "admin_watchAddress", json!([address]), None).await;
+        assert_eq!(response["error"]["code"], json!(-32601));
+
+        // The admin listener only serves the requests presenting the token
+        for token in [None, Some("wrong")] {
+            let response = post_request(admin_url.clone(), "admin_watchAddress", json!([address]), token).await;
+            assert_eq!(response.status(), reqwest::StatusCode::UNAUTHORIZED);
+        }
+
+        let response = call(admin_url.clone(), "admin_watchAddress", json!([address]), Some("secret")).await;
+        assert_eq!(response["result"], json!(true));
+        let response = call(admin_url.clone(), "admin_getWatchedAddresses", json!([]), Some("secret")).await;
+        assert_eq!(response["result"], json!([address]));
+
+        handle.stop();
+        handle.stopped().await;
+    }
+
+    #[tokio::test]
+    async fn test_admin_namespace_manages_watched_addresses() {
+        let starknet_rpc = setup_mock_server().await;
+        let config = StarknetConfig { max_watched_addresses: 1, ..mock_starknet_config(&starknet_rpc.uri()) };
+        let module = KakarotAdminRpc::new(Arc::new(KakarotClient::new(config).unwrap())).into_rpc();
+        let address = H160::from_low_u64_be(1);
+
+        let watched: bool = module.call("admin_watchAddress", rpc_params![address]).await.unwrap();
+        assert!(watched);
+        let addresses: Vec<H160> = module.call("admin_getWatchedAddresses", rpc_params![]).await.unwrap();
+        assert_eq!(addresses, vec![address]);
+        let activity: Option<Vec<serde_json::Value>> =
+            module.call("admin_getAddressActivity", rpc_params![address]).await.unwrap();
+        assert_eq!(activity, Some(vec![]));
+
+        // The watchlist is full
+        let err =
+            module.call::<_, bool>("admin_watchAddress", rpc_params![H160::from_low_u64_be(2)]).await.unwrap_err();
+        assert!(matches!(err, jsonrpsee::core::Error::Call(err) if err.code() == -32005));
+
+        let unwatched: bool = module.call("admin_unwatchAddress", rpc_params![address]).await.unwrap();
+        assert!(unwatched);
+        let activity: Option<Vec<serde_json::Value>> =
+            module.call("admin_getAddressActivity", rpc_params![address]).await.unwrap();
+        assert_eq!(activity, None);
+    }
+
+    #[tokio::test]
+    async fn test_admin_subscription_pushes_added_and_removed_activities() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+        let kakarot_client = kakarot_rpc.kakarot_client;
+        let module = KakarotAdminRpc::new(kakarot_client.clone()).into_rpc();
+        let mut subscription =
+            module.subscribe_unbounded("admin_subscribeAddressActivity", rpc_params![]).await.unwrap();
+        let activity = AddressActivity {
+            address: H160::from_low_u64_be(1),
+            block_hash: H256::from_low_u64_be(2),
+            block_number: U64::from(2),
+            transaction_hash: H256::from_low_u64_be(3),
+        };
+
+        // Only the events about the watched addresses are pushed
+        kakarot_client.events().emit(ClientEvent::ReorgDetected { from_block: 1 });
+        kakarot_client.events().emit(ClientEvent::AddressActivity(activity.clone()));
+        kakarot_client.events().emit(ClientEvent::AddressActivityReverted(activity.clone()));
+
+        let (event, _) = subscription.next::<serde_json::Value>().await.unwrap().unwrap();
+        assert_eq!(event["type"], json!("added"));
+        assert_eq!(serde_json::from_value::<WatchlistEvent>(event).unwrap(), WatchlistEvent::Added(activity.clone()));
+        let (event, _) = subscription.next::<WatchlistEvent>().await.unwrap().unwrap();
+        assert_eq!(event, WatchlistEvent::Removed(activity));
+    }
+
+    /// Returns the pubsub module of a client following the chain of `mock_server`.
+    fn setup_pubsub(mock_server: &MockServer) -> (Arc<dyn KakarotProvider>, jsonrpsee::RpcModule<KakarotEthPubSubRpc>) {
+        let kakarot_client: Arc<dyn KakarotProvider> =
+            Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap());
+        let subscriptions = SubscriptionManager::new(kakarot_client.clone(), Duration::from_millis(10), None);
+        let mut module = EthPubSubApiServer::into_rpc(KakarotEthPubSubRpc::new(subscriptions.clone()));
+        module.merge(KakarotPubSubApiServer::into_rpc(KakarotEthPubSubRpc::new(subscriptions))).unwrap();
+        (kakarot_client, module)
+    }
+
+    fn log_of(address: H160) -> Log {
+        Log {
+            address,
+            topics: vec![H256::from_low_u64_be(1)],
+            data: Bytes::default(),
+            block_hash: Some(H256::from_low_u64_be(2)),
+            block_number: Some(U256::from(2)),
+            transaction_hash: Some(H256::from_low_u64_be(3)),
+            transaction_index: Some(U256::ZERO),
+            log_index: Some(U256::ZERO),
+            removed: false,
+        }
+    }
+
+    /// Returns the event of the block 2 followed, with a transaction emitting `logs`.
+    fn followed_block_with(logs: Vec<Log>) -> ClientEvent {
+        let header = Header {
+            hash: Some(H256::from_low_u64_be(2)),
+            parent_hash: H256::from_low_u64_be(1),
+            uncles_hash: H256::zero(),
+            miner: H160::zero(),
+            state_root: H256::zero(),
+            transactions_root: H256::zero(),
+            receipts_root: H256::zero(),
+            number: Some(U256::from(2)),
+            gas_used: U256::ZERO,
+            gas_limit: U256::ZERO,
+            extra_data: Bytes::default(),
+            logs_bloom: Bloom::default(),
+            timestamp: U256::ZERO,
+            difficulty: U256::ZERO,
+            nonce: None,
+            base_fee_per_gas: None,
+            mix_hash: H256::zero(),
+            withdrawals_root: None,
+        };
+        let block = Block {
+            header,
+            total_difficulty: None,
+            uncles: vec![],
+            transactions: BlockTransactions::Hashes(vec![H256::from_low_u64_be(3)]),
+            size: None,
+            withdrawals: None,
+        };
+        ClientEvent::NewBlock {
+            block: block.into(),
+            receipts: vec![TransactionReceipt { logs, ..create_default_transaction_receipt() }],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_new_heads_subscription_pushes_the_followed_blocks() {
+        let mock_server = MockServer::start().await;
+        mock_events(json!({}), json!([])).mount(&mock_server).await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "starknet_blockNumber" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": 1, "result": 1 })))
+            .mount(&mock_server)
+            .await;
+        for mock in mock_chain_block(1, felt!("0x1"), felt!("0x0")) {
+            mock.mount(&mock_server).await;
+        }
+        let (kakarot_client, module) = setup_pubsub(&mock_server);
+
+        let mut subscription = module.subscribe_unbounded("eth_subscribe", rpc_params!["newHeads"]).await.unwrap();
+
+        let (header, _) = subscription.next::<serde_json::Value>().await.unwrap().unwrap();
+        assert_eq!(header["number"], json!("0x1"));
+        assert_eq!(header["hash"], json!(H256::from_low_u64_be(1)));
+        assert_eq!(header["parentHash"], json!(H256::zero()));
+        kakarot_client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_logs_subscription_pushes_the_matching_logs() {
+        // The node has no chain, only the events broadcast below are pushed
+        let mock_server = MockServer::start().await;
+        let (kakarot_client, module) = setup_pubsub(&mock_server);
+        let address = H160::from_low_u64_be(1);
+
+        let mut subscription = module
+            .subscribe_unbounded("eth_subscribe", rpc_params!["logs", json!({ "address": address })])
+            .await
+            .unwrap();
+        kakarot_client.events().emit(followed_block_with(vec![log_of(H160::from_low_u64_be(2))]));
+        kakarot_client.events().emit(ClientEvent::ReorgDetected { from_block: 1 });
+        kakarot_client.events().emit(followed_block_with(vec![log_of(H160::from_low_u64_be(2)), log_of(address)]));
+
+        let (log, _) = subscription.next::<Log>().await.unwrap().unwrap();
+        assert_eq!(log, log_of(address));
+        kakarot_client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_logs_subscription_removes_the_logs_of_the_replaced_blocks() {
+        let mock_server = MockServer::start().await;
+        let (kakarot_client, module) = setup_pubsub(&mock_server);
+        let address = H160::from_low_u64_be(1);
+
+        let mut subscription = module.subscribe_unbounded("eth_subscribe", rpc_params!["logs"]).await.unwrap();
+        kakarot_client.events().emit(followed_block_with(vec![log_of(address)]));
+        // The block of the log is replaced
+        kakarot_client.events().emit(ClientEvent::ReorgDetected { from_block: 2 });
+
+        let (log, _) = subscription.next::<Log>().await.unwrap().unwrap();
+        assert_eq!(log, log_of(address));
+        let (log, _) = subscription.next::<Log>().await.unwrap().unwrap();
+        assert_eq!(log, Log { removed: true, ..log_of(address) });
+        kakarot_client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_resubscription_replays_the_blocks_from_the_requested_one() {
+        let mock_server = MockServer::start().await;
+        mock_events(json!({}), json!([])).mount(&mock_server).await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "starknet_blockNumber" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "jsonrpc": "2.0", "id": 1, "result": 2 })))
+            .mount(&mock_server)
+            .await;
+        for (number, hash, parent_hash) in [(1, felt!("0x1"), felt!("0x0")), (2, felt!("0x2"), felt!("0x1"))] {
+            for mock in mock_chain_block(number, hash, parent_hash) {
+                mock.mount(&mock_server).await;
+            }
+        }
+        let (kakarot_client, module) = setup_pubsub(&mock_server);
+
+        let mut subscription =
+            module.subscribe_unbounded("kakarot_resubscribeFrom", rpc_params![U64::from(1), "newHeads"]).await.unwrap();
+
+        for number in ["0x1", "0x2"] {
+            let (header, _) = subscription.next::<serde_json::Value>().await.unwrap().unwrap();
+            assert_eq!(header["number"], json!(number));
+        }
+        // The block 2, followed meanwhile, is not pushed again
+        let next = tokio::time::timeout(Duration::from_millis(200), subscription.next::<serde_json::Value>()).await;
+        assert!(next.is_err());
+        kakarot_client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_resubscription_from_beyond_the_replayed_blocks_is_rejected() {
+        let mock_server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "starknet_blockNumber" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": MAX_BLOCK_RANGE + 1
+            })))
+            .mount(&mock_server)
+            .await;
+        let (kakarot_client, module) = setup_pubsub(&mock_server);
+
+        let subscription =
+            module.subscribe_unbounded("kakarot_resubscribeFrom", rpc_params![U64::from(1), "newHeads"]).await;
+
+        assert!(subscription.is_err());
+        kakarot_client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscription_is_closed() {
+        let mock_server = MockServer::start().await;
+        let (kakarot_client, module) = setup_pubsub(&mock_server);
+        let address = H160::from_low_u64_be(1);
+
+        let mut subscription = module.subscribe_unbounded("eth_subscribe", rpc_params!["logs"]).await.unwrap();
+        // The subscription does not run until the test awaits, so it misses the first block
+        for _ in 0..=EVENTS_CAPACITY {
+            kakarot_client.events().emit(followed_block_with(vec![log_of(address)]));
+        }
+
+        // The subscription is closed with an error instead of pushing the logs left
+        assert!(subscription.next::<Log>().await.is_none());
+        kakarot_client.follower().stop();
+    }
+
+    #[test]
+    fn test_config_schema_documents_every_variable() {
+        let schema = config_schema();
+
+        assert_eq!(
+            schema["required"],
+            json!(["KAKAROT_HTTP_RPC_ADDRESS", "STARKNET_RPC_URL", "KAKAROT_ADDRESS", "PROXY_ACCOUNT_CLASS_HASH"])
+        );
+        assert_eq!(schema["properties"]["STARKNET_EVENTS_PAGE_SIZE"]["default"], json!("1000"));
+        assert_eq!(schema["properties"]["KAKAROT_CONFIRMATION_LEVEL"]["enum"][0], json!("l2"));
+        assert!(schema["properties"]["KAKAROT_WRAPPED_NATIVE_TOKEN"]["default"].is_null());
+        assert_eq!(schema["properties"]["KAKAROT_DEFAULT_BLOCK"]["default"], json!("latest"));
+    }
+
+    /// The environment variables read by `RPCConfig::from_env` and `StarknetConfig::from_env`,
+    /// found in their sources so that reading a new variable cannot go undocumented.
+    fn env_vars_read() -> BTreeSet<String> {
+        let sources = [include_str!("../src/config.rs"), include_str!("../../core/src/client/config.rs")];
+        let mut names = BTreeSet::new();
+        for source in sources {
+            // The variables are named as the first argument of the readers, e.g. `std::env::var("…")`
+            let from_env = &source[source.find("pub fn from_env").unwrap()..];
+            for (start, _) in from_env.match_indices("(\"") {
+                let rest = &from_env[start + 2..];
+                let name: String =
+                    rest.chars().take_while(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || *c == '_').collect();
+                if name.len() > 1 && rest[name.len()..].starts_with('"') {
+                    names.insert(name);
+                }
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn test_config_schema_documents_the_variables_read() {
+        let schema = config_schema();
+        let documented: BTreeSet<String> = schema["properties"].as_object().unwrap().keys().cloned().collect();
+
+        let read = env_vars_read();
+        assert!(read.contains("KAKAROT_HTTP_RPC_ADDRESS") && read.contains("STARKNET_RPC_URL"));
+        assert_eq!(documented, read);
+    }
+
+    #[test]
+    fn test_rpc_config_url_is_reachable_when_listening_on_every_interface() {
+        assert_eq!(RPCConfig::new("0.0.0.0:3030".to_string()).url(), "http://127.0.0.1:3030");
+        assert_eq!(RPCConfig::new("[::]:3030".to_string()).url(), "http://[::1]:3030");
+        assert_eq!(RPCConfig::new("10.0.0.1:3030".to_string()).url(), "http://10.0.0.1:3030");
+        assert_eq!(RPCConfig::new("rpc.internal:3030".to_string()).url(), "http://rpc.internal:3030");
+    }
+
+    #[tokio::test]
+    async fn test_txpool_is_served_from_pending_block() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client).build().unwrap();
+
+        // The mocked node has no pending block, so the pool is empty
+        let status: TxpoolStatus = module.call("txpool_status", rpc_params![]).await.unwrap();
+        assert_eq!(status, TxpoolStatus::default());
+        let content: TxpoolContent = module.call("txpool_content", rpc_params![]).await.unwrap();
+        assert_eq!(content, TxpoolContent::default());
+        let pending_transactions: Vec<serde_json::Value> =
+            module.call("eth_pendingTransactions", rpc_params![]).await.unwrap();
+        assert!(pending_transactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transactions_submitted_to_the_node_by_others_are_served_as_pending() {
+        let mock_server = setup_mock_server().await;
+        // The transaction enters the pending block of the node without going through the adapter
+        mock_pending_block(vec![fixture_transaction()]).mount(&mock_server).await;
+        let kakarot_client: Arc<dyn KakarotProvider> =
+            Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap());
+        let hash = H256::from_str("0x03204b4c0e379c3a5ccb80d08661d5a538e95e2960581c9faf7ebcf8ff5a7d3c").unwrap();
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_client).build().unwrap();
+
+        let status: TxpoolStatus = module.call("txpool_status", rpc_params![]).await.unwrap();
+        assert_eq!(status.pending, U64::from(1));
+        let content: TxpoolContent = module.call("txpool_content", rpc_params![]).await.unwrap();
+        let pooled: Vec<H256> =
+            content.pending.values().flat_map(|by_nonce| by_nonce.values()).map(|tx| tx.hash).collect();
+        assert_eq!(pooled, vec![hash]);
+        let pending_transactions: Vec<serde_json::Value> =
+            module.call("eth_pendingTransactions", rpc_params![]).await.unwrap();
+        assert_eq!(pending_transactions.len(), 1);
+        assert_eq!(pending_transactions[0]["hash"], json!(format!("{hash:#x}")));
+    }
+
+    #[tokio::test]
+    async fn test_net_namespace_reports_chain_id_and_upstream() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+        let chain_id = kakarot_rpc.kakarot_client.chain_id();
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client).build().unwrap();
+
+        let version: String = module.call("net_version", rpc_params![]).await.unwrap();
+        assert_eq!(version, chain_id.to_string());
+        // The mocked Starknet node answers, so it is counted as the only peer
+        let listening: bool = module.call("net_listening", rpc_params![]).await.unwrap();
+        assert!(listening);
+        let peer_count: U64 = module.call("net_peerCount", rpc_params![]).await.unwrap();
+        assert_eq!(peer_count, U64::from(1));
+    }
+
+    #[tokio::test]
+    async fn test_net_namespace_reports_upstream_going_back_beyond_a_reorg() {
+        let mock_server = setup_mock_server().await;
+        // The node serves 19640, then is reset to 100
+        for (priority, block_number) in [(1, 19640), (2, 100)] {
+            Mock::given(method("POST"))
+                .and(body_partial_json(json!({ "method": "starknet_blockNumber" })))
+                .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "result": block_number
+                })))
+                .up_to_n_times(1)
+                .with_priority(priority)
+                .mount(&mock_server)
+                .await;
+        }
+        let kakarot_client: Arc<dyn KakarotProvider> =
+            Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap());
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_client.clone()).build().unwrap();
+
+        let listening: bool = module.call("net_listening", rpc_params![]).await.unwrap();
+        assert!(listening);
+        let peer_count: U64 = module.call("net_peerCount", rpc_params![]).await.unwrap();
+        assert_eq!(peer_count, U64::ZERO);
+        assert_eq!(kakarot_client.block_guard().inconsistencies(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_net_namespace_reports_a_follower_lagging_beyond_the_maximum() {
+        let mock_server = MockServer::start().await;
+        // The node is at block 3, which it fails to serve, so the follower stays a block behind
+        Mock::given(method("POST"))
+            .and(body_partial_json(json!({ "method": "starknet_blockNumber" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": 3
+            })))
+            .mount(&mock_server)
+            .await;
+        let kakarot_client: Arc<dyn KakarotProvider> =
+            Arc::new(KakarotClient::new(mock_starknet_config(&mock_server.uri())).unwrap());
+        kakarot_client.follower().start(kakarot_client.clone(), Duration::from_millis(10), None);
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while kakarot_client.follower().lag() != Some(1) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        let strict = KakarotRpcModuleBuilder::new(kakarot_client.clone()).with_max_follower_lag(0).build().unwrap();
+        let lenient = KakarotRpcModuleBuilder::new(kakarot_client.clone()).with_max_follower_lag(1).build().unwrap();
+
+        let listening: bool = strict.call("net_listening", rpc_params![]).await.unwrap();
+        assert!(!listening);
+        let peer_count: U64 = strict.call("net_peerCount", rpc_params![]).await.unwrap();
+        assert_eq!(peer_count, U64::ZERO);
+        let listening: bool = lenient.call("net_listening", rpc_params![]).await.unwrap();
+        assert!(listening);
+        kakarot_client.follower().stop();
+    }
+
+    #[tokio::test]
+    async fn test_web3_namespace() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client).build().unwrap();
+
+        let client_version: String = module.call("web3_clientVersion", rpc_params![]).await.unwrap();
+        assert!(client_version.starts_with(&format!("kakarot-rpc/v{}", env!("CARGO_PKG_VERSION"))));
+        let hash: H256 = module.call("web3_sha3", rpc_params!["0x68656c6c6f"]).await.unwrap();
+        assert_eq!(hash, H256::from_str("0x1c8aff950685c2ed4bc3174f3472287b56d9517b9c948127319a09a7a36deac8").unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_support_matrix_lists_registered_methods() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client.clone())
+            .with_method_aliases([("parity_sign".to_string(), "eth_sign".to_string())])
+            .build()
+            .unwrap();
+
+        let modules: serde_json::Value = module.call("rpc_modules", rpc_params![]).await.unwrap();
+        assert_eq!(modules["eth"], json!("1.0"));
+        assert_eq!(modules["rpc"], json!("1.0"));
+        assert!(modules["admin"].is_null());
+
+        let methods: Vec<SupportedMethod> = module.call("kakarot_supportedMethods", rpc_params![]).await.unwrap();
+        assert_eq!(methods.len(), module.method_names().count());
+        for stub in STUB_METHODS.iter().chain(&["parity_sign"]) {
+            assert!(methods.iter().any(|method| method.name == *stub && method.stub), "{stub} is not a stub");
+        }
+        assert!(methods.iter().any(|method| method.name == "eth_chainId" && !method.stub));
+
+        // The support matrix is served by the kakarot namespace
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client)
+            .with_namespaces([KakarotRpcNamespace::Eth])
+            .build()
+            .unwrap();
+        assert!(module.method_names().any(|method| method == "rpc_modules"));
+        assert!(!module.method_names().any(|method| method == "kakarot_supportedMethods"));
+    }
+
+    #[tokio::test]
+    async fn test_stub_methods_are_not_found() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client).build().unwrap();
+
+        let hash = H256::zero();
+        let address = H160::zero();
+        let calls = [
+            ("eth_coinbase", json!([])),
+            ("eth_getUncleCountByBlockHash", json!([hash])),
+            ("eth_getUncleCountByBlockNumber", json!(["latest"])),
+            ("eth_getUncleByBlockHashAndIndex", json!([hash, "0x0"])),
+            ("eth_getUncleByBlockNumberAndIndex", json!(["latest", "0x0"])),
+            ("eth_createAccessList", json!([{ "to": address }])),
+            ("eth_mining", json!([])),
+            ("eth_hashrate", json!([])),
+            ("eth_getWork", json!([])),
+            ("eth_submitHashrate", json!(["0x1", hash])),
+            ("eth_submitWork", json!(["0x0000000000000001", hash, hash])),
+            ("eth_sendTransaction", json!([{ "from": address }])),
+            ("eth_sign", json!([address, "0x"])),
+            ("eth_signTransaction", json!([{ "from": address }])),
+            ("eth_signTypedData", json!([address, {}])),
+            ("eth_getProof", json!([address, [], "latest"])),
+        ];
+        let mut called: Vec<&str> = calls.iter().map(|(method, _)| *method).collect();
+        called.sort_unstable();
+        let mut stubs = STUB_METHODS.to_vec();
+        stubs.sort_unstable();
+        assert_eq!(called, stubs);
+
+        for (method, params) in calls {
+            let mut array_params = ArrayParams::new();
+            for param in params.as_array().unwrap() {
+                array_params.insert(param).unwrap();
+            }
+            let err = module.call::<_, serde_json::Value>(method, array_params).await.unwrap_err();
+            assert!(
+                matches!(&err, jsonrpsee::core::Error::Call(err) if err.code() == METHOD_NOT_FOUND_CODE),
+                "{method} answered {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rpc_module_serves_method_aliases() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let module = KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client.clone())
+            .with_method_aliases([("parity_chainId".to_string(), "eth_chainId".to_string())])
+            .build()
+            .unwrap();
+        let methods: Vec<&str> = module.method_names().collect();
+        assert!(methods.contains(&"parity_chainId"));
+        assert!(methods.contains(&"eth_chainId"));
+
+        // Aliases of methods which are not served are rejected
+        assert!(
+            KakarotRpcModuleBuilder::new(kakarot_rpc.kakarot_client)
+                .with_namespaces([KakarotRpcNamespace::Eth])
+                .with_method_aliases([("trace_transaction".to_string(), "debug_getRawTransaction".to_string())])
+                .build()
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embedded_server_serves_requests_until_stopped() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let server = KakarotRpcServerBuilder::new(kakarot_rpc.kakarot_client)
+            .with_namespaces([KakarotRpcNamespace::Eth])
+            .build()
+            .unwrap();
+        let local_addr = server.local_addr();
+        assert_ne!(local_addr.port(), 0);
+
+        let handle = server.start().unwrap();
+        assert_eq!(handle.local_addr(), local_addr);
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{local_addr}"))
+            .body(json!({ "jsonrpc": "2.0", "id": 1, "method": "eth_chainId", "params": [] }).to_string())
+            .header("content-type", "application/json")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert!(response["result"].is_string());
+
+        handle.stop();
+        handle.stopped().await;
+    }
+
+    #[tokio::test]
+    async fn test_embedded_server_rejects_oversized_requests() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let handle = KakarotRpcServerBuilder::new(kakarot_rpc.kakarot_client)
+            .with_max_request_size(16)
+            .build()
+            .unwrap()
+            .start()
+            .unwrap();
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}", handle.local_addr()))
+            .body(json!({ "jsonrpc": "2.0", "id": 1, "method": "eth_chainId", "params": [] }).to_string())
+            .header("content-type", "application/json")
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        let response: serde_json::Value = serde_json::from_str(&response).unwrap();
+        assert!(response["error"].is_object());
+
+        handle.stop();
+    }
+
+    #[tokio::test]
+    async fn test_embedded_server_rejects_zero_limits() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let res = KakarotRpcServerBuilder::new(kakarot_rpc.kakarot_client).with_max_connections(0).build();
+
+        let err = res.err().expect("a server without connections is rejected");
+        assert_eq!(err.to_string(), "max_connections should be greater than 0");
+    }
+
+    #[tokio::test]
+    async fn test_embedded_server_rate_limits_the_requests_of_a_connection() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let handle = KakarotRpcServerBuilder::new(kakarot_rpc.kakarot_client)
+            .with_max_requests_per_second(1)
+            .build()
+            .unwrap()
+            .start()
+            .unwrap();
+
+        // The client keeps its connection alive between the requests
+        let client = reqwest::Client::new();
+        let mut statuses = Vec::new();
+        let mut responses = Vec::new();
+        for _ in 0..2 {
+            let response = client
+                .post(format!("http://{}", handle.local_addr()))
+                .body(json!({ "jsonrpc": "2.0", "id": 1, "method": "eth_chainId", "params": [] }).to_string())
+                .header("content-type", "application/json")
+                .send()
+                .await
+                .unwrap();
+            statuses.push(response.status());
+            responses.push(serde_json::from_str::<serde_json::Value>(&response.text().await.unwrap()).unwrap());
+        }
+
+        assert_eq!(statuses[0], reqwest::StatusCode::OK);
+        assert!(responses[0]["result"].is_string());
+        assert_eq!(statuses[1], reqwest::StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(responses[1]["error"]["code"], -32005);
+
+        handle.stop();
+    }
+
+    #[tokio::test]
+    async fn test_block_range_exceeding_max_span_is_rejected() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+        let from_block = 19_000_u64;
+        let to_block = from_block + MAX_BLOCK_RANGE;
+
+        let res = kakarot_rpc.block_range(U64::from(from_block), U64::from(to_block), false).await;
+
+        assert!(res.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_block_filter_lifecycle() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        let id = kakarot_rpc.new_block_filter().await.unwrap();
+
+        assert!(kakarot_rpc.uninstall_filter(id).await.unwrap());
+        assert!(!kakarot_rpc.uninstall_filter(id).await.unwrap());
+        // Same error as geth
+        let err = kakarot_rpc.get_filter_changes(id).await.unwrap_err();
+        assert!(
+            matches!(err, jsonrpsee::core::Error::Call(err) if err.code() == -32000 && err.message() == "filter not found")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fee_history_rejects_invalid_reward_percentiles() {
+        let kakarot_rpc = setup_kakarot_eth_rpc().await;
+
+        for percentiles in [vec![50.0, 10.0], vec![-1.0], vec![101.0]] {
+            let err =
+                kakarot_rpc.fee_history(U256::from(1), BlockNumberOrTag::Latest, Some(percentiles)).await.unwrap_err();
+            assert!(matches!(err, jsonrpsee::core::Error::Call(err) if err.code() == -32602));
+        }
+    }
 }
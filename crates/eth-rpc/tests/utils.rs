@@ -1,8 +1,8 @@
+use std::sync::Arc;
+
 use kakarot_rpc::eth_rpc::KakarotEthRpc;
-use kakarot_rpc_core::client::config::StarknetConfig;
 use kakarot_rpc_core::client::KakarotClient;
-use kakarot_rpc_core::mock::wiremock_utils::setup_wiremock;
-use starknet::core::types::FieldElement;
+use kakarot_rpc_core::mock::wiremock_utils::{mock_starknet_config, setup_wiremock};
 
 /// Run wiremock to fake starknet rpc and then run our own `kakarot_rpc_server`.
 ///
@@ -31,13 +31,7 @@ use starknet::core::types::FieldElement;
 /// ```
 pub async fn setup_kakarot_eth_rpc() -> KakarotEthRpc {
     let starknet_rpc = setup_wiremock().await;
-    let kakarot_address =
-        FieldElement::from_hex_be("0x566864dbc2ae76c2d12a8a5a334913d0806f85b7a4dccea87467c3ba3616e75").unwrap();
-    let proxy_account_class_hash =
-        FieldElement::from_hex_be("0x0775033b738dfe34c48f43a839c3d882ebe521befb3447240f2d218f14816ef5").unwrap();
-
-    let kakarot_client =
-        KakarotClient::new(StarknetConfig::new(&starknet_rpc, kakarot_address, proxy_account_class_hash)).unwrap();
+    let kakarot_client = KakarotClient::new(mock_starknet_config(&starknet_rpc)).unwrap();
 
-    KakarotEthRpc::new(Box::new(kakarot_client))
+    KakarotEthRpc::new(Arc::new(kakarot_client))
 }